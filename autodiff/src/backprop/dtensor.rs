@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use tensor::primitives::tensor::*;
+use tensor::primitives::tensorview::{TensorView, ViewType};
 use tensor::topograph::GraphView;
 
 use crate::backprop::{BackPropable, BackPropagation, Gradients, TensorGradient};
@@ -47,12 +48,49 @@ impl BackPropable for UnarySpec {
             self.input.clone(),
             match self.op {
                 UnaryType::ABS => self.input.Sign(),
+                // -1 / sqrt(1 - x^2)
+                UnaryType::ACOS => Tensor::scalar(1)
+                    .Sub(&self.input.Multiply(&self.input))
+                    .Sqrt()
+                    .Recip()
+                    .Neg(),
+                // 1 / sqrt(x^2 - 1)
+                UnaryType::ACOSH => self
+                    .input
+                    .Multiply(&self.input)
+                    .Sub(&Tensor::scalar(1))
+                    .Sqrt()
+                    .Recip(),
+                // 1 / sqrt(1 - x^2)
+                UnaryType::ASIN => Tensor::scalar(1)
+                    .Sub(&self.input.Multiply(&self.input))
+                    .Sqrt()
+                    .Recip(),
+                // 1 / sqrt(x^2 + 1)
+                UnaryType::ASINH => self
+                    .input
+                    .Multiply(&self.input)
+                    .Add(&Tensor::scalar(1))
+                    .Sqrt()
+                    .Recip(),
+                // 1 / (1 + x^2)
+                UnaryType::ATAN => Tensor::scalar(1)
+                    .Add(&self.input.Multiply(&self.input))
+                    .Recip(),
+                // 1 / (1 - x^2)
+                UnaryType::ATANH => Tensor::scalar(1)
+                    .Sub(&self.input.Multiply(&self.input))
+                    .Recip(),
+                // Bitwise operations are piecewise constant
+                UnaryType::BITWISENOT => Tensor::scalar(0),
                 UnaryType::CAST => self.input.Equal(&output.Cast(self.input.datatype())),
                 UnaryType::CEIL => self.input.Equal(&output.Cast(self.input.datatype())),
                 // 2^x * ln(2)
                 UnaryType::EXP2 => Tensor::scalar(std::f32::consts::LN_2).Multiply(output),
                 UnaryType::FLOOR => self.input.Equal(&output.Cast(self.input.datatype())),
                 UnaryType::IDENTITY => Tensor::scalar(1),
+                UnaryType::ISINF => Tensor::scalar(0),
+                UnaryType::ISNAN => Tensor::scalar(0),
                 // 1/(xln(2))
                 UnaryType::LOG2 => Tensor::scalar(std::f32::consts::LN_2)
                     .Multiply(&self.input)
@@ -70,6 +108,18 @@ impl BackPropable for BinarySpec {
     fn backward(&self, output: &Tensor) -> Vec<TensorGradient> {
         let (lhs_gradient, rhs_gradient) = match self.op {
             BinaryType::ADD => (Tensor::scalar(1), Tensor::scalar(1)),
+            // atan2(y, x) = (x / (x^2 + y^2), -y / (x^2 + y^2))
+            BinaryType::ATAN2 => {
+                let radius = self
+                    .lhs
+                    .Multiply(&self.lhs)
+                    .Add(&self.rhs.Multiply(&self.rhs));
+                (self.rhs.Divide(&radius), self.lhs.Neg().Divide(&radius))
+            }
+            // Bitwise operations are piecewise constant
+            BinaryType::BITWISEAND => (Tensor::scalar(0), Tensor::scalar(0)),
+            BinaryType::BITWISEOR => (Tensor::scalar(0), Tensor::scalar(0)),
+            BinaryType::BITWISEXOR => (Tensor::scalar(0), Tensor::scalar(0)),
             BinaryType::DIVIDE => (
                 // 1/y
                 self.rhs.Recip(),
@@ -94,6 +144,8 @@ impl BackPropable for BinarySpec {
                 )
             }
             BinaryType::MULTIPLY => (self.rhs.clone(), self.lhs.clone()),
+            BinaryType::SHIFTLEFT => (Tensor::scalar(0), Tensor::scalar(0)),
+            BinaryType::SHIFTRIGHT => (Tensor::scalar(0), Tensor::scalar(0)),
             BinaryType::SUB => (Tensor::scalar(1), Tensor::scalar(-1)),
         };
 
@@ -104,34 +156,317 @@ impl BackPropable for BinarySpec {
     }
 }
 
+impl BackPropable for TernarySpec {
+    fn backward(&self, _: &Tensor) -> Vec<TensorGradient> {
+        let (lhs_gradient, rhs_gradient) = match self.op {
+            // Gradient flows only through the selected branch
+            TernaryType::SELECT => {
+                let selected = self.condition.Equal(&Tensor::scalar(0)).Not();
+                (selected.clone(), selected.Not())
+            }
+        };
+
+        vec![
+            TensorGradient::new(self.condition.clone(), Tensor::scalar(0)),
+            TensorGradient::new(self.lhs.clone(), lhs_gradient),
+            TensorGradient::new(self.rhs.clone(), rhs_gradient),
+        ]
+    }
+}
+
 impl BackPropable for ReduceSpec {
     fn backward(&self, output: &Tensor) -> Vec<TensorGradient> {
         vec![TensorGradient::new(
             self.input.clone(),
             match self.op {
                 ReduceType::SUM => Tensor::scalar(1),
-                ReduceType::MAX => {
+                // Product of every other element. Without zeros it is the product divided by the
+                // element, a single zero leaves it nonzero only for the zero itself
+                ReduceType::PROD => {
+                    let is_zero = self.input.Equal(&Tensor::scalar(0));
+                    let zeros = is_zero.Sum(&self.axes, true);
+                    let non_zero_product = is_zero
+                        .Where(&Tensor::scalar(1), &self.input)
+                        .Prod(&self.axes, true);
+
+                    zeros.Equal(&Tensor::scalar(0)).Where(
+                        &non_zero_product.Divide(&self.input),
+                        &zeros
+                            .Equal(&Tensor::scalar(1))
+                            .Multiply(&is_zero)
+                            .Where(&non_zero_product, &Tensor::scalar(0)),
+                    )
+                }
+                ReduceType::MAX | ReduceType::MIN => {
                     let output = if output.ndim() != self.input.ndim() {
                         // Squeezed so unsqueeze
-                        self.axes.iter().fold(output.clone(), |acc, &axis| acc.unsqueeze(axis))
+                        self.axes
+                            .iter()
+                            .fold(output.clone(), |acc, &axis| acc.unsqueeze(axis))
                     } else {
                         output.clone()
                     };
 
-                    // Gradient of 1 iff number is maximum
+                    // Gradient of 1 iff number is the extremum
                     output.Equal(&self.input)
                 }
+                // Boolean and index reductions are piecewise constant
+                ReduceType::ANY | ReduceType::ALL | ReduceType::ARGMAX | ReduceType::ARGMIN => {
+                    Tensor::scalar(0)
+                }
             },
         )]
     }
 }
 
+impl BackPropable for ScanSpec {
+    fn backward(&self, output: &Tensor) -> Vec<TensorGradient> {
+        vec![TensorGradient::new(
+            self.input.clone(),
+            match self.op {
+                // Every element contributes once to each output scanned in the opposite direction
+                ScanType::SUM => Tensor::scalar(1)
+                    .broadcast_to(&TensorView::from_contiguous_shape(self.input.shape()))
+                    .CumSum(self.axis, self.exclusive, !self.reverse),
+                // Sum of every product the element is part of with the element left out, each
+                // element scans its own copy of the line so the (possibly zero) element is never
+                // divided by
+                ScanType::PROD => {
+                    let length = self.input.shape()[self.axis as usize];
+                    let line_view = |axis: ViewType| {
+                        let shape = (0..=self.input.ndim())
+                            .map(|dim| if dim == axis { length } else { 1 })
+                            .collect::<Vec<_>>();
+                        TensorView::from_contiguous_shape(&shape)
+                    };
+
+                    let is_element = Tensor::arange(&[length])
+                        .reshape(&line_view(self.axis))
+                        .Equal(&Tensor::arange(&[length]).reshape(&line_view(self.axis + 1)));
+                    let products = is_element
+                        .Where(&Tensor::scalar(1), &self.input.unsqueeze(self.axis + 1))
+                        .CumProd(self.axis, self.exclusive, self.reverse);
+                    let contributes = is_element
+                        .Where(&Tensor::scalar(1), &Tensor::scalar(0))
+                        .CumSum(self.axis, self.exclusive, self.reverse);
+
+                    contributes
+                        .Equal(&Tensor::scalar(1))
+                        .Where(&products, &Tensor::scalar(0))
+                        .Sum(&[self.axis], false)
+                }
+                // Gradient of 1 iff number is the running maximum
+                ScanType::MAX => output.Equal(&self.input),
+            },
+        )]
+    }
+}
+
+impl BackPropable for SortSpec {
+    fn backward(&self, _: &Tensor) -> Vec<TensorGradient> {
+        vec![TensorGradient::new(
+            self.input.clone(),
+            match self.op {
+                // Every element is moved to exactly one output position
+                SortType::VALUES => Tensor::scalar(1),
+                // Indices are piecewise constant
+                SortType::INDICES => Tensor::scalar(0),
+            },
+        )]
+    }
+}
+
+impl BackPropable for MatMulSpec {
+    fn backward(&self, _: &Tensor) -> Vec<TensorGradient> {
+        let ndim = self.lhs.ndim();
+        vec![
+            // Every lhs element is multiplied with each element of its rhs row
+            TensorGradient::new(
+                self.lhs.clone(),
+                self.rhs.Sum(&[ndim - 1], false).unsqueeze(ndim - 2),
+            ),
+            // Every rhs element is multiplied with each element of its lhs column
+            TensorGradient::new(
+                self.rhs.clone(),
+                self.lhs.Sum(&[ndim - 2], false).unsqueeze(ndim - 1),
+            ),
+        ]
+    }
+}
+
+impl BackPropable for ConcatSpec {
+    fn backward(&self, _: &Tensor) -> Vec<TensorGradient> {
+        // Every output element is a passthrough of exactly one input element
+        self.inputs
+            .iter()
+            .map(|input| TensorGradient::new(input.clone(), Tensor::scalar(1)))
+            .collect::<Vec<_>>()
+    }
+}
+
+impl BackPropable for TakeSpec {
+    fn backward(&self, _: &Tensor) -> Vec<TensorGradient> {
+        vec![
+            // Every output element is a passthrough of the input element it indexes
+            TensorGradient::new(self.input.clone(), Tensor::scalar(1)),
+            // Indices are piecewise constant
+            TensorGradient::new(self.indices.clone(), Tensor::scalar(0)),
+        ]
+    }
+}
+
+impl BackPropable for AttentionSpec {
+    fn backward(&self, _: &Tensor) -> Vec<TensorGradient> {
+        let ndim = self.query.ndim();
+        let transposed = (0..ndim - 2)
+            .chain([ndim - 1, ndim - 2])
+            .collect::<Vec<_>>();
+        let scale = Tensor::scalar(self.scale);
+
+        // Each key j contributes the sum of its value row, weighted by the softmax
+        let weights = self.weights();
+        let value_sum = self.value.Sum(&[ndim - 1], false).unsqueeze(ndim - 2);
+        let expected_sum = weights.Multiply(&value_sum).Sum(&[ndim - 1], true);
+        let scores = weights.Multiply(&value_sum.Sub(&expected_sum));
+
+        let mut gradients = vec![
+            TensorGradient::new(
+                self.query.clone(),
+                scores.MatMul(&self.key).Multiply(&scale),
+            ),
+            TensorGradient::new(
+                self.key.clone(),
+                scores
+                    .transpose(&transposed)
+                    .MatMul(&self.query)
+                    .Multiply(&scale),
+            ),
+            // Every value element is weighted by the attention of each query on its key
+            TensorGradient::new(
+                self.value.clone(),
+                weights.Sum(&[ndim - 2], false).unsqueeze(ndim - 1),
+            ),
+        ];
+        // The mask is added to the scores before the softmax
+        if let Some(mask) = &self.mask {
+            gradients.push(TensorGradient::new(mask.clone(), scores));
+        }
+        gradients
+    }
+}
+
+impl BackPropable for LUSpec {
+    fn backward(&self, output: &Tensor) -> Vec<TensorGradient> {
+        let ndim = self.input.ndim();
+        let n = self.input.shape()[ndim as usize - 1];
+        let transposed = (0..ndim - 2)
+            .chain([ndim - 1, ndim - 2])
+            .collect::<Vec<_>>();
+
+        let parts = output.Split(ndim - 1, &[n, 1]);
+        let factors = &parts[0];
+        let permutation = parts[1].squeeze(ndim - 1).Cast(TensorType::I32);
+
+        let rows = Tensor::arange(&[n, 1]);
+        let columns = Tensor::arange(&[1, n]);
+        let mask = |mask: Tensor| mask.Cast(TensorType::F32).broadcast_to(factors.view());
+        let strict_lower = mask(columns.LessThan(&rows));
+        let upper = mask(rows.LessOrEqual(&columns));
+        let lower_factor = factors
+            .Multiply(&strict_lower)
+            .Add(&mask(rows.Equal(&columns)));
+        let upper_factor = factors.Multiply(&upper);
+
+        // Every strictly lower element of L and upper element of U is an output, so
+        // PA = LU gives L^-T (tril(L^T dL) + triu(dU U^T)) U^-T for PA
+        let factors_gradient = lower_factor
+            .transpose(&transposed)
+            .MatMul(&strict_lower)
+            .Multiply(&strict_lower)
+            .Add(
+                &upper
+                    .MatMul(&upper_factor.transpose(&transposed))
+                    .Multiply(&upper),
+            );
+        let gradient =
+            lower_factor
+                .transpose(&transposed)
+                .TriangularSolve(&factors_gradient, true, true);
+        let gradient = upper_factor
+            .TriangularSolve(&gradient.transpose(&transposed), true, false)
+            .transpose(&transposed);
+
+        // Row i of PA is row permutation[i] of A
+        let inverse_permutation = permutation
+            .ArgSort(ndim - 2, false)
+            .unsqueeze(ndim - 1)
+            .broadcast_to(gradient.view());
+        vec![TensorGradient::new(
+            self.input.clone(),
+            gradient.GatherElements(ndim - 2, &inverse_permutation),
+        )]
+    }
+}
+
+impl BackPropable for TriangularSolveSpec {
+    fn backward(&self, output: &Tensor) -> Vec<TensorGradient> {
+        let ndim = self.matrix.ndim();
+        let n = self.matrix.shape()[ndim as usize - 1];
+        let transposed = (0..ndim - 2)
+            .chain([ndim - 1, ndim - 2])
+            .collect::<Vec<_>>();
+
+        // X = A^-1 B, so every rhs element contributes the row sum of A^-T
+        let ones = Tensor::scalar(1.0).broadcast_to(&TensorView::from_contiguous_shape(
+            &[&self.matrix.shape()[..ndim as usize - 1], &[1]].concat(),
+        ));
+        let rhs_gradient = self.matrix.transpose(&transposed).TriangularSolve(
+            &ones,
+            !self.upper,
+            self.unit_diagonal,
+        );
+
+        // Only the triangle that is read receives a gradient
+        let rows = Tensor::arange(&[n, 1]);
+        let columns = Tensor::arange(&[1, n]);
+        let read = match (self.upper, self.unit_diagonal) {
+            (true, true) => rows.LessThan(&columns),
+            (true, false) => rows.LessOrEqual(&columns),
+            (false, true) => columns.LessThan(&rows),
+            (false, false) => columns.LessOrEqual(&rows),
+        };
+        let matrix_gradient = rhs_gradient
+            .Multiply(&output.Sum(&[ndim - 1], true).transpose(&transposed))
+            .Neg();
+
+        vec![
+            TensorGradient::new(
+                self.matrix.clone(),
+                read.Where(&matrix_gradient, &Tensor::scalar(0.0)),
+            ),
+            TensorGradient::new(
+                self.rhs.clone(),
+                rhs_gradient.broadcast_to(&TensorView::from_contiguous_shape(self.rhs.shape())),
+            ),
+        ]
+    }
+}
+
 impl BackPropable for OperationSpec {
     fn backward(&self, output: &Tensor) -> Vec<TensorGradient> {
         match self {
             OperationSpec::UnaryOp(op) => op.backward(output),
             OperationSpec::BinaryOp(op) => op.backward(output),
+            OperationSpec::TernaryOp(op) => op.backward(output),
             OperationSpec::ReduceOp(op) => op.backward(output),
+            OperationSpec::ScanOp(op) => op.backward(output),
+            OperationSpec::SortOp(op) => op.backward(output),
+            OperationSpec::MatMulOp(op) => op.backward(output),
+            OperationSpec::ConcatOp(op) => op.backward(output),
+            OperationSpec::TakeOp(op) => op.backward(output),
+            OperationSpec::AttentionOp(op) => op.backward(output),
+            OperationSpec::LUOp(op) => op.backward(output),
+            OperationSpec::TriangularSolveOp(op) => op.backward(output),
         }
     }
 }
@@ -2,6 +2,8 @@ use sha1::{Digest, Sha1};
 use std::path::Path;
 
 use filemanager::FileManager;
+use tensor::primitives::tensor::{Tensor, TensorType};
+use tensor::primitives::tensorview::TensorView;
 
 use crate::onnx;
 use crate::onnx::tensor_proto::{DataLocation, DataType};
@@ -52,6 +54,8 @@ impl TensorProto {
                     bytemuck::cast_slice(&self.float_data[..]),
                     self.data_type(),
                 ),
+                // Every element is widened to its own int32, 16-bit floats keep their bits in the
+                // low half
                 DataType::Int32
                 | DataType::Int16
                 | DataType::Int8
@@ -63,10 +67,17 @@ impl TensorProto {
                 | DataType::Float8e4m3fn
                 | DataType::Float8e4m3fnuz
                 | DataType::Float8e5m2
-                | DataType::Float8e5m2fnuz => TensorProto::load_slice(
-                    bytemuck::cast_slice(&self.int32_data[..]),
-                    self.data_type(),
-                ),
+                | DataType::Float8e5m2fnuz => self
+                    .int32_data
+                    .iter()
+                    .map(|x| {
+                        let bytes = x.to_le_bytes();
+                        TensorProto::parse_bytes(
+                            &bytes[..self.data_type().size()],
+                            self.data_type(),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
                 DataType::String => self
                     .string_data
                     .iter()
@@ -156,4 +167,52 @@ impl TensorProto {
             DataLocation::External => self.load_external_data(model_path.unwrap()),
         }
     }
+
+    // 8-bit integers keep their type so quantized weights are not dequantized on load, every
+    // other type is loaded as f32
+    pub fn tensor(&self, model_path: Option<&Path>) -> Tensor {
+        let shape = self.dims.iter().map(|&x| x as i32).collect::<Vec<_>>();
+        let data = self.load(model_path);
+
+        let datatype = match self.data_type() {
+            DataType::Int8 => TensorType::I8,
+            DataType::Uint8 => TensorType::U8,
+            _ => return Tensor::from_contiguous(&data[..], &shape[..]),
+        };
+        let data = data.iter().map(|&x| x as i32).collect::<Vec<_>>();
+        Tensor::from_raw_bytes(
+            bytemuck::cast_slice(&data[..]),
+            TensorView::from_contiguous_shape(&shape[..]),
+            datatype,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn int32_data_elements() {
+        let proto = |data_type: DataType, int32_data: Vec<i32>| TensorProto {
+            dims: vec![int32_data.len() as i64],
+            data_type: data_type as i32,
+            int32_data,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            proto(DataType::Int8, vec![-3, 5, 127]).load(None),
+            vec![-3.0, 5.0, 127.0]
+        );
+        assert_eq!(
+            proto(DataType::Uint16, vec![0, 65535]).load(None),
+            vec![0.0, 65535.0]
+        );
+        // 1.5 and -2.0 as the bits of an f16
+        assert_eq!(
+            proto(DataType::Float16, vec![0x3e00, 0xc000]).load(None),
+            vec![1.5, -2.0]
+        );
+    }
 }
@@ -35,20 +35,14 @@ impl OnnxModel {
             .initializer
             .iter()
             .map(|tensor_proto| {
-                let shape = tensor_proto
-                    .dims
-                    .iter()
-                    .map(|&x| x as i32)
-                    .collect::<Vec<_>>();
-                let data = tensor_proto.load(Some(onnx_filepath));
+                let tensor = tensor_proto.tensor(Some(onnx_filepath));
 
                 log::debug!(
                     "[ONNX] Found initializer `{}` of shape `{:?}`",
                     &tensor_proto.name,
-                    &shape[..]
+                    tensor.shape()
                 );
 
-                let tensor = Tensor::from_contiguous(&data[..], &shape[..]);
                 (tensor_proto.name.clone(), tensor)
             })
             .collect::<HashMap<_, _>>();
@@ -8,6 +8,9 @@ pub trait OnnxRuntime {
 
     fn track_tensor(&self, name: &str, tensor: Tensor);
 
+    // Tensors whose data is read while converting the graph have to be evaluated first
+    fn evaluate(&self, tensor: &Tensor) -> Tensor;
+
     fn serialize_node(&self, proto: &onnx::NodeProto) {
         match proto.op_type.as_str() {
             "Abs" => self.Abs(proto),
@@ -165,6 +168,7 @@ pub trait OnnxRuntime {
             "Where" => self.Where(proto),
             "Xor" => self.Xor(proto),
             "AffineGrid" => self.AffineGrid(proto),
+            "Attention" => self.Attention(proto),
             "Bernoulli" => self.Bernoulli(proto),
             "BlackmanWindow" => self.BlackmanWindow(proto),
             "CastLike" => self.CastLike(proto),
@@ -188,6 +192,7 @@ pub trait OnnxRuntime {
             "Mish" => self.Mish(proto),
             "NegativeLogLikelihoodLoss" => self.NegativeLogLikelihoodLoss(proto),
             "PRelu" => self.PRelu(proto),
+            "RMSNormalization" => self.RMSNormalization(proto),
             "Range" => self.Range(proto),
             "ReduceL1" => self.ReduceL1(proto),
             "ReduceL2" => self.ReduceL2(proto),
@@ -830,6 +835,10 @@ pub trait OnnxRuntime {
         panic!("AffineGrid has not been implemented");
     }
 
+    fn Attention(&self, node_proto: &onnx::NodeProto) {
+        panic!("Attention has not been implemented");
+    }
+
     fn Bernoulli(&self, node_proto: &onnx::NodeProto) {
         panic!("Bernoulli has not been implemented");
     }
@@ -922,6 +931,10 @@ pub trait OnnxRuntime {
         panic!("PRelu has not been implemented");
     }
 
+    fn RMSNormalization(&self, node_proto: &onnx::NodeProto) {
+        panic!("RMSNormalization has not been implemented");
+    }
+
     fn Range(&self, node_proto: &onnx::NodeProto) {
         panic!("Range has not been implemented");
     }
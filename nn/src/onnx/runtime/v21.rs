@@ -2,13 +2,42 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 
-use tensor::primitives::tensor::{ConvPadding, Tensor, TensorType};
-use tensor::primitives::tensorview::TensorView;
+use tensor::primitives::tensor::{
+    ConvConfig, ConvPadding, CoordinateTransformation, GridSampleMode, GridSamplePadding,
+    NearestMode, Quantization, RecurrentActivation, RecurrentConfig, RecurrentDirection,
+    ResizeMode, ScatterReduction, Tensor, TensorType,
+};
+use tensor::primitives::tensorview::{PadMode, TensorView};
 
 use crate::onnx;
+use crate::onnx::runtime::OnnxRuntime;
 
+type Evaluator = Rc<dyn Fn(&Tensor) -> Tensor>;
+
+#[derive(Default)]
 pub struct OpsetV21 {
     lookup: Rc<RefCell<HashMap<String, Tensor>>>,
+    evaluator: Option<Evaluator>,
+}
+
+impl OpsetV21 {
+    // Evaluates the Tensors whose data is read while converting the graph, e.g. on a device
+    pub fn with_evaluator(self, evaluator: impl Fn(&Tensor) -> Tensor + 'static) -> OpsetV21 {
+        OpsetV21 {
+            evaluator: Some(Rc::new(evaluator)),
+            ..self
+        }
+    }
+
+    // Initializers besides 8-bit integers are parsed as F32, bitwise operators need the integers
+    fn integer_tensor(&self, proto_name: &str) -> Tensor {
+        let tensor = self.tensor(proto_name).unwrap();
+        if tensor.datatype().is_integer() {
+            tensor
+        } else {
+            tensor.Cast(TensorType::I32)
+        }
+    }
 }
 
 impl onnx::runtime::OnnxRuntime for OpsetV21 {
@@ -25,6 +54,20 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         self.lookup.borrow_mut().insert(name.to_string(), tensor);
     }
 
+    fn evaluate(&self, tensor: &Tensor) -> Tensor {
+        match &self.evaluator {
+            Some(evaluator) if !tensor.has_data() => evaluator(tensor),
+            _ => {
+                assert!(
+                    tensor.has_data(),
+                    "Tensor {} has to be evaluated before its data is read, but no evaluator is set",
+                    tensor.id()
+                );
+                tensor.clone()
+            }
+        }
+    }
+
     fn Abs(&self, node_proto: &onnx::NodeProto) {
         log::trace!("[ONNX] [Abs]");
 
@@ -37,6 +80,30 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         self.track_tensor(Y, Y_tensor);
     }
 
+    fn Acos(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [Acos]");
+
+        let [input, output] = &node_proto.io_interface()[..] else {
+            panic!("Acos expects [input] -> [output]")
+        };
+
+        let input_tensor = self.tensor(input).unwrap();
+        let output_tensor = input_tensor.Acos();
+        self.track_tensor(output, output_tensor);
+    }
+
+    fn Acosh(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [Acosh]");
+
+        let [input, output] = &node_proto.io_interface()[..] else {
+            panic!("Acosh expects [input] -> [output]")
+        };
+
+        let input_tensor = self.tensor(input).unwrap();
+        let output_tensor = input_tensor.Acosh();
+        self.track_tensor(output, output_tensor);
+    }
+
     fn Add(&self, node_proto: &onnx::NodeProto) {
         log::trace!("[ONNX] [Add]");
 
@@ -93,6 +160,378 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         self.track_tensor(reduced, reduced_tensor);
     }
 
+    fn ArgMin(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let axis = attributes.get("axis").map(Into::<i64>::into).unwrap_or(1);
+        let keepdims = attributes
+            .get("keepdims")
+            .map(Into::<bool>::into)
+            .unwrap_or(true);
+        let select_last_index = attributes
+            .get("select_last_index")
+            .map(Into::<bool>::into)
+            .unwrap_or(false);
+
+        log::trace!(
+            "[ONNX] [ArgMin] [axis={}] [keepdims={}] [select_last_index={}]",
+            axis,
+            keepdims,
+            select_last_index
+        );
+
+        let [data, reduced] = &node_proto.io_interface()[..] else {
+            panic!("ArgMin expects [data] -> [reduced]")
+        };
+
+        let data_tensor = self.tensor(data).unwrap();
+        let axis = ((axis as i32) + data_tensor.ndim()) % data_tensor.ndim();
+
+        let reduced_tensor = data_tensor.ArgMin(axis, keepdims, select_last_index);
+        self.track_tensor(reduced, reduced_tensor);
+    }
+
+    fn Asin(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [Asin]");
+
+        let [input, output] = &node_proto.io_interface()[..] else {
+            panic!("Asin expects [input] -> [output]")
+        };
+
+        let input_tensor = self.tensor(input).unwrap();
+        let output_tensor = input_tensor.Asin();
+        self.track_tensor(output, output_tensor);
+    }
+
+    fn Asinh(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [Asinh]");
+
+        let [input, output] = &node_proto.io_interface()[..] else {
+            panic!("Asinh expects [input] -> [output]")
+        };
+
+        let input_tensor = self.tensor(input).unwrap();
+        let output_tensor = input_tensor.Asinh();
+        self.track_tensor(output, output_tensor);
+    }
+
+    fn Atan(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [Atan]");
+
+        let [input, output] = &node_proto.io_interface()[..] else {
+            panic!("Atan expects [input] -> [output]")
+        };
+
+        let input_tensor = self.tensor(input).unwrap();
+        let output_tensor = input_tensor.Atan();
+        self.track_tensor(output, output_tensor);
+    }
+
+    fn Atanh(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [Atanh]");
+
+        let [input, output] = &node_proto.io_interface()[..] else {
+            panic!("Atanh expects [input] -> [output]")
+        };
+
+        let input_tensor = self.tensor(input).unwrap();
+        let output_tensor = input_tensor.Atanh();
+        self.track_tensor(output, output_tensor);
+    }
+
+    fn Attention(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let is_causal = attributes
+            .get("is_causal")
+            .map(Into::<bool>::into)
+            .unwrap_or(false);
+        let q_num_heads = attributes.get("q_num_heads").map(Into::<i64>::into);
+        let kv_num_heads = attributes.get("kv_num_heads").map(Into::<i64>::into);
+        let scale = attributes.get("scale").map(Into::<f32>::into);
+        let softcap = attributes
+            .get("softcap")
+            .map(Into::<f32>::into)
+            .unwrap_or(0.0);
+        log::trace!(
+            "[ONNX] [Attention] [is_causal={}] [q_num_heads={:?}] [kv_num_heads={:?}] [scale={:?}] [softcap={}]",
+            is_causal,
+            q_num_heads,
+            kv_num_heads,
+            scale,
+            softcap
+        );
+
+        let [Q, K, V, ..] = &node_proto.input[..] else {
+            panic!("Attention expects [Q] [K] [V] [attn_mask] [past_key] [past_value] -> [Y] [present_key] [present_value] [qk_matmul_output]")
+        };
+        let [Y, present @ ..] = &node_proto.output[..] else {
+            panic!("Attention expects [Q] [K] [V] [attn_mask] [past_key] [past_value] -> [Y] [present_key] [present_value] [qk_matmul_output]")
+        };
+        // Optional inputs are either missing or empty
+        let optional_input = |index: usize| {
+            node_proto
+                .input
+                .get(index)
+                .and_then(|name| self.tensor(name))
+        };
+
+        // The fused kernel never materializes the scores these would need
+        assert!(softcap == 0.0, "Attention: softcap is not supported");
+        assert!(
+            present.get(2).is_none_or(|name| name.is_empty()),
+            "Attention: qk_matmul_output is not supported"
+        );
+
+        // 3D inputs pack the heads into the hidden dimension
+        let split_heads = |tensor: Tensor, num_heads: Option<i64>| match tensor.ndim() {
+            3 => {
+                let num_heads = num_heads.expect("Attention expects num_heads for 3D inputs");
+                tensor
+                    .view_as(&[0, 0, num_heads as i32, -1])
+                    .transpose(&[0, 2, 1, 3])
+            }
+            _ => tensor,
+        };
+
+        let Q_tensor = self.tensor(Q).unwrap();
+        let packed = Q_tensor.ndim() == 3;
+        let Q_tensor = split_heads(Q_tensor, q_num_heads);
+        let K_tensor = split_heads(self.tensor(K).unwrap(), kv_num_heads);
+        let V_tensor = split_heads(self.tensor(V).unwrap(), kv_num_heads);
+        let attn_mask_tensor = optional_input(3);
+
+        // Cached keys and values precede the new ones along the sequence
+        let (present_key_tensor, present_value_tensor) =
+            match (optional_input(4), optional_input(5)) {
+                (Some(past_key_tensor), Some(past_value_tensor)) => (
+                    Tensor::Concat(&[past_key_tensor, K_tensor], 2),
+                    Tensor::Concat(&[past_value_tensor, V_tensor], 2),
+                ),
+                _ => (K_tensor, V_tensor),
+            };
+
+        // Grouped query heads share each key and value head
+        let [_, q_heads, ..] = Q_tensor.shape() else {
+            panic!("Attention expects 4D [Q]")
+        };
+        let [_, kv_heads, ..] = present_key_tensor.shape() else {
+            panic!("Attention expects 4D [K]")
+        };
+        assert!(
+            q_heads % kv_heads == 0,
+            "Attention expects the {} query heads to be a multiple of the {} key-value heads",
+            q_heads,
+            kv_heads
+        );
+        let group = q_heads / kv_heads;
+
+        let Y_tensor = Tensor::ScaledDotProductAttention(
+            &Q_tensor,
+            &present_key_tensor.RepeatInterleave(group, 1),
+            &present_value_tensor.RepeatInterleave(group, 1),
+            attn_mask_tensor.as_ref(),
+            is_causal,
+            scale,
+        );
+        let Y_tensor = match packed {
+            true => Y_tensor.transpose(&[0, 2, 1, 3]).view_as(&[0, 0, -1]),
+            false => Y_tensor,
+        };
+        self.track_tensor(Y, Y_tensor);
+
+        present
+            .iter()
+            .zip([present_key_tensor, present_value_tensor])
+            .filter(|(name, _)| !name.is_empty())
+            .for_each(|(name, tensor)| self.track_tensor(name, tensor));
+    }
+
+    fn AveragePool(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let pool_attributes = PoolAttributes::new(&attributes);
+        let count_include_pad = attributes
+            .get("count_include_pad")
+            .map(Into::<bool>::into)
+            .unwrap_or(false);
+        log::trace!(
+            "[ONNX] [AveragePool] {:?} [count_include_pad={}]",
+            pool_attributes,
+            count_include_pad
+        );
+
+        let [X, Y] = &node_proto.io_interface()[..] else {
+            panic!("AveragePool expects [X] -> [Y]")
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+        let padding = pool_attributes.padding(&X_tensor);
+        let Y_tensor = X_tensor.AvgPool(
+            &pool_attributes.kernel_shape[..],
+            &pool_attributes.strides[..],
+            &pool_attributes.dilations[..],
+            pool_attributes.ceil_mode,
+            count_include_pad,
+            pool_attributes.conv_padding(&padding[..]),
+        );
+        self.track_tensor(Y, Y_tensor);
+    }
+
+    fn BatchNormalization(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let epsilon = attributes
+            .get("epsilon")
+            .map(Into::<f32>::into)
+            .unwrap_or(1e-05);
+        let momentum = attributes
+            .get("momentum")
+            .map(Into::<f32>::into)
+            .unwrap_or(0.9);
+        let training_mode = attributes
+            .get("training_mode")
+            .map(Into::<bool>::into)
+            .unwrap_or(false);
+        log::trace!(
+            "[ONNX] [BatchNormalization] [epsilon={}] [momentum={}] [training_mode={}]",
+            epsilon,
+            momentum,
+            training_mode
+        );
+
+        let [X, scale, B, input_mean, input_var] = &node_proto.input[..] else {
+            panic!("BatchNormalization expects [X] [scale] [B] [input_mean] [input_var] -> [Y] [running_mean] [running_var]")
+        };
+        let [Y, running_statistics @ ..] = &node_proto.output[..] else {
+            panic!("BatchNormalization expects [X] [scale] [B] [input_mean] [input_var] -> [Y] [running_mean] [running_var]")
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+        let input_mean_tensor = self.tensor(input_mean).unwrap();
+        let input_var_tensor = self.tensor(input_var).unwrap();
+
+        // Parameters of shape [C] apply to every element of a channel
+        let channel_shape = (0..X_tensor.ndim())
+            .map(|axis| if axis == 1 { -1 } else { 1 })
+            .collect::<Vec<_>>();
+        let scale_tensor = self.tensor(scale).unwrap().view_as(&channel_shape[..]);
+        let B_tensor = self.tensor(B).unwrap().view_as(&channel_shape[..]);
+
+        let epsilon_tensor = Tensor::scalar(epsilon);
+        let normalized_tensor = match training_mode {
+            false => {
+                X_tensor.BatchNormalization(&input_mean_tensor, &input_var_tensor, &epsilon_tensor)
+            }
+            true => {
+                let (normalized_tensor, running_mean_tensor, running_var_tensor) = X_tensor
+                    .BatchNormalizationTraining(
+                        &input_mean_tensor,
+                        &input_var_tensor,
+                        &Tensor::scalar(momentum),
+                        &epsilon_tensor,
+                    );
+
+                // Running statistics are only tracked when requested
+                running_statistics
+                    .iter()
+                    .zip([running_mean_tensor, running_var_tensor])
+                    .filter(|(name, _)| !name.is_empty())
+                    .for_each(|(name, tensor)| self.track_tensor(name, tensor));
+                normalized_tensor
+            }
+        };
+
+        let Y_tensor = scale_tensor.Multiply(&normalized_tensor).Add(&B_tensor);
+        self.track_tensor(Y, Y_tensor);
+    }
+
+    fn BitShift(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let direction = attributes
+            .get("direction")
+            .map(Into::<String>::into)
+            .expect("BitShift requires a direction");
+        log::trace!("[ONNX] [BitShift] [direction={}]", direction);
+
+        let [X, Y, Z] = &node_proto.io_interface()[..] else {
+            panic!("BitShift expects [X] [Y] -> [Z]")
+        };
+
+        let X_tensor = self.integer_tensor(X);
+        let Y_tensor = self.integer_tensor(Y);
+        let Z_tensor = match direction.as_str() {
+            "LEFT" => X_tensor.BitShiftLeft(&Y_tensor),
+            "RIGHT" => X_tensor.BitShiftRight(&Y_tensor),
+            _ => panic!("Unexpected BitShift direction {}", direction),
+        };
+        self.track_tensor(Z, Z_tensor);
+    }
+
+    fn BitwiseAnd(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [BitwiseAnd]");
+
+        let [A, B, C] = &node_proto.io_interface()[..] else {
+            panic!("BitwiseAnd expects [A] [B] -> [C]")
+        };
+
+        let A_tensor = self.integer_tensor(A);
+        let B_tensor = self.integer_tensor(B);
+        let C_tensor = A_tensor.BitwiseAnd(&B_tensor);
+        self.track_tensor(C, C_tensor);
+    }
+
+    fn BitwiseNot(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [BitwiseNot]");
+
+        let [X, Y] = &node_proto.io_interface()[..] else {
+            panic!("BitwiseNot expects [X] -> [Y]")
+        };
+
+        let X_tensor = self.integer_tensor(X);
+        let Y_tensor = X_tensor.BitwiseNot();
+        self.track_tensor(Y, Y_tensor);
+    }
+
+    fn BitwiseOr(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [BitwiseOr]");
+
+        let [A, B, C] = &node_proto.io_interface()[..] else {
+            panic!("BitwiseOr expects [A] [B] -> [C]")
+        };
+
+        let A_tensor = self.integer_tensor(A);
+        let B_tensor = self.integer_tensor(B);
+        let C_tensor = A_tensor.BitwiseOr(&B_tensor);
+        self.track_tensor(C, C_tensor);
+    }
+
+    fn BitwiseXor(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [BitwiseXor]");
+
+        let [A, B, C] = &node_proto.io_interface()[..] else {
+            panic!("BitwiseXor expects [A] [B] -> [C]")
+        };
+
+        let A_tensor = self.integer_tensor(A);
+        let B_tensor = self.integer_tensor(B);
+        let C_tensor = A_tensor.BitwiseXor(&B_tensor);
+        self.track_tensor(C, C_tensor);
+    }
+
+    fn BlackmanWindow(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let periodic = attributes
+            .get("periodic")
+            .map(Into::<bool>::into)
+            .unwrap_or(true);
+        log::trace!("[ONNX] [BlackmanWindow] [periodic={}]", periodic);
+
+        let [size, output] = &node_proto.io_interface()[..] else {
+            panic!("BlackmanWindow expects [size] -> [output]")
+        };
+
+        let size = self.tensor(size).unwrap().load::<f32>()[0] as i32;
+        let output_tensor = Tensor::BlackmanWindow(size, periodic);
+        self.track_tensor(output, output_tensor);
+    }
+
     fn Cast(&self, node_proto: &onnx::NodeProto) {
         let attributes = node_proto.attributes();
         let saturate = attributes
@@ -113,8 +552,8 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
 
         let tensor_type = match to {
             onnx::tensor_proto::DataType::Float => TensorType::F32,
-            onnx::tensor_proto::DataType::Uint8 => TensorType::U32,
-            onnx::tensor_proto::DataType::Int8 => TensorType::I32,
+            onnx::tensor_proto::DataType::Uint8 => TensorType::U8,
+            onnx::tensor_proto::DataType::Int8 => TensorType::I8,
             onnx::tensor_proto::DataType::Uint16 => TensorType::U32,
             onnx::tensor_proto::DataType::Int16 => TensorType::I32,
             onnx::tensor_proto::DataType::Int32 => TensorType::I32,
@@ -165,13 +604,35 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         let min_tensor = self.tensor(min);
         let max_tensor = self.tensor(max);
 
-        let output_tensor =
-            min_tensor.map_or(input_tensor.clone(), |min| input_tensor.Minimum(&min));
-        let output_tensor =
-            max_tensor.map_or(output_tensor.clone(), |max| output_tensor.Maximum(&max));
+        let output_tensor = min_tensor.map_or(input_tensor.clone(), |min| {
+            input_tensor.LessThan(&min).Where(&min, &input_tensor)
+        });
+        let output_tensor = max_tensor.map_or(output_tensor.clone(), |max| {
+            max.LessThan(&output_tensor).Where(&max, &output_tensor)
+        });
         self.track_tensor(output, output_tensor)
     }
 
+    fn Compress(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let axis = attributes.get("axis").map(Into::<i64>::into);
+        log::trace!("[ONNX] [Compress] [axis={:?}]", axis);
+
+        let [input, condition, output] = &node_proto.io_interface()[..] else {
+            panic!("Compress expects [input] [condition] -> [output]")
+        };
+
+        let input_tensor = self.tensor(input).unwrap();
+        let condition_tensor = self.tensor(condition).unwrap();
+        let axis = axis.map(|axis| ((axis as i32) + input_tensor.ndim()) % input_tensor.ndim());
+
+        // The output shape depends on the condition, so it is counted before the output is filled
+        let compressed = input_tensor.Compress(&condition_tensor, axis);
+        let count = self.evaluate(&compressed.count).load::<i32>()[0];
+        let output_tensor = compressed.resolve(count);
+        self.track_tensor(output, output_tensor);
+    }
+
     fn Concat(&self, node_proto: &onnx::NodeProto) {
         let attributes = node_proto.attributes();
         let axis = attributes.get("axis").map(Into::<i64>::into).unwrap();
@@ -181,79 +642,15 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
             panic!("Concat expects [inputs]... -> [concat_result]")
         };
 
-        let reference_tensor = self.tensor(inputs.first().unwrap()).unwrap();
-        let reference_shape = &reference_tensor.shape();
-        let reference_dimension = reference_tensor.ndim() as i64;
         let input_tensors = inputs
             .iter()
             .map(|input_name| self.tensor(&input_name).unwrap())
             .collect::<Vec<_>>();
 
-        let axis = ((axis + reference_dimension) % reference_dimension) as usize;
-
-        // Ensure all shapes are compatible
-        log::trace!("[ONNX] [Concat] Reference Shape: {:?}", reference_shape);
-        input_tensors.iter().for_each(|tensor| {
-            let shape = &tensor.shape();
-            assert!(
-                tensor.ndim() as i64 == reference_dimension,
-                "Tensors must have the same shape, besides on the dimension of concatenation"
-            );
-
-            reference_shape
-                .iter()
-                .zip(shape.iter())
-                .enumerate()
-                .for_each(|(dimension, (&reference, &shape))| {
-                    log::trace!("[Concat] Tensor Shape: {:?}", tensor.shape());
-
-                    assert!(
-                        dimension == axis || reference == shape,
-                        "All dimensions should match besides the axis of concatenation"
-                    );
-                })
-        });
-
-        // TODO: Assumed correct
-        let concat_result_tensor = input_tensors
-            .into_iter()
-            .reduce(|acc, tensor| {
-                let current_axis_length = acc.shape()[axis];
-                let tensor_axis_length = tensor.shape()[axis];
-
-                let current_padding = acc
-                    .view()
-                    .shape
-                    .iter()
-                    .enumerate()
-                    .map(|(dimension, _)| {
-                        if dimension == axis {
-                            (0, tensor_axis_length)
-                        } else {
-                            (0, 0)
-                        }
-                    })
-                    .collect::<Vec<_>>();
-                let tensor_padding = tensor
-                    .view()
-                    .shape
-                    .iter()
-                    .enumerate()
-                    .map(|(dimension, _)| {
-                        if dimension == axis {
-                            (current_axis_length, 0)
-                        } else {
-                            (0, 0)
-                        }
-                    })
-                    .collect::<Vec<_>>();
-
-                let acc = acc.Pad(&current_padding[..]);
-                let tensor = tensor.Pad(&tensor_padding[..]);
+        let data_rank = input_tensors.first().unwrap().ndim() as i64;
+        let axis = (axis + data_rank) % data_rank;
 
-                acc.Add(&tensor)
-            })
-            .unwrap();
+        let concat_result_tensor = Tensor::Concat(&input_tensors[..], axis as i32);
         self.track_tensor(concat_result, concat_result_tensor);
     }
 
@@ -277,10 +674,7 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
             panic!("Constant expects -> [output]")
         };
 
-        let shape = value.dims.iter().map(|&x| x as i32).collect::<Vec<_>>();
-        let data = value.load(None);
-
-        let output_tensor = Tensor::from_contiguous(&data[..], &shape[..]);
+        let output_tensor = value.tensor(None);
         self.track_tensor(output, output_tensor);
     }
 
@@ -339,31 +733,16 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
 
         let X_tensor = self.tensor(X).unwrap();
         let W_tensor = self.tensor(W).unwrap();
-        let B_tensor = self.tensor(B.first().unwrap()).unwrap_or(Tensor::scalar(0));
-
-        // NOTE: Hardcoded for this implementation
-        let dilations = dilations.unwrap_or(vec![1]);
-        let kernel_shape = W_tensor.shape().to_vec();
-        let pads = pads.unwrap_or(
-            (0..X_tensor.ndim())
-                .flat_map(|_| [0, 0].into_iter())
-                .collect::<Vec<_>>(),
-        );
 
-        // Only dilations of 1 are supported here
-        dilations
+        let features = X_tensor.ndim() as usize - 2;
+        let dilations = dilations
+            .unwrap_or(vec![1; features])
             .iter()
-            .for_each(|&dilation| assert!(dilation == 1));
-        // Only group of size 1 is supported here
-        assert!(group == 1);
-
+            .map(|&dilation| dilation as i32)
+            .collect::<Vec<_>>();
+        let pads = pads.unwrap_or(vec![0; 2 * features]);
         let strides = strides
-            .unwrap_or(
-                (0..X_tensor.ndim())
-                    .into_iter()
-                    .map(|_| 1)
-                    .collect::<Vec<_>>(),
-            )
+            .unwrap_or(vec![1; features])
             .iter()
             .map(|&stride| stride as i32)
             .collect::<Vec<_>>();
@@ -415,18 +794,185 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         strides,
     );
 
-        let Y_tensor = X_tensor
-        .Conv(&W_tensor, &strides[..], conv_padding)
-        // .Add(&B_tensor)
-        ;
+        let Y_tensor = X_tensor.Conv(
+            &W_tensor,
+            &strides[..],
+            &dilations[..],
+            group as i32,
+            conv_padding,
+        );
+        // Bias is added per output channel
+        let Y_tensor = match B.first().and_then(|B| self.tensor(B)) {
+            Some(B_tensor) => Y_tensor.Add(
+                &B_tensor.view_as(
+                    &[1, -1]
+                        .into_iter()
+                        .chain((0..features).map(|_| 1))
+                        .collect::<Vec<_>>()[..],
+                ),
+            ),
+            None => Y_tensor,
+        };
         self.track_tensor(Y, Y_tensor);
     }
 
-    fn Cos(&self, node_proto: &onnx::NodeProto) {
-        log::trace!("[ONNX] [Cos]");
+    fn ConvInteger(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
 
-        let [input, output] = &node_proto.io_interface()[..] else {
-            panic!("Cos expects [input] -> [output]")
+        let [x, w, ..] = &node_proto.input[..] else {
+            panic!("ConvInteger expects [x] [w] [x_zero_point] [w_zero_point] -> [y]")
+        };
+        let [y] = &node_proto.output[..] else {
+            panic!("ConvInteger expects [x] [w] [x_zero_point] [w_zero_point] -> [y]")
+        };
+        let optional_input = |index: usize| {
+            node_proto
+                .input
+                .get(index)
+                .and_then(|name| self.tensor(name))
+        };
+
+        let x_tensor = self.tensor(x).unwrap();
+        let w_tensor = self.tensor(w).unwrap();
+        let conv_attributes = ConvAttributes::new(&attributes, &w_tensor);
+        log::trace!("[ONNX] [ConvInteger] {:?}", conv_attributes);
+
+        let padding = conv_attributes.padding(&x_tensor);
+        let y_tensor = x_tensor.ConvInteger(
+            &w_tensor,
+            optional_input(2).as_ref(),
+            optional_input(3).as_ref(),
+            conv_attributes.config(&padding[..]),
+        );
+        self.track_tensor(y, y_tensor);
+    }
+
+    fn ConvTranspose(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let auto_pad = attributes
+            .get("auto_pad")
+            .map(Into::<String>::into)
+            .unwrap_or("NOTSET".to_string());
+        let dilations = attributes.get("dilations").map(Into::<Vec<i64>>::into);
+        let group = attributes.get("group").map(Into::<i64>::into).unwrap_or(1);
+        let output_padding = attributes.get("output_padding").map(Into::<Vec<i64>>::into);
+        let output_shape = attributes.get("output_shape").map(Into::<Vec<i64>>::into);
+        let pads = attributes.get("pads").map(Into::<Vec<i64>>::into);
+        let strides = attributes.get("strides").map(Into::<Vec<i64>>::into);
+
+        log::trace!(
+            "[ONNX] [ConvTranspose] [auto_pad={}] [dilations={:?}] [group={}] [output_padding={:?}] [output_shape={:?}] [pads={:?}] [strides={:?}]",
+            &auto_pad,
+            dilations,
+            group,
+            output_padding,
+            output_shape,
+            pads,
+            strides,
+        );
+
+        let [X, W, B @ ..] = &node_proto.input[..] else {
+            panic!("ConvTranspose expects [X] [W] [B] -> [Y]")
+        };
+        let [Y] = &node_proto.output[..] else {
+            panic!("ConvTranspose expects [X] [W] [B] -> [Y]")
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+        let W_tensor = self.tensor(W).unwrap();
+
+        let features = X_tensor.ndim() as usize - 2;
+        let dilations = dilations
+            .unwrap_or(vec![1; features])
+            .iter()
+            .map(|&dilation| dilation as i32)
+            .collect::<Vec<_>>();
+        let output_padding = output_padding
+            .unwrap_or(vec![0; features])
+            .iter()
+            .map(|&padding| padding as i32)
+            .collect::<Vec<_>>();
+        let strides = strides
+            .unwrap_or(vec![1; features])
+            .iter()
+            .map(|&stride| stride as i32)
+            .collect::<Vec<_>>();
+
+        let input_shape = &X_tensor.shape()[2..];
+        let kernel_shape = &W_tensor.shape()[2..];
+
+        // An explicit output shape (or auto padding) determines the padding
+        let output_shape = match (output_shape, auto_pad.as_str()) {
+            (Some(output_shape), _) => Some(
+                output_shape[output_shape.len() - features..]
+                    .iter()
+                    .map(|&dimension| dimension as i32)
+                    .collect::<Vec<_>>(),
+            ),
+            (None, "SAME_UPPER" | "SAME_LOWER") => Some(
+                input_shape
+                    .iter()
+                    .zip(strides.iter())
+                    .map(|(&dimension, &stride)| dimension * stride)
+                    .collect::<Vec<_>>(),
+            ),
+            (None, "NOTSET" | "VALID") => None,
+            _ => unreachable!("Invalid ConvTranspose padding scheme, got {}", auto_pad),
+        };
+
+        let padding = match output_shape {
+            Some(output_shape) => (0..features)
+                .map(|i| {
+                    let total_padding = strides[i] * (input_shape[i] - 1)
+                        + output_padding[i]
+                        + ((kernel_shape[i] - 1) * dilations[i] + 1)
+                        - output_shape[i];
+                    match auto_pad.as_str() {
+                        "SAME_UPPER" => (total_padding / 2, total_padding - total_padding / 2),
+                        _ => (total_padding - total_padding / 2, total_padding / 2),
+                    }
+                })
+                .collect::<Vec<_>>(),
+            None if auto_pad == "VALID" => vec![(0, 0); features],
+            None => {
+                let pads = pads.unwrap_or(vec![0; 2 * features]);
+                let (padding_pre, padding_post) = pads.split_at(features);
+                padding_pre
+                    .iter()
+                    .zip(padding_post.iter())
+                    .map(|(&pre, &post)| (pre as i32, post as i32))
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        let Y_tensor = X_tensor.ConvTranspose(
+            &W_tensor,
+            &strides[..],
+            &dilations[..],
+            group as i32,
+            &padding[..],
+            &output_padding[..],
+        );
+        // Bias is added per output channel
+        let Y_tensor = match B.first().and_then(|B| self.tensor(B)) {
+            Some(B_tensor) => Y_tensor.Add(
+                &B_tensor.view_as(
+                    &[1, -1]
+                        .into_iter()
+                        .chain((0..features).map(|_| 1))
+                        .collect::<Vec<_>>()[..],
+                ),
+            ),
+            None => Y_tensor,
+        };
+        self.track_tensor(Y, Y_tensor);
+    }
+
+    fn Cos(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [Cos]");
+
+        let [input, output] = &node_proto.io_interface()[..] else {
+            panic!("Cos expects [input] -> [output]")
         };
 
         let input_tensor = self.tensor(input).unwrap();
@@ -434,6 +980,129 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         self.track_tensor(output, output_tensor);
     }
 
+    fn CumSum(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let exclusive = attributes
+            .get("exclusive")
+            .map(Into::<bool>::into)
+            .unwrap_or(false);
+        let reverse = attributes
+            .get("reverse")
+            .map(Into::<bool>::into)
+            .unwrap_or(false);
+        log::trace!(
+            "[ONNX] [CumSum] [exclusive={}] [reverse={}]",
+            exclusive,
+            reverse
+        );
+
+        let [x, axis, y] = &node_proto.io_interface()[..] else {
+            panic!("CumSum expects [x] [axis] -> [y]")
+        };
+
+        let x_tensor = self.tensor(x).unwrap();
+        let axis = self.tensor(axis).unwrap().load::<f32>()[0] as i32;
+        let axis = (axis + x_tensor.ndim()) % x_tensor.ndim();
+
+        let y_tensor = x_tensor.CumSum(axis, exclusive, reverse);
+        self.track_tensor(y, y_tensor);
+    }
+
+    fn DFT(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let inverse = attributes
+            .get("inverse")
+            .map(Into::<bool>::into)
+            .unwrap_or(false);
+        let onesided = attributes
+            .get("onesided")
+            .map(Into::<bool>::into)
+            .unwrap_or(false);
+
+        let [input, ..] = &node_proto.input[..] else {
+            panic!("DFT expects [input] [dft_length] [axis] -> [output]")
+        };
+        let [output] = &node_proto.output[..] else {
+            panic!("DFT expects [input] [dft_length] [axis] -> [output]")
+        };
+        // Optional inputs are either missing or empty
+        let optional_input = |index: usize| {
+            node_proto
+                .input
+                .get(index)
+                .and_then(|name| self.tensor(name))
+                .map(|tensor| tensor.load::<f32>()[0] as i32)
+        };
+
+        // Opsets before 20 define the axis as an attribute
+        let dft_length = optional_input(1);
+        let axis = match attributes.get("axis") {
+            Some(axis) => Into::<i64>::into(axis) as i32,
+            None => optional_input(2).unwrap_or(-2),
+        };
+        log::trace!(
+            "[ONNX] [DFT] [axis={}] [dft_length={:?}] [inverse={}] [onesided={}]",
+            axis,
+            dft_length,
+            inverse,
+            onesided
+        );
+
+        let input_tensor = self.tensor(input).unwrap();
+        let output_tensor = input_tensor.DFT(axis, dft_length, inverse, onesided);
+        self.track_tensor(output, output_tensor);
+    }
+
+    fn DequantizeLinear(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let axis = attributes.get("axis").map(Into::<i64>::into).unwrap_or(1);
+        let block_size = attributes
+            .get("block_size")
+            .map(Into::<i64>::into)
+            .unwrap_or(0);
+        log::trace!(
+            "[ONNX] [DequantizeLinear] [axis={}] [block_size={}]",
+            axis,
+            block_size
+        );
+
+        let [x, x_scale, ..] = &node_proto.input[..] else {
+            panic!("DequantizeLinear expects [x] [x_scale] [x_zero_point] -> [y]")
+        };
+        let [y] = &node_proto.output[..] else {
+            panic!("DequantizeLinear expects [x] [x_scale] [x_zero_point] -> [y]")
+        };
+        assert!(
+            block_size == 0,
+            "DequantizeLinear: blocked quantization is not supported"
+        );
+
+        let x_tensor = self.tensor(x).unwrap();
+        let x_scale_tensor = self.tensor(x_scale).unwrap();
+        let x_zero_point_tensor = node_proto.input.get(2).and_then(|name| self.tensor(name));
+        let ndim = x_tensor.ndim().max(1) as i64;
+        let y_tensor = x_tensor.DequantizeLinear(
+            Quantization {
+                scale: &x_scale_tensor,
+                zero_point: x_zero_point_tensor.as_ref(),
+            },
+            ((axis + ndim) % ndim) as i32,
+        );
+        self.track_tensor(y, y_tensor);
+    }
+
+    fn Det(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [Det]");
+
+        let [input, output] = &node_proto.io_interface()[..] else {
+            panic!("Det expects [X] -> [Y]")
+        };
+
+        let input_tensor = self.tensor(input).unwrap();
+        let output_tensor = input_tensor.Det();
+        self.track_tensor(output, output_tensor);
+    }
+
     fn Div(&self, node_proto: &onnx::NodeProto) {
         log::trace!("[ONNX] [Div]");
 
@@ -447,6 +1116,26 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         self.track_tensor(output, output_tensor);
     }
 
+    fn Einsum(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let equation = attributes
+            .get("equation")
+            .map(Into::<String>::into)
+            .unwrap();
+        log::trace!("[ONNX] [Einsum] [equation={}]", equation);
+
+        let [inputs @ .., output] = &node_proto.io_interface()[..] else {
+            panic!("Einsum expects [Inputs]... -> [Output]")
+        };
+
+        let input_tensors = inputs
+            .iter()
+            .map(|input_name| self.tensor(input_name).unwrap())
+            .collect::<Vec<_>>();
+        let output_tensor = Tensor::Einsum(&equation, &input_tensors[..]);
+        self.track_tensor(output, output_tensor);
+    }
+
     fn Equal(&self, node_proto: &onnx::NodeProto) {
         log::trace!("[ONNX] [Equal]");
 
@@ -523,10 +1212,7 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
             d2.iter().fold(1, |acc, &x| acc * x),
         ];
 
-        // Important for Tensor to be contiguous so that the internal view is correct
-        let output_tensor = input_tensor
-            .contiguous()
-            .reshape(&TensorView::from_contiguous_shape(&output_shape[..]));
+        let output_tensor = input_tensor.view_as(&output_shape[..]);
         self.track_tensor(output, output_tensor);
     }
 
@@ -542,6 +1228,64 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         self.track_tensor(output, output_tensor);
     }
 
+    fn GRU(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let recurrent_attributes = RecurrentAttributes::new(&attributes);
+        let linear_before_reset = attributes
+            .get("linear_before_reset")
+            .map(Into::<bool>::into)
+            .unwrap_or(false);
+        log::trace!(
+            "[ONNX] [GRU] {:?} [linear_before_reset={}]",
+            recurrent_attributes,
+            linear_before_reset
+        );
+
+        let [X, W, R, ..] = &node_proto.input[..] else {
+            panic!("GRU expects [X] [W] [R] [B] [sequence_lens] [initial_h] -> [Y] [Y_h]")
+        };
+        // Optional inputs are either missing or empty
+        let optional_input = |index: usize| {
+            node_proto
+                .input
+                .get(index)
+                .and_then(|name| self.tensor(name))
+        };
+
+        let X_tensor = recurrent_attributes.batch_first(&self.tensor(X).unwrap(), &[1, 0, 2]);
+        let W_tensor = self.tensor(W).unwrap();
+        let R_tensor = self.tensor(R).unwrap();
+        let B_tensor = optional_input(3);
+        let sequence_lens_tensor = optional_input(4);
+        let initial_h_tensor = optional_input(5)
+            .map(|initial_h| recurrent_attributes.batch_first(&initial_h, &[1, 0, 2]));
+
+        let activations = recurrent_attributes.activations(&["Sigmoid", "Tanh"]);
+        let config = RecurrentConfig {
+            input_weights: &W_tensor,
+            recurrent_weights: &R_tensor,
+            bias: B_tensor.as_ref(),
+            sequence_lens: sequence_lens_tensor.as_ref(),
+            direction: recurrent_attributes.direction(),
+            activations: &activations[..],
+            clip: recurrent_attributes.clip,
+        };
+        let (Y_tensor, Y_h_tensor) =
+            X_tensor.GRU(config, initial_h_tensor.as_ref(), linear_before_reset);
+        let outputs = [
+            recurrent_attributes.batch_first(&Y_tensor, &[2, 0, 1, 3]),
+            recurrent_attributes.batch_first(&Y_h_tensor, &[1, 0, 2]),
+        ];
+
+        // Outputs are only tracked when requested
+        node_proto
+            .output
+            .iter()
+            .zip(outputs)
+            .filter(|(name, _)| !name.is_empty())
+            .for_each(|(name, tensor)| self.track_tensor(name, tensor));
+    }
+
     fn Gather(&self, node_proto: &onnx::NodeProto) {
         let attributes = node_proto.attributes();
         let axis = attributes.get("axis").map(Into::<i64>::into).unwrap_or(0);
@@ -561,6 +1305,43 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         self.track_tensor(output, output_tensor);
     }
 
+    fn GatherElements(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let axis = attributes.get("axis").map(Into::<i64>::into).unwrap_or(0);
+        log::trace!("[ONNX] [GatherElements] [axis={}]", axis);
+
+        let [data, indices, output] = &node_proto.io_interface()[..] else {
+            panic!("GatherElements expects [data] [indices] -> [output]")
+        };
+
+        let data_tensor = self.tensor(data).unwrap();
+        let indices_tensor = self.tensor(indices).unwrap();
+
+        let data_dimension = data_tensor.ndim() as i64;
+        let axis = ((axis + data_dimension) % data_dimension) as i32;
+
+        let output_tensor = data_tensor.GatherElements(axis, &indices_tensor);
+        self.track_tensor(output, output_tensor);
+    }
+
+    fn GatherND(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let batch_dims = attributes
+            .get("batch_dims")
+            .map(Into::<i64>::into)
+            .unwrap_or(0);
+        log::trace!("[ONNX] [GatherND] [batch_dims={}]", batch_dims);
+
+        let [data, indices, output] = &node_proto.io_interface()[..] else {
+            panic!("GatherND expects [data] [indices] -> [output]")
+        };
+
+        let data_tensor = self.tensor(data).unwrap();
+        let indices_tensor = self.tensor(indices).unwrap();
+        let output_tensor = data_tensor.GatherND(&indices_tensor, batch_dims as i32);
+        self.track_tensor(output, output_tensor);
+    }
+
     fn Gemm(&self, node_proto: &onnx::NodeProto) {
         let attributes = node_proto.attributes();
         let alpha = attributes
@@ -614,6 +1395,41 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         self.track_tensor(Y, Y_tensor);
     }
 
+    fn GlobalAveragePool(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [GlobalAveragePool]");
+
+        let [X, Y] = &node_proto.io_interface()[..] else {
+            panic!("GlobalAveragePool expects [X] -> [Y]")
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+        self.track_tensor(Y, X_tensor.GlobalAvgPool());
+    }
+
+    fn GlobalLpPool(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let p = attributes.get("p").map(Into::<i64>::into).unwrap_or(2);
+        log::trace!("[ONNX] [GlobalLpPool] [p={}]", p);
+
+        let [X, Y] = &node_proto.io_interface()[..] else {
+            panic!("GlobalLpPool expects [X] -> [Y]")
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+        self.track_tensor(Y, X_tensor.GlobalLpPool(p as i32));
+    }
+
+    fn GlobalMaxPool(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [GlobalMaxPool]");
+
+        let [X, Y] = &node_proto.io_interface()[..] else {
+            panic!("GlobalMaxPool expects [X] -> [Y]")
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+        self.track_tensor(Y, X_tensor.GlobalMaxPool());
+    }
+
     fn Greater(&self, node_proto: &onnx::NodeProto) {
         log::trace!("[ONNX] [Greater]");
 
@@ -640,6 +1456,120 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         self.track_tensor(C, C_tensor);
     }
 
+    fn GridSample(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let align_corners = attributes
+            .get("align_corners")
+            .map(Into::<bool>::into)
+            .unwrap_or(false);
+        let mode = attributes
+            .get("mode")
+            .map(Into::<String>::into)
+            .unwrap_or("linear".to_string());
+        let padding_mode = attributes
+            .get("padding_mode")
+            .map(Into::<String>::into)
+            .unwrap_or("zeros".to_string());
+        log::trace!(
+            "[ONNX] [GridSample] [align_corners={}] [mode={}] [padding_mode={}]",
+            align_corners,
+            mode,
+            padding_mode
+        );
+
+        let [X, grid, Y] = &node_proto.io_interface()[..] else {
+            panic!("GridSample expects [X] [grid] -> [Y]")
+        };
+
+        // Opsets before 20 name linear interpolation bilinear
+        let mode = match mode.as_str() {
+            "linear" | "bilinear" => GridSampleMode::Bilinear,
+            "nearest" => GridSampleMode::Nearest,
+            _ => panic!("GridSample does not support `{}` mode", mode),
+        };
+        let padding = match padding_mode.as_str() {
+            "zeros" => GridSamplePadding::Zeros,
+            "border" => GridSamplePadding::Border,
+            "reflection" => GridSamplePadding::Reflection,
+            _ => panic!("GridSample does not support `{}` padding", padding_mode),
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+        let grid_tensor = self.tensor(grid).unwrap();
+        let Y_tensor = X_tensor.GridSample(&grid_tensor, mode, padding, align_corners);
+        self.track_tensor(Y, Y_tensor);
+    }
+
+    fn GroupNormalization(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let epsilon = attributes
+            .get("epsilon")
+            .map(Into::<f32>::into)
+            .unwrap_or(1e-05);
+        let num_groups = attributes
+            .get("num_groups")
+            .map(Into::<i64>::into)
+            .expect("GroupNormalization expects num_groups to be defined");
+        log::trace!(
+            "[ONNX] [GroupNormalization] [epsilon={}] [num_groups={}]",
+            epsilon,
+            num_groups
+        );
+
+        let [X, scale, bias, Y] = &node_proto.io_interface()[..] else {
+            panic!("GroupNormalization expects [X] [scale] [bias] -> [Y]")
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+
+        // Parameters of shape [C] apply to every element of a channel
+        let channel_shape = (0..X_tensor.ndim())
+            .map(|axis| if axis == 1 { -1 } else { 1 })
+            .collect::<Vec<_>>();
+        let scale_tensor = self.tensor(scale).unwrap().view_as(&channel_shape[..]);
+        let bias_tensor = self.tensor(bias).unwrap().view_as(&channel_shape[..]);
+
+        let epsilon_tensor = Tensor::scalar(epsilon);
+        let Y_tensor = scale_tensor
+            .Multiply(&X_tensor.GroupNormalization(num_groups as i32, &epsilon_tensor))
+            .Add(&bias_tensor);
+        self.track_tensor(Y, Y_tensor);
+    }
+
+    fn HammingWindow(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let periodic = attributes
+            .get("periodic")
+            .map(Into::<bool>::into)
+            .unwrap_or(true);
+        log::trace!("[ONNX] [HammingWindow] [periodic={}]", periodic);
+
+        let [size, output] = &node_proto.io_interface()[..] else {
+            panic!("HammingWindow expects [size] -> [output]")
+        };
+
+        let size = self.tensor(size).unwrap().load::<f32>()[0] as i32;
+        let output_tensor = Tensor::HammingWindow(size, periodic);
+        self.track_tensor(output, output_tensor);
+    }
+
+    fn HannWindow(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let periodic = attributes
+            .get("periodic")
+            .map(Into::<bool>::into)
+            .unwrap_or(true);
+        log::trace!("[ONNX] [HannWindow] [periodic={}]", periodic);
+
+        let [size, output] = &node_proto.io_interface()[..] else {
+            panic!("HannWindow expects [size] -> [output]")
+        };
+
+        let size = self.tensor(size).unwrap().load::<f32>()[0] as i32;
+        let output_tensor = Tensor::HannWindow(size, periodic);
+        self.track_tensor(output, output_tensor);
+    }
+
     fn Identity(&self, node_proto: &onnx::NodeProto) {
         log::trace!("[ONNX] [Identity]");
 
@@ -680,11 +1610,188 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         self.track_tensor(output, output_tensor);
     }
 
-    fn Less(&self, node_proto: &onnx::NodeProto) {
-        log::trace!("[ONNX] [Less]");
+    fn IsInf(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let detect_negative = attributes
+            .get("detect_negative")
+            .map(Into::<i64>::into)
+            .unwrap_or(1);
+        let detect_positive = attributes
+            .get("detect_positive")
+            .map(Into::<i64>::into)
+            .unwrap_or(1);
+        log::trace!(
+            "[ONNX] [IsInf] [detect_negative={}] [detect_positive={}]",
+            detect_negative,
+            detect_positive
+        );
 
-        let [A, B, C] = &node_proto.io_interface()[..] else {
-            panic!("Less expects [A] [B] -> [C]")
+        let [X, Y] = &node_proto.io_interface()[..] else {
+            panic!("IsInf expects [X] -> [Y]")
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+        let Y_tensor = X_tensor.IsInf(detect_positive != 0, detect_negative != 0);
+        self.track_tensor(Y, Y_tensor);
+    }
+
+    fn IsNaN(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [IsNaN]");
+
+        let [X, Y] = &node_proto.io_interface()[..] else {
+            panic!("IsNaN expects [X] -> [Y]")
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+        let Y_tensor = X_tensor.IsNaN();
+        self.track_tensor(Y, Y_tensor);
+    }
+
+    fn LRN(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let alpha = attributes
+            .get("alpha")
+            .map(Into::<f32>::into)
+            .unwrap_or(0.0001);
+        let beta = attributes
+            .get("beta")
+            .map(Into::<f32>::into)
+            .unwrap_or(0.75);
+        let bias = attributes.get("bias").map(Into::<f32>::into).unwrap_or(1.0);
+        let size = attributes
+            .get("size")
+            .map(Into::<i64>::into)
+            .expect("LRN expects size to be defined");
+        log::trace!(
+            "[ONNX] [LRN] [alpha={}] [beta={}] [bias={}] [size={}]",
+            alpha,
+            beta,
+            bias,
+            size
+        );
+
+        let [X, Y] = &node_proto.io_interface()[..] else {
+            panic!("LRN expects [X] -> [Y]")
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+        let Y_tensor = X_tensor.LRN(size as i32, alpha, beta, bias);
+        self.track_tensor(Y, Y_tensor);
+    }
+
+    fn LSTM(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let recurrent_attributes = RecurrentAttributes::new(&attributes);
+        let input_forget = attributes
+            .get("input_forget")
+            .map(Into::<bool>::into)
+            .unwrap_or(false);
+        log::trace!(
+            "[ONNX] [LSTM] {:?} [input_forget={}]",
+            recurrent_attributes,
+            input_forget
+        );
+
+        let [X, W, R, ..] = &node_proto.input[..] else {
+            panic!("LSTM expects [X] [W] [R] [B] [sequence_lens] [initial_h] [initial_c] [P] -> [Y] [Y_h] [Y_c]")
+        };
+        // Optional inputs are either missing or empty
+        let optional_input = |index: usize| {
+            node_proto
+                .input
+                .get(index)
+                .and_then(|name| self.tensor(name))
+        };
+
+        let X_tensor = recurrent_attributes.batch_first(&self.tensor(X).unwrap(), &[1, 0, 2]);
+        let W_tensor = self.tensor(W).unwrap();
+        let R_tensor = self.tensor(R).unwrap();
+        let B_tensor = optional_input(3);
+        let sequence_lens_tensor = optional_input(4);
+        let initial_h_tensor = optional_input(5)
+            .map(|initial_h| recurrent_attributes.batch_first(&initial_h, &[1, 0, 2]));
+        let initial_c_tensor = optional_input(6)
+            .map(|initial_c| recurrent_attributes.batch_first(&initial_c, &[1, 0, 2]));
+        let P_tensor = optional_input(7);
+
+        let activations = recurrent_attributes.activations(&["Sigmoid", "Tanh", "Tanh"]);
+        let config = RecurrentConfig {
+            input_weights: &W_tensor,
+            recurrent_weights: &R_tensor,
+            bias: B_tensor.as_ref(),
+            sequence_lens: sequence_lens_tensor.as_ref(),
+            direction: recurrent_attributes.direction(),
+            activations: &activations[..],
+            clip: recurrent_attributes.clip,
+        };
+        let (Y_tensor, Y_h_tensor, Y_c_tensor) = X_tensor.LSTM(
+            config,
+            initial_h_tensor.as_ref(),
+            initial_c_tensor.as_ref(),
+            P_tensor.as_ref(),
+            input_forget,
+        );
+        let outputs = [
+            recurrent_attributes.batch_first(&Y_tensor, &[2, 0, 1, 3]),
+            recurrent_attributes.batch_first(&Y_h_tensor, &[1, 0, 2]),
+            recurrent_attributes.batch_first(&Y_c_tensor, &[1, 0, 2]),
+        ];
+
+        // Outputs are only tracked when requested
+        node_proto
+            .output
+            .iter()
+            .zip(outputs)
+            .filter(|(name, _)| !name.is_empty())
+            .for_each(|(name, tensor)| self.track_tensor(name, tensor));
+    }
+
+    fn LayerNormalization(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let axis = attributes.get("axis").map(Into::<i64>::into).unwrap_or(-1);
+        let epsilon = attributes
+            .get("epsilon")
+            .map(Into::<f32>::into)
+            .unwrap_or(1e-05);
+        log::trace!(
+            "[ONNX] [LayerNormalization] [axis={}] [epsilon={}]",
+            axis,
+            epsilon
+        );
+
+        let [X, Scale, B @ ..] = &node_proto.input[..] else {
+            panic!("LayerNormalization expects [X] [Scale] [B] -> [Y] [Mean] [InvStdDev]")
+        };
+        let [Y, statistics @ ..] = &node_proto.output[..] else {
+            panic!("LayerNormalization expects [X] [Scale] [B] -> [Y] [Mean] [InvStdDev]")
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+        let Scale_tensor = self.tensor(Scale).unwrap();
+
+        let epsilon_tensor = Tensor::scalar(epsilon);
+        let (normalized_tensor, Mean_tensor, InvStdDev_tensor) =
+            X_tensor.LayerNormalization(axis as i32, &epsilon_tensor);
+        let Y_tensor = Scale_tensor.Multiply(&normalized_tensor);
+        let Y_tensor = match B.first().and_then(|B| self.tensor(B)) {
+            Some(B_tensor) => Y_tensor.Add(&B_tensor),
+            None => Y_tensor,
+        };
+        self.track_tensor(Y, Y_tensor);
+
+        // Statistics are only tracked when requested
+        statistics
+            .iter()
+            .zip([Mean_tensor, InvStdDev_tensor])
+            .filter(|(name, _)| !name.is_empty())
+            .for_each(|(name, tensor)| self.track_tensor(name, tensor));
+    }
+
+    fn Less(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [Less]");
+
+        let [A, B, C] = &node_proto.io_interface()[..] else {
+            panic!("Less expects [A] [B] -> [C]")
         };
 
         let A_tensor = self.tensor(A).unwrap();
@@ -718,6 +1825,29 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         self.track_tensor(output, output_tensor);
     }
 
+    fn LpPool(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let pool_attributes = PoolAttributes::new(&attributes);
+        let p = attributes.get("p").map(Into::<i64>::into).unwrap_or(2);
+        log::trace!("[ONNX] [LpPool] {:?} [p={}]", pool_attributes, p);
+
+        let [X, Y] = &node_proto.io_interface()[..] else {
+            panic!("LpPool expects [X] -> [Y]")
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+        let padding = pool_attributes.padding(&X_tensor);
+        let Y_tensor = X_tensor.LpPool(
+            p as i32,
+            &pool_attributes.kernel_shape[..],
+            &pool_attributes.strides[..],
+            &pool_attributes.dilations[..],
+            pool_attributes.ceil_mode,
+            pool_attributes.conv_padding(&padding[..]),
+        );
+        self.track_tensor(Y, Y_tensor);
+    }
+
     fn MatMul(&self, node_proto: &onnx::NodeProto) {
         let [A, B, Y] = &node_proto.io_interface()[..] else {
             panic!("MatMul expects [A] [B] -> [Y]")
@@ -729,6 +1859,72 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         self.track_tensor(Y, Y_tensor);
     }
 
+    fn MatMulInteger(&self, node_proto: &onnx::NodeProto) {
+        let [A, B, ..] = &node_proto.input[..] else {
+            panic!("MatMulInteger expects [A] [B] [a_zero_point] [b_zero_point] -> [Y]")
+        };
+        let [Y] = &node_proto.output[..] else {
+            panic!("MatMulInteger expects [A] [B] [a_zero_point] [b_zero_point] -> [Y]")
+        };
+        let optional_input = |index: usize| {
+            node_proto
+                .input
+                .get(index)
+                .and_then(|name| self.tensor(name))
+        };
+
+        let A_tensor = self.tensor(A).unwrap();
+        let B_tensor = self.tensor(B).unwrap();
+        let Y_tensor = A_tensor.MatMulInteger(
+            &B_tensor,
+            optional_input(2).as_ref(),
+            optional_input(3).as_ref(),
+        );
+        self.track_tensor(Y, Y_tensor);
+    }
+
+    fn MaxPool(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let pool_attributes = PoolAttributes::new(&attributes);
+        let storage_order = attributes
+            .get("storage_order")
+            .map(Into::<i64>::into)
+            .unwrap_or(0);
+        log::trace!(
+            "[ONNX] [MaxPool] {:?} [storage_order={}]",
+            pool_attributes,
+            storage_order
+        );
+
+        let [X] = &node_proto.input[..] else {
+            panic!("MaxPool expects [X] -> [Y] [Indices]")
+        };
+        let [Y, Indices @ ..] = &node_proto.output[..] else {
+            panic!("MaxPool expects [X] -> [Y] [Indices]")
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+        let padding = pool_attributes.padding(&X_tensor);
+        let (Y_tensor, Indices_tensor) = X_tensor.MaxPoolWithIndices(
+            &pool_attributes.kernel_shape[..],
+            &pool_attributes.strides[..],
+            &pool_attributes.dilations[..],
+            pool_attributes.ceil_mode,
+            pool_attributes.conv_padding(&padding[..]),
+        );
+        self.track_tensor(Y, Y_tensor);
+
+        // Indices are only computed when requested
+        if let Some(Indices) = Indices.first().filter(|name| !name.is_empty()) {
+            assert!(
+                storage_order == 0,
+                "MaxPool only supports row major indices, got storage_order={}",
+                storage_order
+            );
+            self.track_tensor(Indices, Indices_tensor);
+        }
+    }
+
     fn Mul(&self, node_proto: &onnx::NodeProto) {
         log::trace!("[ONNX] [Mul]");
 
@@ -754,6 +1950,22 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         self.track_tensor(Y, Y_tensor);
     }
 
+    fn NonZero(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [NonZero]");
+
+        let [X, Y] = &node_proto.io_interface()[..] else {
+            panic!("NonZero expects [X] -> [Y]")
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+
+        // The output shape depends on the data, so it is counted before the output is filled
+        let nonzero = X_tensor.NonZero();
+        let count = self.evaluate(&nonzero.count).load::<i32>()[0];
+        let Y_tensor = nonzero.resolve(count);
+        self.track_tensor(Y, Y_tensor);
+    }
+
     fn Not(&self, node_proto: &onnx::NodeProto) {
         log::trace!("[ONNX] [Not]");
 
@@ -787,75 +1999,64 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
             .unwrap_or("constant".to_string());
         log::trace!("[ONNX] [Pad] [mode={}]", &mode);
 
-        assert!(
-            mode == "constant",
-            "Pad does not support non-`constant` mode"
-        );
-
         let [data, pads, optional_inputs @ .., output] = &node_proto.io_interface()[..] else {
             panic!("Pad expects [data] [pads] [constant_value] [axes] -> [output]")
         };
 
         let data_tensor = self.tensor(data).unwrap();
         let pads_tensor = self.tensor(pads).unwrap();
-        let constant_value_tensor = self
-            .tensor(optional_inputs.first().unwrap())
-            .unwrap_or(Tensor::scalar(0));
+        let constant_value = optional_inputs
+            .first()
+            .and_then(|constant_value| self.tensor(constant_value))
+            .map(|tensor| tensor.load::<f32>()[0])
+            .unwrap_or(0.0);
+
+        let pad_mode = match mode.as_str() {
+            "constant" => PadMode::Constant(constant_value),
+            "reflect" => PadMode::Reflect,
+            "edge" => PadMode::Edge,
+            "wrap" => PadMode::Wrap,
+            _ => panic!("Pad does not support `{}` mode", mode),
+        };
 
         let pads = pads_tensor.load::<f32>();
 
-        let data_dimension = data_tensor.ndim();
+        let data_rank = data_tensor.ndim();
         let axes = optional_inputs
             .get(1)
             .map(|axes| self.tensor(axes))
             .flatten()
-            .map(|tensor| tensor.load::<f32>().to_vec())
-            .unwrap_or((0..data_dimension).into_iter().map(|x| x as f32).collect());
-        let axes = axes
-            .into_iter()
-            .map(|x| ((x + (data_dimension as f32)) / (data_dimension as f32)) as i32)
-            .collect::<Vec<_>>();
+            .map(|tensor| {
+                tensor
+                    .load::<f32>()
+                    .iter()
+                    .map(|&x| x as i32)
+                    .map(|x| (x + data_rank) % data_rank)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or((0..data_rank).collect());
 
+        // Pads are laid out as [x1_begin, x2_begin, ..., x1_end, x2_end, ...] following axes,
+        // negative pads remove elements instead
         let axes_padding_lookup = axes
             .iter()
-            .map(|&i| {
-                let pads_index = (i as usize) * 2;
-                let padding_begin = pads[pads_index];
-                let padding_end = pads[pads_index + 1];
-                (i, (padding_begin, padding_end))
+            .enumerate()
+            .map(|(i, &axis)| {
+                let padding_begin = pads[i] as i32;
+                let padding_end = pads[i + axes.len()] as i32;
+                (axis, (padding_begin, padding_end))
             })
             .collect::<HashMap<_, _>>();
-        let axes_padding_all = (0..data_dimension)
-            .into_iter()
+        let axes_padding = (0..data_rank)
             .map(|axis| {
                 axes_padding_lookup
                     .get(&axis)
-                    .map_or((0.0, 0.0), |&axis_padding| axis_padding)
-            })
-            .collect::<Vec<_>>();
-
-        // Offset would be negative padding
-        let axes_offset = axes_padding_all
-            .iter()
-            .map(|&(padding_begin, padding_end)| {
-                (
-                    padding_begin.min(0.0).abs() as i32,
-                    padding_end.min(0.0).abs() as i32,
-                )
-            })
-            .collect::<Vec<_>>();
-        let axes_padding = axes_padding_all
-            .iter()
-            .map(|&(padding_begin, padding_end)| {
-                (padding_begin.max(0.0) as i32, padding_end.max(0.0) as i32)
+                    .map_or((0, 0), |&axis_padding| axis_padding)
             })
             .collect::<Vec<_>>();
 
-        // let output_tensor = data_tensor
-        //     .reshape(&data_tensor.view().offset(&axes_offset[..]))
-        //     .contiguous()
-        //     .Pad(&axes_padding[..]);
-        // self.track_tensor(output, output_tensor);
+        let output_tensor = data_tensor.PadWith(&axes_padding[..], pad_mode);
+        self.track_tensor(output, output_tensor);
     }
 
     fn Pow(&self, node_proto: &onnx::NodeProto) {
@@ -871,6 +2072,217 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         self.track_tensor(output, output_tensor);
     }
 
+    fn QLinearConv(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+
+        let [x, x_scale, x_zero_point, w, w_scale, w_zero_point, y_scale, y_zero_point, ..] =
+            &node_proto.input[..]
+        else {
+            panic!("QLinearConv expects [x] [x_scale] [x_zero_point] [w] [w_scale] [w_zero_point] [y_scale] [y_zero_point] [B] -> [y]")
+        };
+        let [y] = &node_proto.output[..] else {
+            panic!("QLinearConv expects [x] [x_scale] [x_zero_point] [w] [w_scale] [w_zero_point] [y_scale] [y_zero_point] [B] -> [y]")
+        };
+
+        let x_tensor = self.tensor(x).unwrap();
+        let x_scale_tensor = self.tensor(x_scale).unwrap();
+        let x_zero_point_tensor = self.tensor(x_zero_point);
+        let w_tensor = self.tensor(w).unwrap();
+        let w_scale_tensor = self.tensor(w_scale).unwrap();
+        let w_zero_point_tensor = self.tensor(w_zero_point);
+        let y_scale_tensor = self.tensor(y_scale).unwrap();
+        let y_zero_point_tensor = self.tensor(y_zero_point);
+        let B_tensor = node_proto.input.get(8).and_then(|name| self.tensor(name));
+
+        let conv_attributes = ConvAttributes::new(&attributes, &w_tensor);
+        log::trace!("[ONNX] [QLinearConv] {:?}", conv_attributes);
+
+        let padding = conv_attributes.padding(&x_tensor);
+        let y_tensor = x_tensor.QLinearConv(
+            Quantization {
+                scale: &x_scale_tensor,
+                zero_point: x_zero_point_tensor.as_ref(),
+            },
+            &w_tensor,
+            Quantization {
+                scale: &w_scale_tensor,
+                zero_point: w_zero_point_tensor.as_ref(),
+            },
+            Quantization {
+                scale: &y_scale_tensor,
+                zero_point: y_zero_point_tensor.as_ref(),
+            },
+            B_tensor.as_ref(),
+            conv_attributes.config(&padding[..]),
+        );
+        self.track_tensor(y, y_tensor);
+    }
+
+    fn QLinearMatMul(&self, node_proto: &onnx::NodeProto) {
+        let [a, a_scale, a_zero_point, b, b_scale, b_zero_point, y_scale, y_zero_point, y] =
+            &node_proto.io_interface()[..]
+        else {
+            panic!("QLinearMatMul expects [a] [a_scale] [a_zero_point] [b] [b_scale] [b_zero_point] [y_scale] [y_zero_point] -> [y]")
+        };
+
+        let a_tensor = self.tensor(a).unwrap();
+        let a_scale_tensor = self.tensor(a_scale).unwrap();
+        let a_zero_point_tensor = self.tensor(a_zero_point).unwrap();
+        let b_tensor = self.tensor(b).unwrap();
+        let b_scale_tensor = self.tensor(b_scale).unwrap();
+        let b_zero_point_tensor = self.tensor(b_zero_point).unwrap();
+        let y_scale_tensor = self.tensor(y_scale).unwrap();
+        let y_zero_point_tensor = self.tensor(y_zero_point).unwrap();
+
+        let y_tensor = a_tensor.QLinearMatMul(
+            Quantization {
+                scale: &a_scale_tensor,
+                zero_point: Some(&a_zero_point_tensor),
+            },
+            &b_tensor,
+            Quantization {
+                scale: &b_scale_tensor,
+                zero_point: Some(&b_zero_point_tensor),
+            },
+            Quantization {
+                scale: &y_scale_tensor,
+                zero_point: Some(&y_zero_point_tensor),
+            },
+        );
+        self.track_tensor(y, y_tensor);
+    }
+
+    fn QuantizeLinear(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let axis = attributes.get("axis").map(Into::<i64>::into).unwrap_or(1);
+        let block_size = attributes
+            .get("block_size")
+            .map(Into::<i64>::into)
+            .unwrap_or(0);
+        let output_dtype = attributes
+            .get("output_dtype")
+            .map(Into::<i64>::into)
+            .unwrap_or(0);
+        log::trace!(
+            "[ONNX] [QuantizeLinear] [axis={}] [block_size={}] [output_dtype={}]",
+            axis,
+            block_size,
+            output_dtype
+        );
+
+        let [x, y_scale, ..] = &node_proto.input[..] else {
+            panic!("QuantizeLinear expects [x] [y_scale] [y_zero_point] -> [y]")
+        };
+        let [y] = &node_proto.output[..] else {
+            panic!("QuantizeLinear expects [x] [y_scale] [y_zero_point] -> [y]")
+        };
+        assert!(
+            block_size == 0,
+            "QuantizeLinear: blocked quantization is not supported"
+        );
+
+        let x_tensor = self.tensor(x).unwrap();
+        let y_scale_tensor = self.tensor(y_scale).unwrap();
+        let y_zero_point_tensor = node_proto.input.get(2).and_then(|name| self.tensor(name));
+        let quantization = Quantization {
+            scale: &y_scale_tensor,
+            zero_point: y_zero_point_tensor.as_ref(),
+        };
+
+        // Without an explicit output type the zero point decides
+        let datatype = match output_dtype {
+            0 => quantization.datatype(),
+            output_dtype => match onnx::tensor_proto::DataType::try_from(output_dtype as i32) {
+                Ok(onnx::tensor_proto::DataType::Int8) => TensorType::I8,
+                Ok(onnx::tensor_proto::DataType::Uint8) => TensorType::U8,
+                _ => panic!(
+                    "QuantizeLinear only supports int8 and uint8 outputs, got {}",
+                    output_dtype
+                ),
+            },
+        };
+
+        let ndim = x_tensor.ndim().max(1) as i64;
+        let y_tensor =
+            x_tensor.QuantizeLinear(quantization, ((axis + ndim) % ndim) as i32, datatype);
+        self.track_tensor(y, y_tensor);
+    }
+
+    fn RMSNormalization(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let axis = attributes.get("axis").map(Into::<i64>::into).unwrap_or(-1);
+        let epsilon = attributes
+            .get("epsilon")
+            .map(Into::<f32>::into)
+            .unwrap_or(1e-05);
+        log::trace!(
+            "[ONNX] [RMSNormalization] [axis={}] [epsilon={}]",
+            axis,
+            epsilon
+        );
+
+        let [X, scale, Y] = &node_proto.io_interface()[..] else {
+            panic!("RMSNormalization expects [X] [scale] -> [Y]")
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+        let scale_tensor = self.tensor(scale).unwrap();
+
+        let epsilon_tensor = Tensor::scalar(epsilon);
+        let Y_tensor =
+            scale_tensor.Multiply(&X_tensor.RMSNormalization(axis as i32, &epsilon_tensor));
+        self.track_tensor(Y, Y_tensor);
+    }
+
+    fn RNN(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let recurrent_attributes = RecurrentAttributes::new(&attributes);
+        log::trace!("[ONNX] [RNN] {:?}", recurrent_attributes);
+
+        let [X, W, R, ..] = &node_proto.input[..] else {
+            panic!("RNN expects [X] [W] [R] [B] [sequence_lens] [initial_h] -> [Y] [Y_h]")
+        };
+        // Optional inputs are either missing or empty
+        let optional_input = |index: usize| {
+            node_proto
+                .input
+                .get(index)
+                .and_then(|name| self.tensor(name))
+        };
+
+        let X_tensor = recurrent_attributes.batch_first(&self.tensor(X).unwrap(), &[1, 0, 2]);
+        let W_tensor = self.tensor(W).unwrap();
+        let R_tensor = self.tensor(R).unwrap();
+        let B_tensor = optional_input(3);
+        let sequence_lens_tensor = optional_input(4);
+        let initial_h_tensor = optional_input(5)
+            .map(|initial_h| recurrent_attributes.batch_first(&initial_h, &[1, 0, 2]));
+
+        let activations = recurrent_attributes.activations(&["Tanh"]);
+        let config = RecurrentConfig {
+            input_weights: &W_tensor,
+            recurrent_weights: &R_tensor,
+            bias: B_tensor.as_ref(),
+            sequence_lens: sequence_lens_tensor.as_ref(),
+            direction: recurrent_attributes.direction(),
+            activations: &activations[..],
+            clip: recurrent_attributes.clip,
+        };
+        let (Y_tensor, Y_h_tensor) = X_tensor.RNN(config, initial_h_tensor.as_ref());
+        let outputs = [
+            recurrent_attributes.batch_first(&Y_tensor, &[2, 0, 1, 3]),
+            recurrent_attributes.batch_first(&Y_h_tensor, &[1, 0, 2]),
+        ];
+
+        // Outputs are only tracked when requested
+        node_proto
+            .output
+            .iter()
+            .zip(outputs)
+            .filter(|(name, _)| !name.is_empty())
+            .for_each(|(name, tensor)| self.track_tensor(name, tensor));
+    }
+
     fn RandomNormalLike(&self, node_proto: &onnx::NodeProto) {
         let attributes = node_proto.attributes();
         let dtype = attributes.get("dtype").map(Into::<i64>::into);
@@ -916,11 +2328,111 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
             .map(|i| start + ((i as f32) * delta))
             .collect::<Vec<_>>();
 
-        let output_tensor = Tensor::from_contiguous(&data[..], &[n]);
-        self.track_tensor(output, output_tensor);
+        let output_tensor = Tensor::from_contiguous(&data[..], &[n]);
+        self.track_tensor(output, output_tensor);
+    }
+
+    fn ReduceMean(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let keepdims = attributes
+            .get("keepdims")
+            .map(Into::<bool>::into)
+            .unwrap_or(true);
+        let noop_with_empty_axes = attributes
+            .get("noop_with_empty_axes")
+            .map(Into::<bool>::into)
+            .unwrap_or(false);
+        log::trace!(
+            "[ONNX] [ReduceMean] [keepdims={}] [noop_with_empty_axes={}]",
+            keepdims,
+            noop_with_empty_axes
+        );
+
+        let [data, axes @ ..] = &node_proto.input[..] else {
+            panic!("ReduceMean expects [data] [axes] -> [reduced]")
+        };
+        let [reduced] = &node_proto.output[..] else {
+            panic!("ReduceMean expects [data] [axes] -> [reduced]")
+        };
+
+        let data_tensor = self.tensor(data).unwrap();
+        let axes = axes
+            .first()
+            .map(|axes| self.tensor(axes))
+            .flatten()
+            .map(|tensor| tensor.load())
+            .map(|bytes| {
+                bytemuck::cast_slice::<u8, f32>(&bytes[..])
+                    .iter()
+                    .map(|&x: &f32| x as i32)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or(
+                (0..data_tensor.ndim())
+                    .into_iter()
+                    .map(|axis| axis as i32)
+                    .collect::<Vec<_>>(),
+            );
+
+        let reduced_tensor = if noop_with_empty_axes {
+            data_tensor.clone()
+        } else {
+            data_tensor.Mean(&axes[..], keepdims)
+        };
+        self.track_tensor(reduced, reduced_tensor);
+    }
+
+    fn ReduceMin(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let keepdims = attributes
+            .get("keepdims")
+            .map(Into::<bool>::into)
+            .unwrap_or(true);
+        let noop_with_empty_axes = attributes
+            .get("noop_with_empty_axes")
+            .map(Into::<bool>::into)
+            .unwrap_or(false);
+        log::trace!(
+            "[ONNX] [ReduceMin] [keepdims={}] [noop_with_empty_axes={}]",
+            keepdims,
+            noop_with_empty_axes
+        );
+
+        let [data, axes @ ..] = &node_proto.input[..] else {
+            panic!("ReduceMin expects [data] [axes] -> [reduced]")
+        };
+        let [reduced] = &node_proto.output[..] else {
+            panic!("ReduceMin expects [data] [axes] -> [reduced]")
+        };
+
+        let data_tensor = self.tensor(data).unwrap();
+        let axes = axes
+            .first()
+            .map(|axes| self.tensor(axes))
+            .flatten()
+            .map(|tensor| tensor.load())
+            .map(|bytes| {
+                bytemuck::cast_slice::<u8, f32>(&bytes[..])
+                    .iter()
+                    .map(|&x: &f32| x as i32)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or(
+                (0..data_tensor.ndim())
+                    .into_iter()
+                    .map(|axis| axis as i32)
+                    .collect::<Vec<_>>(),
+            );
+
+        let reduced_tensor = if noop_with_empty_axes {
+            data_tensor.clone()
+        } else {
+            data_tensor.Min(&axes[..], keepdims)
+        };
+        self.track_tensor(reduced, reduced_tensor);
     }
 
-    fn ReduceMean(&self, node_proto: &onnx::NodeProto) {
+    fn ReduceProd(&self, node_proto: &onnx::NodeProto) {
         let attributes = node_proto.attributes();
         let keepdims = attributes
             .get("keepdims")
@@ -931,16 +2443,16 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
             .map(Into::<bool>::into)
             .unwrap_or(false);
         log::trace!(
-            "[ONNX] [ReduceMean] [keepdims={}] [noop_with_empty_axes={}]",
+            "[ONNX] [ReduceProd] [keepdims={}] [noop_with_empty_axes={}]",
             keepdims,
             noop_with_empty_axes
         );
 
         let [data, axes @ ..] = &node_proto.input[..] else {
-            panic!("ReduceMean expects [data] [axes] -> [reduced]")
+            panic!("ReduceProd expects [data] [axes] -> [reduced]")
         };
         let [reduced] = &node_proto.output[..] else {
-            panic!("ReduceMean expects [data] [axes] -> [reduced]")
+            panic!("ReduceProd expects [data] [axes] -> [reduced]")
         };
 
         let data_tensor = self.tensor(data).unwrap();
@@ -965,7 +2477,7 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         let reduced_tensor = if noop_with_empty_axes {
             data_tensor.clone()
         } else {
-            data_tensor.Mean(&axes[..], keepdims)
+            data_tensor.Prod(&axes[..], keepdims)
         };
         self.track_tensor(reduced, reduced_tensor);
     }
@@ -990,54 +2502,270 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         let shape = shape_tensor.load::<f32>();
         // Map [] -> [1]
         let shape = if shape.len() == 0 { vec![1.0] } else { shape };
-        let shape = &shape[..];
+        let shape = shape.iter().map(|&x| x as i32).collect::<Vec<_>>();
 
-        let data_tensor_size = data_tensor.len();
-        let shape_size = shape.iter().product::<f32>().abs() as i32;
-        let inferred_size = data_tensor_size / shape_size;
+        let reshaped_tensor = data_tensor.view_as(&shape[..]);
+        self.track_tensor(reshaped, reshaped_tensor);
+    }
 
-        let shape = shape
-            .into_iter()
-            .enumerate()
-            .map(|(axis, &dimension)| {
-                let dimension = dimension as i64;
+    fn Resize(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let antialias = attributes
+            .get("antialias")
+            .map(Into::<bool>::into)
+            .unwrap_or(false);
+        let axes = attributes.get("axes").map(Into::<Vec<i64>>::into);
+        let coordinate_transformation_mode = attributes
+            .get("coordinate_transformation_mode")
+            .map(Into::<String>::into)
+            .unwrap_or("half_pixel".to_string());
+        let cubic_coeff_a = attributes
+            .get("cubic_coeff_a")
+            .map(Into::<f32>::into)
+            .unwrap_or(-0.75);
+        let exclude_outside = attributes
+            .get("exclude_outside")
+            .map(Into::<bool>::into)
+            .unwrap_or(false);
+        let extrapolation_value = attributes
+            .get("extrapolation_value")
+            .map(Into::<f32>::into)
+            .unwrap_or(0.0);
+        let keep_aspect_ratio_policy = attributes
+            .get("keep_aspect_ratio_policy")
+            .map(Into::<String>::into)
+            .unwrap_or("stretch".to_string());
+        let mode = attributes
+            .get("mode")
+            .map(Into::<String>::into)
+            .unwrap_or("nearest".to_string());
+        let nearest_mode = attributes
+            .get("nearest_mode")
+            .map(Into::<String>::into)
+            .unwrap_or("round_prefer_floor".to_string());
+        log::trace!(
+            "[ONNX] [Resize] [antialias={}] [axes={:?}] [coordinate_transformation_mode={}] [cubic_coeff_a={}] [exclude_outside={}] [extrapolation_value={}] [keep_aspect_ratio_policy={}] [mode={}] [nearest_mode={}]",
+            antialias,
+            axes,
+            coordinate_transformation_mode,
+            cubic_coeff_a,
+            exclude_outside,
+            extrapolation_value,
+            keep_aspect_ratio_policy,
+            mode,
+            nearest_mode
+        );
 
-                assert!(
-                    dimension >= -1,
-                    "Reshape does not support negative dimensions"
-                );
+        assert!(!antialias, "Resize: antialiasing is not supported");
+
+        let [X, optional_inputs @ .., Y] = &node_proto.io_interface()[..] else {
+            panic!("Resize expects [X] [roi] [scales] [sizes] -> [Y]")
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+        // Absent inputs are either missing or empty
+        let optional_input = |index: usize| {
+            optional_inputs
+                .get(index)
+                .and_then(|name| self.tensor(name))
+                .map(|tensor| tensor.load::<f32>())
+                .filter(|values| !values.is_empty())
+        };
+        let roi = optional_input(0);
+        let scales = optional_input(1);
+        let sizes = optional_input(2);
 
-                if dimension == -1 {
-                    inferred_size as i32
-                } else if dimension == 0 {
-                    if allowzero {
-                        0
-                    } else {
-                        data_tensor.shape()[axis]
+        let shape = X_tensor.shape().to_vec();
+        let rank = shape.len() as i64;
+        let axes = axes
+            .unwrap_or((0..rank).collect())
+            .iter()
+            .map(|&axis| ((axis + rank) % rank) as usize)
+            .collect::<Vec<_>>();
+
+        // Dimensions outside of axes are kept as they are
+        let mut output_sizes = shape.clone();
+        let mut output_scales = vec![1.0; shape.len()];
+        match (scales, sizes) {
+            (Some(scales), None) => {
+                for (&axis, &scale) in axes.iter().zip(scales.iter()) {
+                    output_sizes[axis] = (shape[axis] as f32 * scale).floor() as i32;
+                    output_scales[axis] = scale;
+                }
+            }
+            (None, Some(sizes)) => {
+                let axis_scales = axes
+                    .iter()
+                    .zip(sizes.iter())
+                    .map(|(&axis, &size)| size / shape[axis] as f32)
+                    .collect::<Vec<_>>();
+
+                // The aspect ratio may be kept by applying a single scale to all axes
+                let policy_scale = match keep_aspect_ratio_policy.as_str() {
+                    "stretch" => None,
+                    "not_larger" => axis_scales.iter().copied().reduce(f32::min),
+                    "not_smaller" => axis_scales.iter().copied().reduce(f32::max),
+                    _ => panic!(
+                        "Resize does not support `{}` aspect ratio policy",
+                        keep_aspect_ratio_policy
+                    ),
+                };
+                for (i, &axis) in axes.iter().enumerate() {
+                    match policy_scale {
+                        Some(scale) => {
+                            output_sizes[axis] = (shape[axis] as f32 * scale).round() as i32;
+                            output_scales[axis] = scale;
+                        }
+                        None => {
+                            output_sizes[axis] = sizes[i] as i32;
+                            output_scales[axis] = axis_scales[i];
+                        }
                     }
-                } else {
-                    dimension as i32
                 }
-            })
-            .collect::<Vec<_>>();
-        let view = TensorView::from_contiguous_shape(&shape[..]);
+            }
+            _ => panic!("Resize expects exactly one of scales and sizes"),
+        }
+
+        // Roi is laid out as [x1_start, x2_start, ..., x1_end, x2_end, ...] following axes
+        let mut region = vec![(0.0, 1.0); shape.len()];
+        if let Some(roi) = roi {
+            for (i, &axis) in axes.iter().enumerate() {
+                region[axis] = (roi[i], roi[i + axes.len()]);
+            }
+        }
+
+        let transformation = match coordinate_transformation_mode.as_str() {
+            "half_pixel" => CoordinateTransformation::HalfPixel,
+            "half_pixel_symmetric" => CoordinateTransformation::HalfPixelSymmetric,
+            "pytorch_half_pixel" => CoordinateTransformation::PytorchHalfPixel,
+            "align_corners" => CoordinateTransformation::AlignCorners,
+            "asymmetric" => CoordinateTransformation::Asymmetric,
+            "tf_crop_and_resize" => {
+                CoordinateTransformation::TfCropAndResize(&region[..], extrapolation_value)
+            }
+            _ => panic!(
+                "Resize does not support `{}` coordinate transformation",
+                coordinate_transformation_mode
+            ),
+        };
+        let resize_mode = match (mode.as_str(), nearest_mode.as_str()) {
+            ("nearest", "round_prefer_floor") => ResizeMode::Nearest(NearestMode::RoundPreferFloor),
+            ("nearest", "round_prefer_ceil") => ResizeMode::Nearest(NearestMode::RoundPreferCeil),
+            ("nearest", "floor") => ResizeMode::Nearest(NearestMode::Floor),
+            ("nearest", "ceil") => ResizeMode::Nearest(NearestMode::Ceil),
+            ("nearest", _) => panic!("Resize does not support `{}` nearest mode", nearest_mode),
+            ("linear", _) => ResizeMode::Linear,
+            ("cubic", _) => ResizeMode::Cubic(cubic_coeff_a),
+            _ => panic!("Resize does not support `{}` mode", mode),
+        };
 
-        assert!(
-            data_tensor_size == view.len(),
-            "Unable to reshape from {:?} -> {:?}",
-            data_tensor.shape(),
-            shape
+        let Y_tensor = X_tensor.Resize(
+            &output_sizes[..],
+            &output_scales[..],
+            resize_mode,
+            transformation,
+            exclude_outside,
+        );
+        self.track_tensor(Y, Y_tensor);
+    }
+
+    fn STFT(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let onesided = attributes
+            .get("onesided")
+            .map(Into::<bool>::into)
+            .unwrap_or(true);
+        log::trace!("[ONNX] [STFT] [onesided={}]", onesided);
+
+        let [signal, frame_step, ..] = &node_proto.input[..] else {
+            panic!("STFT expects [signal] [frame_step] [window] [frame_length] -> [output]")
+        };
+        let [output] = &node_proto.output[..] else {
+            panic!("STFT expects [signal] [frame_step] [window] [frame_length] -> [output]")
+        };
+        // Optional inputs are either missing or empty
+        let optional_input = |index: usize| {
+            node_proto
+                .input
+                .get(index)
+                .and_then(|name| self.tensor(name))
+        };
+
+        let signal_tensor = self.tensor(signal).unwrap();
+        let frame_step = self.tensor(frame_step).unwrap().load::<f32>()[0] as i32;
+        let window_tensor = optional_input(2);
+        let frame_length = optional_input(3).map(|tensor| tensor.load::<f32>()[0] as i32);
+
+        let output_tensor =
+            signal_tensor.STFT(frame_step, window_tensor.as_ref(), frame_length, onesided);
+        self.track_tensor(output, output_tensor);
+    }
+
+    fn ScatterElements(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let axis = attributes.get("axis").map(Into::<i64>::into).unwrap_or(0);
+        let reduction = attributes
+            .get("reduction")
+            .map(Into::<String>::into)
+            .unwrap_or("none".to_string());
+        log::trace!(
+            "[ONNX] [ScatterElements] [axis={}] [reduction={}]",
+            axis,
+            reduction
         );
 
-        let reshaped_tensor = data_tensor.reshape(&view);
-        self.track_tensor(reshaped, reshaped_tensor);
+        let [data, indices, updates, output] = &node_proto.io_interface()[..] else {
+            panic!("ScatterElements expects [data] [indices] [updates] -> [output]")
+        };
+
+        let data_tensor = self.tensor(data).unwrap();
+        let indices_tensor = self.tensor(indices).unwrap();
+        let updates_tensor = self.tensor(updates).unwrap();
+
+        let reduction = match reduction.as_str() {
+            "none" => ScatterReduction::None,
+            "add" => ScatterReduction::Add,
+            "mul" => ScatterReduction::Mul,
+            "max" => ScatterReduction::Max,
+            "min" => ScatterReduction::Min,
+            _ => panic!("ScatterElements does not support `{}` reduction", reduction),
+        };
+
+        let data_dimension = data_tensor.ndim() as i64;
+        let axis = ((axis + data_dimension) % data_dimension) as i32;
+
+        let output_tensor =
+            data_tensor.ScatterElements(axis, reduction, &indices_tensor, &updates_tensor);
+        self.track_tensor(output, output_tensor);
     }
 
-    // fn Resize(&self, node_proto: &onnx::NodeProto) {
-    //     // TODO P1
-    //     Tensor::scalar(1);
-    //     panic!("Resize has not been implemented");
-    // }
+    fn ScatterND(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let reduction = attributes
+            .get("reduction")
+            .map(Into::<String>::into)
+            .unwrap_or("none".to_string());
+        log::trace!("[ONNX] [ScatterND] [reduction={}]", reduction);
+
+        let [data, indices, updates, output] = &node_proto.io_interface()[..] else {
+            panic!("ScatterND expects [data] [indices] [updates] -> [output]")
+        };
+
+        let data_tensor = self.tensor(data).unwrap();
+        let indices_tensor = self.tensor(indices).unwrap();
+        let updates_tensor = self.tensor(updates).unwrap();
+        let reduction = match reduction.as_str() {
+            "none" => ScatterReduction::None,
+            "add" => ScatterReduction::Add,
+            "mul" => ScatterReduction::Mul,
+            "max" => ScatterReduction::Max,
+            "min" => ScatterReduction::Min,
+            _ => panic!("ScatterND does not support `{}` reduction", reduction),
+        };
+
+        let output_tensor = data_tensor.ScatterND(reduction, &indices_tensor, &updates_tensor);
+        self.track_tensor(output, output_tensor);
+    }
 
     fn Shape(&self, node_proto: &onnx::NodeProto) {
         let attributes = node_proto.attributes();
@@ -1174,6 +2902,55 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         self.track_tensor(output, output_tensor);
     }
 
+    fn Split(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let axis = attributes.get("axis").map(Into::<i64>::into).unwrap_or(0);
+        let num_outputs = attributes.get("num_outputs").map(Into::<i64>::into);
+        log::trace!(
+            "[ONNX] [Split] [axis={}] [num_outputs={:?}]",
+            axis,
+            num_outputs
+        );
+
+        let io_interface = node_proto.io_interface();
+        let n_inputs = node_proto.input.len();
+        let ([input, optional_inputs @ ..], outputs) = io_interface.split_at(n_inputs) else {
+            panic!("Split expects [input] [split] -> [outputs]...")
+        };
+
+        let input_tensor = self.tensor(input).unwrap();
+
+        let data_rank = input_tensor.ndim() as i64;
+        let axis = ((axis + data_rank) % data_rank) as i32;
+        let axis_shape = input_tensor.shape()[axis as usize];
+
+        let splits = optional_inputs
+            .first()
+            .and_then(|split| self.tensor(split))
+            .map(|tensor| {
+                tensor
+                    .load::<f32>()
+                    .iter()
+                    .map(|&x| x as i32)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_else(|| {
+                // Chunks of ceil(axis_shape / n_outputs), the trailing chunks are smaller or even
+                // empty if the axis is not evenly divisible
+                let n_outputs = num_outputs.unwrap_or(outputs.len() as i64) as i32;
+                let chunk = (axis_shape + n_outputs - 1) / n_outputs;
+                (0..n_outputs)
+                    .map(|i| (axis_shape - i * chunk).clamp(0, chunk))
+                    .collect::<Vec<_>>()
+            });
+
+        input_tensor
+            .Split(axis, &splits[..])
+            .into_iter()
+            .zip(outputs.iter())
+            .for_each(|(output_tensor, output)| self.track_tensor(output, output_tensor));
+    }
+
     fn Sqrt(&self, node_proto: &onnx::NodeProto) {
         log::trace!("[ONNX] [Sqrt]");
 
@@ -1241,6 +3018,63 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         self.track_tensor(output, output_tensor);
     }
 
+    fn Tile(&self, node_proto: &onnx::NodeProto) {
+        log::trace!("[ONNX] [Tile]");
+
+        let [input, repeats, output] = &node_proto.io_interface()[..] else {
+            panic!("Tile expects [input] [repeats] -> [output]")
+        };
+
+        let input_tensor = self.tensor(input).unwrap();
+        let repeats_tensor = self.tensor(repeats).unwrap();
+
+        let repeats = repeats_tensor
+            .load::<f32>()
+            .into_iter()
+            .map(|x| x as i32)
+            .collect::<Vec<_>>();
+        assert!(
+            repeats.len() == input_tensor.ndim() as usize,
+            "Tile expects repeats ({}) to be defined for every dimension ({})",
+            repeats.len(),
+            input_tensor.ndim()
+        );
+
+        let output_tensor = input_tensor.Tile(&repeats[..]);
+        self.track_tensor(output, output_tensor);
+    }
+
+    fn TopK(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let axis = attributes.get("axis").map(Into::<i64>::into).unwrap_or(-1);
+        let largest = attributes
+            .get("largest")
+            .map(Into::<bool>::into)
+            .unwrap_or(true);
+        let sorted = attributes
+            .get("sorted")
+            .map(Into::<bool>::into)
+            .unwrap_or(true);
+        log::trace!(
+            "[ONNX] [TopK] [axis={}] [largest={}] [sorted={}]",
+            axis,
+            largest,
+            sorted
+        );
+
+        let [X, K, Values, Indices] = &node_proto.io_interface()[..] else {
+            panic!("TopK expects [X] [K] -> [Values] [Indices]")
+        };
+
+        let X_tensor = self.tensor(X).unwrap();
+        let k = self.tensor(K).unwrap().load::<f32>()[0] as i32;
+        let axis = ((axis as i32) + X_tensor.ndim()) % X_tensor.ndim();
+
+        let (Values_tensor, Indices_tensor) = X_tensor.TopK(k, axis, largest, sorted);
+        self.track_tensor(Values, Values_tensor);
+        self.track_tensor(Indices, Indices_tensor);
+    }
+
     fn Transpose(&self, node_proto: &onnx::NodeProto) {
         let attributes = node_proto.attributes();
         let perm = attributes.get("perm").map(Into::<Vec<i64>>::into);
@@ -1267,6 +3101,40 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         self.track_tensor(transposed, transposed_tensor);
     }
 
+    fn Unique(&self, node_proto: &onnx::NodeProto) {
+        let attributes = node_proto.attributes();
+        let axis = attributes.get("axis").map(Into::<i64>::into);
+        let sorted = attributes
+            .get("sorted")
+            .map(Into::<bool>::into)
+            .unwrap_or(true);
+        log::trace!("[ONNX] [Unique] [axis={:?}] [sorted={}]", axis, sorted);
+
+        let [X] = &node_proto.input[..] else {
+            panic!("Unique expects [X] -> [Y] [indices] [inverse_indices] [counts]")
+        };
+
+        // The output shapes depend on the data, so the input is evaluated first
+        let X_tensor = self.evaluate(&self.tensor(X).unwrap());
+        let axis = axis.map(|axis| ((axis as i32) + X_tensor.ndim()) % X_tensor.ndim());
+
+        let (Y_tensor, indices_tensor, inverse_indices_tensor, counts_tensor) =
+            X_tensor.Unique(axis, sorted);
+
+        // Every output besides Y is optional
+        node_proto
+            .output
+            .iter()
+            .zip([
+                Y_tensor,
+                indices_tensor,
+                inverse_indices_tensor,
+                counts_tensor,
+            ])
+            .filter(|(output, _)| !output.is_empty())
+            .for_each(|(output, output_tensor)| self.track_tensor(output, output_tensor));
+    }
+
     fn Unsqueeze(&self, node_proto: &onnx::NodeProto) {
         let [data, axes, expanded] = &node_proto.io_interface()[..] else {
             panic!("Unsqueeze expects [data] [axes] -> [expanded]")
@@ -1307,14 +3175,312 @@ impl onnx::runtime::OnnxRuntime for OpsetV21 {
         let y_tensor = self.tensor(y).unwrap();
 
         // When True (nonzero), yield X, otherwise yield Y
-        let zero = Tensor::scalar(0);
-        let condition_zero = condition_tensor.Equal(&zero);
-        let condition_nonzero = condition_zero.Not();
+        let output_tensor = condition_tensor.Where(&x_tensor, &y_tensor);
+        self.track_tensor(output, output_tensor);
+    }
+}
 
-        let yield_x = condition_nonzero.Multiply(&x_tensor);
-        let yield_y = condition_zero.Multiply(&y_tensor);
+// Attributes shared by the windowed pooling operators
+#[derive(Debug)]
+struct PoolAttributes {
+    auto_pad: String,
+    ceil_mode: bool,
+    dilations: Vec<i32>,
+    kernel_shape: Vec<i32>,
+    pads: Option<Vec<i64>>,
+    strides: Vec<i32>,
+}
 
-        let output_tensor = yield_x.Add(&yield_y);
-        self.track_tensor(output, output_tensor);
+impl PoolAttributes {
+    fn new(attributes: &HashMap<String, onnx::AttributeProto>) -> PoolAttributes {
+        let auto_pad = attributes
+            .get("auto_pad")
+            .map(Into::<String>::into)
+            .unwrap_or("NOTSET".to_string());
+        let ceil_mode = attributes
+            .get("ceil_mode")
+            .map(Into::<bool>::into)
+            .unwrap_or(false);
+        let kernel_shape = attributes
+            .get("kernel_shape")
+            .map(Into::<Vec<i64>>::into)
+            .expect("Pooling expects a kernel_shape")
+            .iter()
+            .map(|&dimension| dimension as i32)
+            .collect::<Vec<_>>();
+        let dilations = attributes
+            .get("dilations")
+            .map(Into::<Vec<i64>>::into)
+            .unwrap_or(vec![1; kernel_shape.len()])
+            .iter()
+            .map(|&dilation| dilation as i32)
+            .collect::<Vec<_>>();
+        let pads = attributes.get("pads").map(Into::<Vec<i64>>::into);
+        let strides = attributes
+            .get("strides")
+            .map(Into::<Vec<i64>>::into)
+            .unwrap_or(vec![1; kernel_shape.len()])
+            .iter()
+            .map(|&stride| stride as i32)
+            .collect::<Vec<_>>();
+
+        PoolAttributes {
+            auto_pad,
+            ceil_mode,
+            dilations,
+            kernel_shape,
+            pads,
+            strides,
+        }
+    }
+
+    // ONNX only pads the features, the batch and channel dimensions are left as is
+    fn padding(&self, input: &Tensor) -> Vec<(i32, i32)> {
+        let features = self.kernel_shape.len();
+        let pads = self.pads.clone().unwrap_or(vec![0; 2 * features]);
+        let (padding_pre, padding_post) = pads.split_at(features);
+
+        (0..input.ndim() as usize - features)
+            .map(|_| (0, 0))
+            .chain(
+                padding_pre
+                    .iter()
+                    .zip(padding_post.iter())
+                    .map(|(&pre, &post)| (pre as i32, post as i32)),
+            )
+            .collect::<Vec<_>>()
+    }
+
+    fn conv_padding<'a>(&self, padding: &'a [(i32, i32)]) -> ConvPadding<'a> {
+        match self.auto_pad.as_str() {
+            "SAME_UPPER" => ConvPadding::SameUpper,
+            "SAME_LOWER" => ConvPadding::SameLower,
+            "VALID" => ConvPadding::Valid,
+            "NOTSET" => ConvPadding::Custom(padding),
+            _ => unreachable!("Invalid pooling padding scheme, got {}", self.auto_pad),
+        }
+    }
+}
+
+// Attributes shared by the integer convolutions
+#[derive(Debug)]
+struct ConvAttributes {
+    auto_pad: String,
+    dilations: Vec<i32>,
+    group: i32,
+    pads: Option<Vec<i64>>,
+    strides: Vec<i32>,
+}
+
+impl ConvAttributes {
+    // Defaults cover every feature dimension of the kernel
+    fn new(attributes: &HashMap<String, onnx::AttributeProto>, kernel: &Tensor) -> ConvAttributes {
+        let features = kernel.ndim() as usize - 2;
+        let auto_pad = attributes
+            .get("auto_pad")
+            .map(Into::<String>::into)
+            .unwrap_or("NOTSET".to_string());
+        let dilations = attributes
+            .get("dilations")
+            .map(Into::<Vec<i64>>::into)
+            .unwrap_or(vec![1; features])
+            .iter()
+            .map(|&dilation| dilation as i32)
+            .collect::<Vec<_>>();
+        let group = attributes.get("group").map(Into::<i64>::into).unwrap_or(1) as i32;
+        let pads = attributes.get("pads").map(Into::<Vec<i64>>::into);
+        let strides = attributes
+            .get("strides")
+            .map(Into::<Vec<i64>>::into)
+            .unwrap_or(vec![1; features])
+            .iter()
+            .map(|&stride| stride as i32)
+            .collect::<Vec<_>>();
+
+        ConvAttributes {
+            auto_pad,
+            dilations,
+            group,
+            pads,
+            strides,
+        }
+    }
+
+    // ONNX only pads the features, the batch and channel dimensions are left as is
+    fn padding(&self, input: &Tensor) -> Vec<(i32, i32)> {
+        let features = self.dilations.len();
+        let pads = self.pads.clone().unwrap_or(vec![0; 2 * features]);
+        let (padding_pre, padding_post) = pads.split_at(features);
+
+        (0..input.ndim() as usize - features)
+            .map(|_| (0, 0))
+            .chain(
+                padding_pre
+                    .iter()
+                    .zip(padding_post.iter())
+                    .map(|(&pre, &post)| (pre as i32, post as i32)),
+            )
+            .collect::<Vec<_>>()
+    }
+
+    fn config<'a>(&'a self, padding: &'a [(i32, i32)]) -> ConvConfig<'a> {
+        let padding = match self.auto_pad.as_str() {
+            "SAME_UPPER" => ConvPadding::SameUpper,
+            "SAME_LOWER" => ConvPadding::SameLower,
+            "VALID" => ConvPadding::Valid,
+            "NOTSET" => ConvPadding::Custom(padding),
+            _ => unreachable!("Invalid Conv padding scheme, got {}", self.auto_pad),
+        };
+
+        ConvConfig {
+            kernel_strides: &self.strides[..],
+            dilations: &self.dilations[..],
+            group: self.group,
+            padding,
+        }
+    }
+}
+
+// Attributes shared by the recurrent operators
+#[derive(Debug)]
+struct RecurrentAttributes {
+    activation_alpha: Vec<f32>,
+    activation_beta: Vec<f32>,
+    activations: Option<Vec<String>>,
+    clip: Option<f32>,
+    direction: String,
+    layout: i64,
+}
+
+impl RecurrentAttributes {
+    fn new(attributes: &HashMap<String, onnx::AttributeProto>) -> RecurrentAttributes {
+        let activation_alpha = attributes
+            .get("activation_alpha")
+            .map(Into::<Vec<f32>>::into)
+            .unwrap_or(vec![]);
+        let activation_beta = attributes
+            .get("activation_beta")
+            .map(Into::<Vec<f32>>::into)
+            .unwrap_or(vec![]);
+        let activations = attributes.get("activations").map(Into::<Vec<String>>::into);
+        let clip = attributes.get("clip").map(Into::<f32>::into);
+        let direction = attributes
+            .get("direction")
+            .map(Into::<String>::into)
+            .unwrap_or("forward".to_string());
+        let layout = attributes.get("layout").map(Into::<i64>::into).unwrap_or(0);
+
+        RecurrentAttributes {
+            activation_alpha,
+            activation_beta,
+            activations,
+            clip,
+            direction,
+            layout,
+        }
+    }
+
+    fn direction(&self) -> RecurrentDirection {
+        match self.direction.as_str() {
+            "forward" => RecurrentDirection::Forward,
+            "reverse" => RecurrentDirection::Reverse,
+            "bidirectional" => RecurrentDirection::Bidirectional,
+            _ => panic!(
+                "Recurrent layers do not support `{}` direction",
+                self.direction
+            ),
+        }
+    }
+
+    // Alpha and beta values are consumed in order by the activations which take them
+    fn activations(&self, defaults: &[&str]) -> Vec<RecurrentActivation> {
+        let defaults = defaults
+            .iter()
+            .map(|activation| activation.to_string())
+            .collect::<Vec<_>>();
+        let activations = self.activations.clone().unwrap_or(defaults);
+
+        let mut alphas = self.activation_alpha.iter().copied();
+        let mut betas = self.activation_beta.iter().copied();
+        activations
+            .iter()
+            .map(|activation| match activation.to_lowercase().as_str() {
+                "affine" => RecurrentActivation::Affine(
+                    alphas.next().unwrap_or(1.0),
+                    betas.next().unwrap_or(0.0),
+                ),
+                "elu" => RecurrentActivation::Elu(alphas.next().unwrap_or(1.0)),
+                "hardsigmoid" => RecurrentActivation::HardSigmoid(
+                    alphas.next().unwrap_or(0.2),
+                    betas.next().unwrap_or(0.5),
+                ),
+                "leakyrelu" => RecurrentActivation::LeakyRelu(alphas.next().unwrap_or(0.01)),
+                "relu" => RecurrentActivation::Relu,
+                "scaledtanh" => RecurrentActivation::ScaledTanh(
+                    alphas.next().unwrap_or(1.0),
+                    betas.next().unwrap_or(1.0),
+                ),
+                "sigmoid" => RecurrentActivation::Sigmoid,
+                "softplus" => RecurrentActivation::Softplus,
+                "softsign" => RecurrentActivation::Softsign,
+                "tanh" => RecurrentActivation::Tanh,
+                "thresholdedrelu" => {
+                    RecurrentActivation::ThresholdedRelu(alphas.next().unwrap_or(1.0))
+                }
+                _ => panic!(
+                    "Recurrent layers do not support `{}` activation",
+                    activation
+                ),
+            })
+            .collect::<Vec<_>>()
+    }
+
+    // Batch major layouts swap the sequence and batch axes of the inputs and outputs
+    fn batch_first(&self, tensor: &Tensor, permutation: &[i32]) -> Tensor {
+        match self.layout {
+            0 => tensor.clone(),
+            _ => tensor.transpose(permutation),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node_proto(op_type: &str, input: &[&str], output: &[&str]) -> onnx::NodeProto {
+        onnx::NodeProto {
+            op_type: op_type.to_string(),
+            input: input.iter().map(|name| name.to_string()).collect(),
+            output: output.iter().map(|name| name.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn bitwise_float_initializers() {
+        let opset = OpsetV21::default();
+        // Initializers are parsed as F32
+        opset.track_tensor("A", Tensor::from_contiguous(&[12.0, 10.0], &[2]));
+        opset.track_tensor("B", Tensor::from_contiguous(&[10.0, 3.0], &[2]));
+
+        ["BitwiseAnd", "BitwiseOr", "BitwiseXor"]
+            .iter()
+            .for_each(|op_type| {
+                opset.serialize_node(&node_proto(op_type, &["A", "B"], &["C"]));
+                assert_eq!(opset.tensor("C").unwrap().datatype(), TensorType::I32);
+            });
+
+        opset.serialize_node(&node_proto("BitwiseNot", &["A"], &["C"]));
+        assert_eq!(opset.tensor("C").unwrap().datatype(), TensorType::I32);
+
+        let mut shift = node_proto("BitShift", &["A", "B"], &["C"]);
+        shift.attribute.push(onnx::AttributeProto {
+            name: "direction".to_string(),
+            s: b"LEFT".to_vec(),
+            ..Default::default()
+        });
+        opset.serialize_node(&shift);
+        assert_eq!(opset.tensor("C").unwrap().datatype(), TensorType::I32);
     }
 }
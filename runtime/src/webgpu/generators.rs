@@ -1,8 +1,17 @@
 use tensor::primitives::tensor::TensorType;
-use tensor::primitives::tensorview::TensorViewTracker;
+use tensor::primitives::tensorview::{PadMode, TensorViewTracker, ViewType};
 
+pub mod attention;
 pub mod binary;
+pub mod concat;
+pub mod lu;
+pub mod matmul;
 pub mod reduce;
+pub mod scan;
+pub mod sort;
+pub mod take;
+pub mod ternary;
+pub mod triangular;
 pub mod unary;
 
 pub fn wgsl_from_tensortype(datatype: TensorType) -> String {
@@ -11,6 +20,7 @@ pub fn wgsl_from_tensortype(datatype: TensorType) -> String {
         TensorType::F32 => "f32",
         TensorType::I32 => "i32",
         TensorType::U32 => "u32",
+        TensorType::I8 | TensorType::U8 => "i32",
     }
     .to_string()
 }
@@ -28,42 +38,212 @@ pub fn compute_index(
     )
 }
 
+// Reads of constant padded views that fall outside of the input are redirected to a sentinel
+// index, counting down from PADDED_INDEX for each such view, which read_mapped replaces with
+// the padding value
+const PADDED_INDEX: u32 = u32::MAX;
+
 pub fn map_index(index_variable: &str, viewtracker: &TensorViewTracker) -> String {
-    let index_transformation = {
-        viewtracker
-            .serialized_history_fifo()
-            .iter()
-            .fold(index_variable.to_string(), |previous_index, view| {
-                let mapped_index = if view.ndim() == 0 {
-                    format!("0u")
-                } else {
-                    view.shape
-                        .iter()
-                        .zip(view.stride.iter().zip(view.contiguous_stride().iter()))
-                        .map(|(&shape, (&stride, &contiguous_stride))| {
-                            format!(
-                                "((({previous_index} / {contiguous_stride}u) % {shape}u) * {stride}u)",
-                                previous_index = previous_index,
-                                contiguous_stride = contiguous_stride,
-                                shape = shape,
-                                stride = stride,
-                            )
-                        })
-                        .collect::<Vec<_>>()
-                        .join("+")
+    let mut padded_views = 0;
+    let index_transformation = viewtracker
+        .serialized_history_fifo()
+        .iter()
+        .map(|view| {
+            let (mapped_index, predicates) = if view.ndim() == 0 {
+                ("0u".to_string(), Vec::new())
+            } else {
+                let (padding, mode) = match &view.padding {
+                    Some(padding) => (padding.padding.to_vec(), padding.mode),
+                    None => (vec![(0, 0); view.shape.len()], PadMode::Constant(0.0)),
                 };
 
-                format!("({})", mapped_index)
-            })
-    };
+                let (offsets, predicates): (Vec<_>, Vec<_>) = view
+                    .shape
+                    .iter()
+                    .zip(view.stride.iter().zip(view.contiguous_stride().iter()))
+                    .zip(padding)
+                    .map(|((&shape, (&stride, &contiguous_stride)), padding)| {
+                        let coordinate = format!(
+                            "(({index_variable} / {contiguous_stride}u) % {shape}u)",
+                            index_variable = index_variable,
+                            contiguous_stride = contiguous_stride,
+                            shape = shape,
+                        );
+                        let (coordinate, predicate) =
+                            map_padded_coordinate(&coordinate, shape, padding, mode);
+
+                        (
+                            format!(
+                                "({coordinate} * {stride}u)",
+                                coordinate = coordinate,
+                                stride = stride
+                            ),
+                            predicate,
+                        )
+                    })
+                    .unzip();
+
+                (
+                    offsets.join("+"),
+                    predicates.into_iter().flatten().collect(),
+                )
+            };
+
+            let assignment = match predicates.is_empty() {
+                true => format!("{} = ({});", index_variable, mapped_index),
+                false => format!(
+                    "{index_variable} = select({sentinel}u, ({mapped_index}), {predicate});",
+                    index_variable = index_variable,
+                    sentinel = PADDED_INDEX - padded_views,
+                    mapped_index = mapped_index,
+                    predicate = predicates.join(" && "),
+                ),
+            };
+
+            // Redirected indices pass through the remaining views untouched
+            let assignment = match padded_views {
+                0 => assignment,
+                _ => format!(
+                    "if {index_variable} < {sentinel}u {{ {assignment} }}",
+                    index_variable = index_variable,
+                    sentinel = PADDED_INDEX - (padded_views - 1),
+                    assignment = assignment,
+                ),
+            };
+            if !predicates.is_empty() {
+                padded_views += 1;
+            }
+
+            assignment
+        })
+        .collect::<Vec<_>>();
 
     format!(
         "
 {{
-    {index_variable} = {singular_map};
+    {index_transformation}
 }}
 ",
-        index_variable = index_variable,
-        singular_map = index_transformation,
+        index_transformation = index_transformation.join("\n    "),
     )
 }
+
+// Reads a tensor at an index mapped by map_index, selecting the padding value for indices
+// redirected by constant padded views
+pub fn read_mapped(
+    tensor_name: &str,
+    index_variable: &str,
+    viewtracker: &TensorViewTracker,
+    datatype: TensorType,
+) -> String {
+    let fallbacks = viewtracker
+        .serialized_history_fifo()
+        .iter()
+        .filter_map(|view| match &view.padding {
+            Some(padding)
+                if view.ndim() != 0 && padding.padding.iter().any(|&axis| axis != (0, 0)) =>
+            {
+                match padding.mode {
+                    PadMode::Constant(fallback) => Some(fallback),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if fallbacks.is_empty() {
+        return format!("{}[{}]", tensor_name, index_variable);
+    }
+
+    // Redirected indices are still read, so they are pointed at the first element
+    let read = format!(
+        "{tensor_name}[select({index_variable}, 0u, {index_variable} > {last}u)]",
+        tensor_name = tensor_name,
+        index_variable = index_variable,
+        last = PADDED_INDEX - fallbacks.len() as u32,
+    );
+    fallbacks
+        .iter()
+        .enumerate()
+        .fold(read, |read, (padded_view, &fallback)| {
+            format!(
+                "select({read}, {fallback}, {index_variable} == {sentinel}u)",
+                read = read,
+                fallback = wgsl_constant(fallback, datatype),
+                index_variable = index_variable,
+                sentinel = PADDED_INDEX - padded_view as u32,
+            )
+        })
+}
+
+fn wgsl_constant(value: f32, datatype: TensorType) -> String {
+    let wgsl_type = wgsl_from_tensortype(datatype);
+    match datatype {
+        TensorType::F16 | TensorType::F32 if !value.is_finite() => {
+            format!("{}(bitcast<f32>({:#010x}u))", wgsl_type, value.to_bits())
+        }
+        TensorType::F16 | TensorType::F32 => format!("{}({:?})", wgsl_type, value),
+        TensorType::U32 => format!("{}({})", wgsl_type, value as u32),
+        TensorType::I32 | TensorType::I8 | TensorType::U8 => {
+            format!("{}({})", wgsl_type, value as i32)
+        }
+    }
+}
+
+// Returns the coordinate within the unpadded axis, and for constant padding the predicate that
+// it lies within the axis, mirroring the predicated shader IR
+fn map_padded_coordinate(
+    coordinate: &str,
+    shape: ViewType,
+    (padding_pre, padding_post): (ViewType, ViewType),
+    mode: PadMode,
+) -> (String, Option<String>) {
+    if padding_pre == 0 && padding_post == 0 {
+        return (coordinate.to_string(), None);
+    }
+
+    let extent = shape - padding_pre - padding_post;
+    let source = format!(
+        "(i32({coordinate}) - {padding_pre}i)",
+        coordinate = coordinate,
+        padding_pre = padding_pre
+    );
+    let clamped = format!(
+        "clamp({source}, 0i, {last}i)",
+        source = source,
+        last = extent - 1,
+    );
+
+    let (mapped, predicate) = match mode {
+        // Out-of-range reads are clamped to stay in bounds, the predicate discards them
+        PadMode::Constant(_) => (
+            clamped.clone(),
+            Some(format!(
+                "({clamped} == {source})",
+                clamped = clamped,
+                source = source
+            )),
+        ),
+        PadMode::Reflect if extent > 1 => (
+            format!(
+                "({last}i - abs((abs({source}) % {period}i) - {last}i))",
+                last = extent - 1,
+                source = source,
+                period = 2 * (extent - 1),
+            ),
+            None,
+        ),
+        PadMode::Reflect | PadMode::Edge => (clamped, None),
+        PadMode::Wrap => (
+            format!(
+                "((({source} % {extent}i) + {extent}i) % {extent}i)",
+                source = source,
+                extent = extent,
+            ),
+            None,
+        ),
+    };
+
+    (format!("u32({mapped})", mapped = mapped), predicate)
+}
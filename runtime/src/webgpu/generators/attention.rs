@@ -0,0 +1,191 @@
+use tensor::primitives::tensor::{AttentionSpec, Tensor};
+
+use crate::webgpu::generators::*;
+use crate::webgpu::WebGPUTensor;
+use crate::webgpu::WebGPUWorkGroup;
+use crate::webgpu::WORKGROUP_SIZE;
+
+pub fn build_shader(
+    op: &AttentionSpec,
+    query: &Tensor,
+    key: &Tensor,
+    value: &Tensor,
+    mask: Option<&Tensor>,
+    output: &Tensor,
+    workgroups: &WebGPUWorkGroup,
+) -> String {
+    let query_wgpu = Into::<WebGPUTensor>::into(query);
+    let key_wgpu = Into::<WebGPUTensor>::into(key);
+    let value_wgpu = Into::<WebGPUTensor>::into(value);
+    let output_wgpu = Into::<WebGPUTensor>::into(output);
+
+    let [.., l, e] = query.shape() else {
+        panic!("Attention requires query to be at least 2D");
+    };
+    let [.., s, ev] = value.shape() else {
+        panic!("Attention requires value to be at least 2D");
+    };
+
+    // The mask is bound ahead of the output when present
+    let (mask_interface, load_mask, apply_mask, output_binding) = match mask {
+        Some(mask) => {
+            let mask_wgpu = Into::<WebGPUTensor>::into(mask);
+            (
+                mask_wgpu.serialize_type(&wgsl_from_tensortype(mask.datatype()), "3", "read"),
+                format!(
+                    "
+fn load_mask(index: u32) -> f32 {{
+    var mapped_index = index;
+    {map_mask_index}
+    return f32({read_mask});
+}}
+",
+                    map_mask_index = map_index("mapped_index", mask.viewtracker()),
+                    read_mask = read_mapped(
+                        &mask_wgpu.name(),
+                        "mapped_index",
+                        mask.viewtracker(),
+                        mask.datatype()
+                    ),
+                ),
+                format!("score += load_mask(row * {s}u + key);", s = s),
+                "4",
+            )
+        }
+        None => (String::new(), String::new(), String::new(), "3"),
+    };
+
+    // Keys after the query position are never attended to
+    let causal_limit = match op.causal {
+        true => "min(position + 1u, KEYS)",
+        false => "KEYS",
+    };
+
+    // Online softmax: the running maximum rescales the partial sums whenever it grows, so
+    // the scores are never materialized
+    format!(
+        "
+{query_interface}
+
+{key_interface}
+
+{value_interface}
+
+{mask_interface}
+
+{output_interface}
+
+const KEYS: u32 = {s}u;
+
+fn load_query(index: u32) -> f32 {{
+    var mapped_index = index;
+    {map_query_index}
+    return f32({read_query});
+}}
+
+fn load_key(index: u32) -> f32 {{
+    var mapped_index = index;
+    {map_key_index}
+    return f32({read_key});
+}}
+
+fn load_value(index: u32) -> f32 {{
+    var mapped_index = index;
+    {map_value_index}
+    return f32({read_value});
+}}
+{load_mask}
+{workgroup_stride}
+@compute {workgroup_size}
+fn {entry_point}(
+    @builtin(global_invocation_id) global_id: vec3u
+) {{
+    {index}
+
+    // Guard against out-of-bounds work group sizes
+    if index >= {output_length}u {{
+        return;
+    }}
+
+    // Output index (..., position, channel) of the query row and value column
+    let channel = index % {ev}u;
+    let row = index / {ev}u;
+    let position = row % {l}u;
+    let batch = row / {l}u;
+
+    var maximum = bitcast<f32>(0xff800000u);
+    var denominator = 0.0;
+    var accumulator = 0.0;
+    for (var key = 0u; key < {causal_limit}; key++) {{
+        var score = 0.0;
+        for (var inner = 0u; inner < {e}u; inner++) {{
+            score += load_query(row * {e}u + inner) * load_key((batch * KEYS + key) * {e}u + inner);
+        }}
+        score *= bitcast<f32>({scale:#010x}u);
+        {apply_mask}
+
+        // Masked keys carry no weight
+        if bitcast<u32>(score) == 0xff800000u {{
+            continue;
+        }}
+
+        let next_maximum = max(maximum, score);
+        let correction = exp(maximum - next_maximum);
+        let weight = exp(score - next_maximum);
+        denominator = denominator * correction + weight;
+        accumulator = accumulator * correction
+            + weight * load_value((batch * KEYS + key) * {ev}u + channel);
+        maximum = next_maximum;
+    }}
+
+    {output_tensor_name}[index] = accumulator / denominator;
+}}
+",
+        query_interface =
+            query_wgpu.serialize_type(&wgsl_from_tensortype(query.datatype()), "0", "read"),
+        key_interface = key_wgpu.serialize_type(&wgsl_from_tensortype(key.datatype()), "1", "read"),
+        value_interface =
+            value_wgpu.serialize_type(&wgsl_from_tensortype(value.datatype()), "2", "read"),
+        mask_interface = mask_interface,
+        output_interface = output_wgpu.serialize_type(
+            &wgsl_from_tensortype(output.datatype()),
+            output_binding,
+            "read_write"
+        ),
+        s = s,
+        e = e,
+        l = l,
+        ev = ev,
+        map_query_index = map_index("mapped_index", query.viewtracker()),
+        map_key_index = map_index("mapped_index", key.viewtracker()),
+        map_value_index = map_index("mapped_index", value.viewtracker()),
+        read_query = read_mapped(
+            &query_wgpu.name(),
+            "mapped_index",
+            query.viewtracker(),
+            query.datatype()
+        ),
+        read_key = read_mapped(
+            &key_wgpu.name(),
+            "mapped_index",
+            key.viewtracker(),
+            key.datatype()
+        ),
+        read_value = read_mapped(
+            &value_wgpu.name(),
+            "mapped_index",
+            value.viewtracker(),
+            value.datatype()
+        ),
+        output_tensor_name = output_wgpu.name(),
+        load_mask = load_mask,
+        apply_mask = apply_mask,
+        causal_limit = causal_limit,
+        scale = op.scale.to_bits(),
+        workgroup_stride = workgroups.serialize_strides("WORKGROUP_STRIDE"),
+        workgroup_size = WORKGROUP_SIZE.serialize_decorator(),
+        entry_point = "main",
+        index = compute_index("index", "global_id", "WORKGROUP_STRIDE"),
+        output_length = output.len(),
+    )
+}
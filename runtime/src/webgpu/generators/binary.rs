@@ -17,6 +17,13 @@ fn build_webgpu_operation<'a>(op: BinaryType) -> impl Fn(&'a str, &'a str, &'a s
         BinaryType::LESSTHAN => {
             |lhs, rhs, output_type| format!("{}({} < {})", output_type, lhs, rhs)
         }
+        BinaryType::ATAN2 => |lhs, rhs, _| format!("atan2({}, {})", lhs, rhs),
+        BinaryType::BITWISEAND => |lhs, rhs, _| format!("{} & {}", lhs, rhs),
+        BinaryType::BITWISEOR => |lhs, rhs, _| format!("{} | {}", lhs, rhs),
+        BinaryType::BITWISEXOR => |lhs, rhs, _| format!("{} ^ {}", lhs, rhs),
+        // WGSL only shifts by unsigned amounts
+        BinaryType::SHIFTLEFT => |lhs, rhs, _| format!("{} << u32({})", lhs, rhs),
+        BinaryType::SHIFTRIGHT => |lhs, rhs, _| format!("{} >> u32({})", lhs, rhs),
     }
 }
 
@@ -74,8 +81,18 @@ fn {entry_point}(
         map_lhs_index = map_index("lhs_mapped_index", lhs.viewtracker()),
         map_rhs_index = map_index("rhs_mapped_index", rhs.viewtracker()),
         output = {
-            let mapped_lhs_data = format!("{}[lhs_mapped_index]", lhs_wgpu.name());
-            let mapped_rhs_data = format!("{}[rhs_mapped_index]", rhs_wgpu.name());
+            let mapped_lhs_data = read_mapped(
+                &lhs_wgpu.name(),
+                "lhs_mapped_index",
+                lhs.viewtracker(),
+                lhs.datatype(),
+            );
+            let mapped_rhs_data = read_mapped(
+                &rhs_wgpu.name(),
+                "rhs_mapped_index",
+                rhs.viewtracker(),
+                rhs.datatype(),
+            );
             let output =
                 build_webgpu_operation(op)(&mapped_lhs_data, &mapped_rhs_data, &output_datatype);
             output
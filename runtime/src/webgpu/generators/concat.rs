@@ -0,0 +1,121 @@
+use tensor::primitives::tensor::{ConcatSpec, Tensor};
+use tensor::primitives::tensorview::{PadMode, TensorView};
+
+use crate::webgpu::generators::*;
+use crate::webgpu::WebGPUTensor;
+use crate::webgpu::WebGPUWorkGroup;
+use crate::webgpu::WORKGROUP_SIZE;
+
+pub fn build_shader(
+    spec: &ConcatSpec,
+    inputs: &[&Tensor],
+    output: &Tensor,
+    workgroups: &WebGPUWorkGroup,
+) -> String {
+    let axis = spec.axis as usize;
+    let ranges = spec.ranges();
+    let output_wgpu = Into::<WebGPUTensor>::into(output);
+    let output_view = TensorView::from_contiguous_shape(output.shape());
+
+    // The same tensor may be concatenated more than once, but is only bound once
+    let unique_inputs = inputs.iter().fold(vec![], |mut unique_inputs, input| {
+        if !unique_inputs
+            .iter()
+            .any(|unique_input: &&Tensor| unique_input.id() == input.id())
+        {
+            unique_inputs.push(*input);
+        }
+        unique_inputs
+    });
+
+    let input_interfaces = unique_inputs
+        .iter()
+        .enumerate()
+        .map(|(index, input)| {
+            Into::<WebGPUTensor>::into(*input).serialize_type(
+                &wgsl_from_tensortype(input.datatype()),
+                &index.to_string(),
+                "read",
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    // Each input is read at its offset along the axis, the first range containing
+    // the coordinate writes the output and returns
+    let selections = inputs
+        .iter()
+        .zip(ranges.iter())
+        .map(|(input, &(begin, end))| {
+            let padding = (0..input.ndim())
+                .map(|dimension| {
+                    if dimension == spec.axis {
+                        (begin, output.shape()[axis] - end)
+                    } else {
+                        (0, 0)
+                    }
+                })
+                .collect::<Vec<_>>();
+            let viewtracker = input
+                .viewtracker()
+                .track_view(&input.view().pad(&padding[..], PadMode::Edge));
+
+            format!(
+                "if coordinate < {end}u {{
+        var mapped_index = index;
+        {map_index}
+        {output_tensor_name}[index] = {read_input};
+        return;
+    }}",
+                end = end,
+                map_index = map_index("mapped_index", &viewtracker),
+                output_tensor_name = output_wgpu.name(),
+                read_input = read_mapped(
+                    &Into::<WebGPUTensor>::into(*input).name(),
+                    "mapped_index",
+                    &viewtracker,
+                    input.datatype(),
+                ),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n    ");
+
+    format!(
+        "
+{input_interfaces}
+
+{output_interface}
+
+{workgroup_stride}
+@compute {workgroup_size}
+fn {entry_point}(
+    @builtin(global_invocation_id) global_id: vec3u
+) {{
+    {index}
+
+    // Guard against out-of-bounds work group sizes
+    if index >= {output_length}u {{
+        return;
+    }}
+
+    let coordinate = (index / {contiguous_stride}u) % {axis_shape}u;
+    {selections}
+}}
+",
+        input_interfaces = input_interfaces,
+        output_interface = output_wgpu.serialize_type(
+            &wgsl_from_tensortype(output.datatype()),
+            &unique_inputs.len().to_string(),
+            "read_write"
+        ),
+        workgroup_stride = workgroups.serialize_strides("WORKGROUP_STRIDE"),
+        workgroup_size = WORKGROUP_SIZE.serialize_decorator(),
+        entry_point = "main",
+        index = compute_index("index", "global_id", "WORKGROUP_STRIDE"),
+        output_length = output.len(),
+        contiguous_stride = output_view.contiguous_stride()[axis],
+        axis_shape = output.shape()[axis],
+        selections = selections,
+    )
+}
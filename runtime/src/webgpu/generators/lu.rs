@@ -0,0 +1,112 @@
+use tensor::primitives::tensor::{LUSpec, Tensor};
+
+use crate::webgpu::generators::*;
+use crate::webgpu::WebGPUTensor;
+use crate::webgpu::WebGPUWorkGroup;
+use crate::webgpu::WORKGROUP_SIZE;
+
+pub fn build_shader(
+    spec: &LUSpec,
+    input: &Tensor,
+    output: &Tensor,
+    workgroups: &WebGPUWorkGroup,
+) -> String {
+    let input_wgpu = Into::<WebGPUTensor>::into(input);
+    let output_wgpu = Into::<WebGPUTensor>::into(output);
+
+    let [.., n, _] = input.shape() else {
+        panic!("LU requires input to be at least 2D");
+    };
+    let matrices = input.len() / (n * n);
+
+    // Rows below the diagonal are searched for the largest element of the column
+    let pivot = match spec.pivoting {
+        true => format!(
+            "
+        var pivot_row = column;
+        for (var row = column + 1u; row < N; row++) {{
+            if abs({output}[offset + row * WIDTH + column]) > abs({output}[offset + pivot_row * WIDTH + column]) {{
+                pivot_row = row;
+            }}
+        }}
+        if pivot_row != column {{
+            for (var entry = 0u; entry < WIDTH; entry++) {{
+                let swapped = {output}[offset + column * WIDTH + entry];
+                {output}[offset + column * WIDTH + entry] = {output}[offset + pivot_row * WIDTH + entry];
+                {output}[offset + pivot_row * WIDTH + entry] = swapped;
+            }}
+        }}
+",
+            output = output_wgpu.name(),
+        ),
+        false => String::new(),
+    };
+
+    // Each invocation eliminates one matrix in place, the output rows hold the factors
+    // followed by the original row number
+    format!(
+        "
+{input_interface}
+
+{output_interface}
+
+const N: u32 = {n}u;
+const WIDTH: u32 = {width}u;
+
+{workgroup_stride}
+@compute {workgroup_size}
+fn {entry_point}(
+    @builtin(global_invocation_id) global_id: vec3u
+) {{
+    {index}
+
+    // Guard against out-of-bounds work group sizes
+    if index >= {matrices}u {{
+        return;
+    }}
+
+    let offset = index * N * WIDTH;
+    for (var row = 0u; row < N; row++) {{
+        for (var column = 0u; column < N; column++) {{
+            var mapped_index = (index * N + row) * N + column;
+            {map_input_index}
+            {output_tensor_name}[offset + row * WIDTH + column] = f32({read_input});
+        }}
+        {output_tensor_name}[offset + row * WIDTH + N] = f32(row);
+    }}
+
+    for (var column = 0u; column < N; column++) {{
+        {pivot}
+        // Zero pivots leave a singular column untouched
+        let divisor = {output_tensor_name}[offset + column * WIDTH + column];
+        if divisor == 0.0 {{
+            continue;
+        }}
+
+        for (var row = column + 1u; row < N; row++) {{
+            let factor = {output_tensor_name}[offset + row * WIDTH + column] / divisor;
+            {output_tensor_name}[offset + row * WIDTH + column] = factor;
+            for (var entry = column + 1u; entry < N; entry++) {{
+                {output_tensor_name}[offset + row * WIDTH + entry] -= factor * {output_tensor_name}[offset + column * WIDTH + entry];
+            }}
+        }}
+    }}
+}}
+",
+        input_interface =
+            input_wgpu.serialize_type(&wgsl_from_tensortype(input.datatype()), "0", "read"),
+        output_interface =
+            output_wgpu.serialize_type(&wgsl_from_tensortype(output.datatype()), "1", "read_write"),
+        n = n,
+        width = n + 1,
+        workgroup_stride = workgroups.serialize_strides("WORKGROUP_STRIDE"),
+        workgroup_size = WORKGROUP_SIZE.serialize_decorator(),
+        entry_point = "main",
+        index = compute_index("index", "global_id", "WORKGROUP_STRIDE"),
+        matrices = matrices,
+        map_input_index = map_index("mapped_index", input.viewtracker()),
+        read_input = read_mapped(&input_wgpu.name(), "mapped_index", input.viewtracker(), input.datatype()),
+        output_tensor_name = output_wgpu.name(),
+        pivot = pivot,
+    )
+}
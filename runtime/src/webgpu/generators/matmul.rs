@@ -0,0 +1,152 @@
+use tensor::primitives::tensor::Tensor;
+use tensor::primitives::tensorview::TensorView;
+
+use crate::webgpu::generators::*;
+use crate::webgpu::WebGPUTensor;
+use crate::webgpu::WebGPUWorkGroup;
+use crate::webgpu::WORKGROUP_SIZE;
+
+// Each workgroup lane computes one element of a TILE x TILE output tile
+const TILE: u32 = 8;
+const _: () = assert!(
+    TILE * TILE == WORKGROUP_SIZE.x * WORKGROUP_SIZE.y * WORKGROUP_SIZE.z,
+    "MatMul tile does not match the workgroup size"
+);
+
+fn tiles(output: &Tensor) -> (usize, usize, usize) {
+    let [.., n, m] = output.shape() else {
+        panic!("MatMul requires output to be at least 2D");
+    };
+    let row_tiles = (*n as usize).div_ceil(TILE as usize);
+    let column_tiles = (*m as usize).div_ceil(TILE as usize);
+    let batches = output.len() as usize / (n * m) as usize;
+
+    (batches, row_tiles, column_tiles)
+}
+
+// A workgroup computes each output tile
+pub fn workgroups(output: &Tensor) -> WebGPUWorkGroup {
+    let (batches, row_tiles, column_tiles) = tiles(output);
+    let invocations = batches
+        * row_tiles
+        * column_tiles
+        * (WORKGROUP_SIZE.x * WORKGROUP_SIZE.y * WORKGROUP_SIZE.z) as usize;
+    Into::<WebGPUWorkGroup>::into(&TensorView::from_contiguous_shape(&[invocations as i32]))
+}
+
+pub fn build_shader(
+    lhs: &Tensor,
+    rhs: &Tensor,
+    output: &Tensor,
+    workgroups: &WebGPUWorkGroup,
+) -> String {
+    let lhs_wgpu = Into::<WebGPUTensor>::into(lhs);
+    let rhs_wgpu = Into::<WebGPUTensor>::into(rhs);
+    let output_wgpu = Into::<WebGPUTensor>::into(output);
+    let output_datatype = wgsl_from_tensortype(output.datatype());
+
+    let [.., n, k] = lhs.shape() else {
+        panic!("MatMul requires input to be at least 2D");
+    };
+    let [.., _, m] = rhs.shape() else {
+        panic!("MatMul requires tensor to be at least 2D");
+    };
+    let (batches, row_tiles, column_tiles) = tiles(output);
+
+    // Both operands are staged through workgroup memory one TILE wide slice of k at a time
+    format!(
+        "
+{lhs_interface}
+
+{rhs_interface}
+
+{output_interface}
+
+var<workgroup> lhs_tile: array<array<{output_datatype}, {tile}>, {tile}>;
+var<workgroup> rhs_tile: array<array<{output_datatype}, {tile}>, {tile}>;
+
+fn load_lhs(index: u32) -> {output_datatype} {{
+    var mapped_index = index;
+    {map_lhs_index}
+    return {output_datatype}({read_lhs});
+}}
+
+fn load_rhs(index: u32) -> {output_datatype} {{
+    var mapped_index = index;
+    {map_rhs_index}
+    return {output_datatype}({read_rhs});
+}}
+
+{workgroup_stride}
+@compute {workgroup_size}
+fn {entry_point}(
+    @builtin(workgroup_id) workgroup_id: vec3u,
+    @builtin(num_workgroups) num_workgroups: vec3u,
+    @builtin(local_invocation_index) lane: u32
+) {{
+    let tile = dot(workgroup_id, vec3u(num_workgroups.y * num_workgroups.z, num_workgroups.z, 1u));
+
+    // Guard against out-of-bounds work group sizes
+    if tile >= {tiles}u {{
+        return;
+    }}
+
+    let batch = tile / {batch_tiles}u;
+    let local_row = lane / {tile}u;
+    let local_column = lane % {tile}u;
+    let row = (tile / {column_tiles}u) % {row_tiles}u * {tile}u + local_row;
+    let column = tile % {column_tiles}u * {tile}u + local_column;
+
+    var accumulator = {output_datatype}(0);
+    for (var offset = 0u; offset < {k}u; offset += {tile}u) {{
+        var lhs_value = {output_datatype}(0);
+        if row < {n}u && offset + local_column < {k}u {{
+            lhs_value = load_lhs(batch * {lhs_batch_stride}u + row * {k}u + offset + local_column);
+        }}
+        lhs_tile[local_row][local_column] = lhs_value;
+
+        var rhs_value = {output_datatype}(0);
+        if offset + local_row < {k}u && column < {m}u {{
+            rhs_value = load_rhs(batch * {rhs_batch_stride}u + (offset + local_row) * {m}u + column);
+        }}
+        rhs_tile[local_row][local_column] = rhs_value;
+        workgroupBarrier();
+
+        for (var inner = 0u; inner < {tile}u; inner++) {{
+            accumulator += lhs_tile[local_row][inner] * rhs_tile[inner][local_column];
+        }}
+        workgroupBarrier();
+    }}
+
+    if row < {n}u && column < {m}u {{
+        {output_tensor_name}[batch * {output_batch_stride}u + row * {m}u + column] = accumulator;
+    }}
+}}
+",
+        lhs_interface =
+            lhs_wgpu.serialize_type(&wgsl_from_tensortype(lhs.datatype()), "0", "read"),
+        rhs_interface =
+            rhs_wgpu.serialize_type(&wgsl_from_tensortype(rhs.datatype()), "1", "read"),
+        output_interface = output_wgpu.serialize_type(&output_datatype, "2", "read_write"),
+        output_datatype = output_datatype,
+        tile = TILE,
+        map_lhs_index = map_index("mapped_index", lhs.viewtracker()),
+        map_rhs_index = map_index("mapped_index", rhs.viewtracker()),
+        read_lhs = read_mapped(&lhs_wgpu.name(), "mapped_index", lhs.viewtracker(), lhs.datatype()),
+        read_rhs = read_mapped(&rhs_wgpu.name(), "mapped_index", rhs.viewtracker(), rhs.datatype()),
+        output_tensor_name = output_wgpu.name(),
+        workgroup_stride = workgroups.serialize_strides("WORKGROUP_STRIDE"),
+        workgroup_size = WORKGROUP_SIZE.serialize_decorator(),
+        entry_point = "main",
+        tiles = batches * row_tiles * column_tiles,
+        batch_tiles = row_tiles * column_tiles,
+        row_tiles = row_tiles,
+        column_tiles = column_tiles,
+        n = n,
+        k = k,
+        m = m,
+        lhs_batch_stride = n * k,
+        rhs_batch_stride = k * m,
+        output_batch_stride = n * m,
+    )
+}
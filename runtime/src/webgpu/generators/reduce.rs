@@ -5,10 +5,47 @@ use crate::webgpu::WebGPUWorkGroup;
 use crate::webgpu::WORKGROUP_SIZE;
 use crate::webgpu::{generators::*, WebGPUTensor};
 
-fn build_webgpu_operation<'a>(op: ReduceType) -> impl Fn(&'a str, &'a str) -> String {
+fn build_webgpu_operation<'a>(op: ReduceType) -> impl Fn(&'a str, &'a str, &'a str) -> String {
     match op {
-        ReduceType::MAX => |accumulator, current| format!("max({}, {})", accumulator, current),
-        ReduceType::SUM => |accumulator, current| format!("{} + {}", accumulator, current),
+        ReduceType::ALL => |accumulator, current, output_type| {
+            format!(
+                "{} = min({}, {}({} != {}(0)));",
+                accumulator, accumulator, output_type, current, output_type
+            )
+        },
+        ReduceType::ANY => |accumulator, current, output_type| {
+            format!(
+                "{} = max({}, {}({} != {}(0)));",
+                accumulator, accumulator, output_type, current, output_type
+            )
+        },
+        // Index-tracking reductions keep the first extremum along the axis
+        ReduceType::ARGMAX => |accumulator, current, _| {
+            format!(
+                "if {current} > {accumulator} {{ {accumulator} = {current}; {accumulator}_index = i; }}",
+                accumulator = accumulator,
+                current = current
+            )
+        },
+        ReduceType::ARGMIN => |accumulator, current, _| {
+            format!(
+                "if {current} < {accumulator} {{ {accumulator} = {current}; {accumulator}_index = i; }}",
+                accumulator = accumulator,
+                current = current
+            )
+        },
+        ReduceType::MAX => |accumulator, current, _| {
+            format!("{} = max({}, {});", accumulator, accumulator, current)
+        },
+        ReduceType::MIN => |accumulator, current, _| {
+            format!("{} = min({}, {});", accumulator, accumulator, current)
+        },
+        ReduceType::PROD => {
+            |accumulator, current, _| format!("{} = {} * {};", accumulator, accumulator, current)
+        }
+        ReduceType::SUM => {
+            |accumulator, current, _| format!("{} = {} + {};", accumulator, accumulator, current)
+        }
     }
 }
 
@@ -21,6 +58,7 @@ pub fn build_shader(
 ) -> String {
     let input_wgpu = Into::<WebGPUTensor>::into(input);
     let output_wgpu = Into::<WebGPUTensor>::into(output);
+    let output_datatype = wgsl_from_tensortype(output.datatype());
 
     let index_normalization = |index_variable| {
         let normalized_mapper = Into::<TensorViewTracker>::into(TensorView::as_defined(
@@ -70,17 +108,18 @@ fn {entry_point}(
     var mapped_axis_index = mapped_index;
     {map_axis_index}
 
-    var reduction = {input_tensor_name}[mapped_index];
+    var reduction = {initial};
+    var reduction_index = 0u;
     for (var i = 1u; i < {reduce_iterations}u; i++) {{
         var offset_at_index = i;
         {map_index_to_offset}
         mapped_axis_index = mapped_index + offset_at_index;
         {map_axis_index}
 
-        reduction = {operation};
+        {operation}
     }}
 
-    {output_tensor_name}[index] = reduction;
+    {output_tensor_name}[index] = {output};
 }}
 ",
         workgroup_stride = workgroups.serialize_strides("WORKGROUP_STRIDE"),
@@ -93,15 +132,42 @@ fn {entry_point}(
         index = compute_index("index", "global_id", "WORKGROUP_STRIDE"),
         output_length = output.len(),
         output_tensor_name = output_wgpu.name(),
-        input_tensor_name = input_wgpu.name(),
         normalize_index = index_normalization("mapped_index"),
         map_axis_index = map_index("mapped_axis_index", input.viewtracker()),
         reduce_iterations = reduce_iterations,
         map_index_to_offset = map_index("offset_at_index", &iteration_mapper),
+        initial = {
+            let input_data = read_mapped(
+                &input_wgpu.name(),
+                "mapped_axis_index",
+                input.viewtracker(),
+                input.datatype(),
+            );
+            match op {
+                ReduceType::ALL | ReduceType::ANY => {
+                    format!(
+                        "{}({} != {}(0))",
+                        output_datatype, input_data, output_datatype
+                    )
+                }
+                _ => input_data,
+            }
+        },
         operation = {
-            let input_data = format!("{}[mapped_axis_index]", input_wgpu.name());
-            let output = build_webgpu_operation(op)("reduction", &input_data);
+            let input_data = read_mapped(
+                &input_wgpu.name(),
+                "mapped_axis_index",
+                input.viewtracker(),
+                input.datatype(),
+            );
+            let output = build_webgpu_operation(op)("reduction", &input_data, &output_datatype);
             output
         },
+        output = match op {
+            ReduceType::ARGMAX | ReduceType::ARGMIN => {
+                format!("{}(reduction_index)", output_datatype)
+            }
+            _ => "reduction".to_string(),
+        },
     )
 }
@@ -0,0 +1,170 @@
+use tensor::primitives::tensor::{ScanSpec, ScanType, Tensor, TensorType};
+use tensor::primitives::tensorview::TensorView;
+
+use crate::webgpu::generators::*;
+use crate::webgpu::WebGPUTensor;
+use crate::webgpu::WebGPUWorkGroup;
+use crate::webgpu::WORKGROUP_SIZE;
+
+fn build_webgpu_operation<'a>(op: ScanType) -> impl Fn(&'a str, &'a str) -> String {
+    match op {
+        ScanType::MAX => |accumulator, current| format!("max({}, {})", accumulator, current),
+        ScanType::PROD => |accumulator, current| format!("{} * {}", accumulator, current),
+        ScanType::SUM => |accumulator, current| format!("{} + {}", accumulator, current),
+    }
+}
+
+fn build_webgpu_identity(op: ScanType, datatype: TensorType) -> String {
+    match (op, datatype) {
+        (ScanType::MAX, TensorType::I32 | TensorType::I8 | TensorType::U8) => {
+            "(-2147483647i - 1i)".to_string()
+        }
+        (ScanType::MAX, TensorType::U32) => "0u".to_string(),
+        (ScanType::MAX, datatype) => format!("{}(-3.40282347e38)", wgsl_from_tensortype(datatype)),
+        (ScanType::PROD, datatype) => format!("{}(1)", wgsl_from_tensortype(datatype)),
+        (ScanType::SUM, datatype) => format!("{}(0)", wgsl_from_tensortype(datatype)),
+    }
+}
+
+// A workgroup scans each line along the axis
+pub fn workgroups(spec: &ScanSpec, output: &Tensor) -> WebGPUWorkGroup {
+    let lines = output.len() / output.shape()[spec.axis as usize];
+    let invocations = lines * (WORKGROUP_SIZE.x * WORKGROUP_SIZE.y * WORKGROUP_SIZE.z) as i32;
+    Into::<WebGPUWorkGroup>::into(&TensorView::from_contiguous_shape(&[invocations]))
+}
+
+pub fn build_shader(
+    spec: &ScanSpec,
+    input: &Tensor,
+    output: &Tensor,
+    workgroups: &WebGPUWorkGroup,
+) -> String {
+    let input_wgpu = Into::<WebGPUTensor>::into(input);
+    let output_wgpu = Into::<WebGPUTensor>::into(output);
+    let output_datatype = wgsl_from_tensortype(output.datatype());
+
+    let axis = spec.axis as usize;
+    let axis_shape = output.shape()[axis];
+    let axis_stride = TensorView::from_contiguous_shape(output.shape()).contiguous_stride()[axis];
+    let lanes = WORKGROUP_SIZE.x * WORKGROUP_SIZE.y * WORKGROUP_SIZE.z;
+
+    // Work-efficient scan (Blelloch), each lane serially scans a chunk of the line,
+    // the chunk totals are scanned in workgroup memory and then distributed back
+    format!(
+        "
+{input_interface}
+
+{output_interface}
+
+var<workgroup> partials: array<{output_datatype}, {lanes}>;
+
+fn load_input(index: u32) -> {output_datatype} {{
+    var mapped_index = index;
+    {map_index}
+    return {read_input};
+}}
+
+fn line_index(line: u32, position: u32) -> u32 {{
+    let element = select(position, {axis_shape}u - 1u - position, {reverse});
+    return (line / {axis_stride}u) * {line_stride}u + line % {axis_stride}u + element * {axis_stride}u;
+}}
+
+{workgroup_stride}
+@compute {workgroup_size}
+fn {entry_point}(
+    @builtin(workgroup_id) workgroup_id: vec3u,
+    @builtin(num_workgroups) num_workgroups: vec3u,
+    @builtin(local_invocation_index) lane: u32
+) {{
+    let line = dot(workgroup_id, vec3u(num_workgroups.y * num_workgroups.z, num_workgroups.z, 1u));
+
+    // Guard against out-of-bounds work group sizes
+    if line >= {lines}u {{
+        return;
+    }}
+
+    let chunk = ({axis_shape}u + {lanes}u - 1u) / {lanes}u;
+    let begin = min(lane * chunk, {axis_shape}u);
+    let end = min(begin + chunk, {axis_shape}u);
+
+    var total = {identity};
+    for (var position = begin; position < end; position++) {{
+        let current = load_input(line_index(line, position));
+        total = {reduce_total};
+    }}
+    partials[lane] = total;
+    workgroupBarrier();
+
+    // Up-sweep
+    for (var offset = 1u; offset < {lanes}u; offset *= 2u) {{
+        let right = (lane + 1u) * offset * 2u - 1u;
+        if right < {lanes}u {{
+            partials[right] = {reduce_up};
+        }}
+        workgroupBarrier();
+    }}
+
+    if lane == 0u {{
+        partials[{lanes}u - 1u] = {identity};
+    }}
+    workgroupBarrier();
+
+    // Down-sweep
+    for (var offset = {lanes}u / 2u; offset > 0u; offset /= 2u) {{
+        let right = (lane + 1u) * offset * 2u - 1u;
+        if right < {lanes}u {{
+            let left = partials[right - offset];
+            partials[right - offset] = partials[right];
+            partials[right] = {reduce_down};
+        }}
+        workgroupBarrier();
+    }}
+
+    var accumulator = partials[lane];
+    for (var position = begin; position < end; position++) {{
+        let index = line_index(line, position);
+        let current = load_input(index);
+        {scan_step}
+    }}
+}}
+",
+        input_interface =
+            input_wgpu.serialize_type(&wgsl_from_tensortype(input.datatype()), "0", "read"),
+        output_interface =
+            output_wgpu.serialize_type(&output_datatype, "1", "read_write"),
+        output_datatype = output_datatype,
+        lanes = lanes,
+        map_index = map_index("mapped_index", input.viewtracker()),
+        read_input = read_mapped(&input_wgpu.name(), "mapped_index", input.viewtracker(), input.datatype()),
+        axis_shape = axis_shape,
+        axis_stride = axis_stride,
+        line_stride = axis_shape * axis_stride,
+        reverse = spec.reverse,
+        workgroup_stride = workgroups.serialize_strides("WORKGROUP_STRIDE"),
+        workgroup_size = WORKGROUP_SIZE.serialize_decorator(),
+        entry_point = "main",
+        lines = output.len() / axis_shape,
+        identity = build_webgpu_identity(spec.op, output.datatype()),
+        reduce_total = build_webgpu_operation(spec.op)("total", "current"),
+        reduce_up = build_webgpu_operation(spec.op)("partials[right - offset]", "partials[right]"),
+        reduce_down = build_webgpu_operation(spec.op)("partials[right]", "left"),
+        scan_step = {
+            let reduce_step = build_webgpu_operation(spec.op)("accumulator", "current");
+            if spec.exclusive {
+                format!(
+                    "{output}[index] = accumulator;
+        accumulator = {reduce_step};",
+                    output = output_wgpu.name(),
+                    reduce_step = reduce_step
+                )
+            } else {
+                format!(
+                    "accumulator = {reduce_step};
+        {output}[index] = accumulator;",
+                    output = output_wgpu.name(),
+                    reduce_step = reduce_step
+                )
+            }
+        },
+    )
+}
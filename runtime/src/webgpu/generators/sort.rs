@@ -0,0 +1,198 @@
+use tensor::primitives::tensor::{SortSpec, SortType, Tensor, TensorElementOrder, TensorType};
+use tensor::primitives::tensorview::TensorView;
+
+use crate::webgpu::generators::*;
+use crate::webgpu::WebGPUTensor;
+use crate::webgpu::WebGPUWorkGroup;
+use crate::webgpu::WORKGROUP_SIZE;
+
+// Longest line which is sorted within workgroup memory, longer lines are sorted on the host
+const BITONIC_CAPACITY: usize = 2048;
+
+pub fn sorts_on_host(spec: &SortSpec, output: &Tensor) -> bool {
+    output.shape()[spec.axis as usize] as usize > BITONIC_CAPACITY
+}
+
+// Expects a contiguous evaluated input, equal elements keep their original order
+pub fn host_sort(spec: &SortSpec, input: &Tensor, output: &Tensor) -> Tensor {
+    match input.datatype() {
+        TensorType::F32 => host_sort_lines::<f32>(spec, input, output),
+        TensorType::I32 | TensorType::I8 | TensorType::U8 => {
+            host_sort_lines::<i32>(spec, input, output)
+        }
+        TensorType::U32 => host_sort_lines::<u32>(spec, input, output),
+        datatype => panic!(
+            "Sorting lines longer than {} does not support {:?}",
+            BITONIC_CAPACITY, datatype
+        ),
+    }
+}
+
+fn host_sort_lines<T: bytemuck::Pod + TensorElementOrder>(
+    spec: &SortSpec,
+    input: &Tensor,
+    output: &Tensor,
+) -> Tensor {
+    let data = input.load::<T>();
+    let axis = spec.axis as usize;
+    let axis_shape = output.shape()[axis] as usize;
+    let axis_stride =
+        TensorView::from_contiguous_shape(output.shape()).contiguous_stride()[axis] as usize;
+
+    let mut values = data.clone();
+    let mut indices = vec![0; data.len()];
+    (0..data.len() / axis_shape).for_each(|line| {
+        let line_index = |position: usize| {
+            (line / axis_stride) * axis_shape * axis_stride
+                + line % axis_stride
+                + position * axis_stride
+        };
+
+        let mut order = (0..axis_shape).collect::<Vec<_>>();
+        order.sort_by(|&lhs, &rhs| {
+            let ordering = data[line_index(lhs)].total_order(&data[line_index(rhs)]);
+            if spec.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        order.iter().enumerate().for_each(|(position, &element)| {
+            values[line_index(position)] = data[line_index(element)];
+            indices[line_index(position)] = element as i32;
+        });
+    });
+
+    let bytes = match spec.op {
+        SortType::VALUES => bytemuck::cast_slice::<T, u8>(&values[..]),
+        SortType::INDICES => bytemuck::cast_slice::<i32, u8>(&indices[..]),
+    };
+    Tensor::from_raw_bytes(bytes, output.view().clone(), output.datatype())
+}
+
+// A workgroup sorts each line along the axis
+pub fn workgroups(spec: &SortSpec, output: &Tensor) -> WebGPUWorkGroup {
+    let lines = output.len() / output.shape()[spec.axis as usize];
+    let invocations = lines * (WORKGROUP_SIZE.x * WORKGROUP_SIZE.y * WORKGROUP_SIZE.z) as i32;
+    Into::<WebGPUWorkGroup>::into(&TensorView::from_contiguous_shape(&[invocations]))
+}
+
+pub fn build_shader(
+    spec: &SortSpec,
+    input: &Tensor,
+    output: &Tensor,
+    workgroups: &WebGPUWorkGroup,
+) -> String {
+    let input_wgpu = Into::<WebGPUTensor>::into(input);
+    let output_wgpu = Into::<WebGPUTensor>::into(output);
+    let output_datatype = wgsl_from_tensortype(output.datatype());
+
+    let axis = spec.axis as usize;
+    let axis_shape = output.shape()[axis] as usize;
+    let axis_stride = TensorView::from_contiguous_shape(output.shape()).contiguous_stride()[axis];
+    let lanes = WORKGROUP_SIZE.x * WORKGROUP_SIZE.y * WORKGROUP_SIZE.z;
+
+    let result = |position: &str| match spec.op {
+        SortType::VALUES => format!(
+            "{}(load_input(line_index(line, {})))",
+            output_datatype, position
+        ),
+        SortType::INDICES => format!("{}({})", output_datatype, position),
+    };
+
+    // Each line is sorted by a bitonic network whose comparators all point the same way,
+    // so the padding up to a power of two never has to be stored
+    let sort_body = format!(
+        "
+    for (var position = lane; position < {axis_shape}u; position += {lanes}u) {{
+        order[position] = position;
+    }}
+    workgroupBarrier();
+
+    for (var size = 2u; size <= {padded_shape}u; size *= 2u) {{
+        for (var stride = size / 2u; stride > 0u; stride /= 2u) {{
+            for (var pair = lane; pair < {padded_shape}u / 2u; pair += {lanes}u) {{
+                let lower = 2u * stride * (pair / stride) + pair % stride;
+                let upper = select(lower ^ stride, lower ^ (2u * stride - 1u), stride == size / 2u);
+                if upper < {axis_shape}u && ordered_before(order[upper], order[lower]) {{
+                    let swapped = order[lower];
+                    order[lower] = order[upper];
+                    order[upper] = swapped;
+                }}
+            }}
+            workgroupBarrier();
+        }}
+    }}
+
+    for (var position = lane; position < {axis_shape}u; position += {lanes}u) {{
+        let element = order[position];
+        {output_tensor_name}[line_index(line, position)] = {bitonic_result};
+    }}",
+        axis_shape = axis_shape,
+        padded_shape = axis_shape.next_power_of_two(),
+        lanes = lanes,
+        output_tensor_name = output_wgpu.name(),
+        bitonic_result = result("element"),
+    );
+
+    format!(
+        "
+{input_interface}
+
+{output_interface}
+
+var<workgroup> order: array<u32, {order_length}>;
+
+fn load_input(index: u32) -> {input_datatype} {{
+    var mapped_index = index;
+    {map_index}
+    return {read_input};
+}}
+
+fn line_index(line: u32, position: u32) -> u32 {{
+    return (line / {axis_stride}u) * {line_stride}u + line % {axis_stride}u + position * {axis_stride}u;
+}}
+
+var<private> current_line: u32;
+
+// Equal elements keep their original order
+fn ordered_before(lhs: u32, rhs: u32) -> bool {{
+    let lhs_value = load_input(line_index(current_line, lhs));
+    let rhs_value = load_input(line_index(current_line, rhs));
+    return lhs_value {comparison} rhs_value || (lhs_value == rhs_value && lhs < rhs);
+}}
+
+{workgroup_stride}
+@compute {workgroup_size}
+fn {entry_point}(
+    @builtin(workgroup_id) workgroup_id: vec3u,
+    @builtin(num_workgroups) num_workgroups: vec3u,
+    @builtin(local_invocation_index) lane: u32
+) {{
+    let line = dot(workgroup_id, vec3u(num_workgroups.y * num_workgroups.z, num_workgroups.z, 1u));
+
+    // Guard against out-of-bounds work group sizes
+    if line >= {lines}u {{
+        return;
+    }}
+    current_line = line;
+{sort_body}
+}}
+",
+        input_interface =
+            input_wgpu.serialize_type(&wgsl_from_tensortype(input.datatype()), "0", "read"),
+        output_interface = output_wgpu.serialize_type(&output_datatype, "1", "read_write"),
+        order_length = axis_shape.next_power_of_two(),
+        input_datatype = wgsl_from_tensortype(input.datatype()),
+        map_index = map_index("mapped_index", input.viewtracker()),
+        read_input = read_mapped(&input_wgpu.name(), "mapped_index", input.viewtracker(), input.datatype()),
+        axis_stride = axis_stride,
+        line_stride = axis_shape as i32 * axis_stride,
+        comparison = if spec.descending { ">" } else { "<" },
+        workgroup_stride = workgroups.serialize_strides("WORKGROUP_STRIDE"),
+        workgroup_size = WORKGROUP_SIZE.serialize_decorator(),
+        entry_point = "main",
+        lines = output.len() / axis_shape as i32,
+        sort_body = sort_body,
+    )
+}
@@ -0,0 +1,75 @@
+use tensor::primitives::tensor::Tensor;
+
+use crate::webgpu::generators::*;
+use crate::webgpu::WebGPUTensor;
+use crate::webgpu::WebGPUWorkGroup;
+use crate::webgpu::WORKGROUP_SIZE;
+
+pub fn build_shader(
+    input: &Tensor,
+    indices: &Tensor,
+    output: &Tensor,
+    workgroups: &WebGPUWorkGroup,
+) -> String {
+    let input_wgpu = Into::<WebGPUTensor>::into(input);
+    let indices_wgpu = Into::<WebGPUTensor>::into(indices);
+    let output_wgpu = Into::<WebGPUTensor>::into(output);
+
+    format!(
+        "
+{input_interface}
+
+{indices_interface}
+
+{output_interface}
+
+{workgroup_stride}
+@compute {workgroup_size}
+fn {entry_point}(
+    @builtin(global_invocation_id) global_id: vec3u
+) {{
+    {index}
+
+    // Guard against out-of-bounds work group sizes
+    if index >= {output_length}u {{
+        return;
+    }}
+
+    var indices_mapped_index = index;
+    {map_indices_index}
+
+    // The index read from indices addresses the flattened input
+    var input_mapped_index = u32({read_indices});
+    {map_input_index}
+
+    {output_tensor_name}[index] = {read_input};
+}}
+",
+        workgroup_stride = workgroups.serialize_strides("WORKGROUP_STRIDE"),
+        input_interface =
+            input_wgpu.serialize_type(&wgsl_from_tensortype(input.datatype()), "0", "read"),
+        indices_interface =
+            indices_wgpu.serialize_type(&wgsl_from_tensortype(indices.datatype()), "1", "read"),
+        output_interface =
+            output_wgpu.serialize_type(&wgsl_from_tensortype(output.datatype()), "2", "read_write"),
+        workgroup_size = WORKGROUP_SIZE.serialize_decorator(),
+        entry_point = "main",
+        index = compute_index("index", "global_id", "WORKGROUP_STRIDE"),
+        output_length = output.len(),
+        map_indices_index = map_index("indices_mapped_index", indices.viewtracker()),
+        map_input_index = map_index("input_mapped_index", input.viewtracker()),
+        read_indices = read_mapped(
+            &indices_wgpu.name(),
+            "indices_mapped_index",
+            indices.viewtracker(),
+            indices.datatype()
+        ),
+        read_input = read_mapped(
+            &input_wgpu.name(),
+            "input_mapped_index",
+            input.viewtracker(),
+            input.datatype()
+        ),
+        output_tensor_name = output_wgpu.name(),
+    )
+}
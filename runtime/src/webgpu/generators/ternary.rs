@@ -0,0 +1,105 @@
+use tensor::primitives::tensor::{Tensor, TernaryType};
+
+use crate::webgpu::generators::*;
+use crate::webgpu::WebGPUTensor;
+use crate::webgpu::WebGPUWorkGroup;
+use crate::webgpu::WORKGROUP_SIZE;
+
+fn build_webgpu_operation<'a>(op: TernaryType) -> impl Fn(&'a str, &'a str, &'a str) -> String {
+    match op {
+        TernaryType::SELECT => {
+            |condition, lhs, rhs| format!("select({}, {}, bool({}))", rhs, lhs, condition)
+        }
+    }
+}
+
+pub fn build_shader(
+    op: TernaryType,
+    condition: &Tensor,
+    lhs: &Tensor,
+    rhs: &Tensor,
+    output: &Tensor,
+    workgroups: &WebGPUWorkGroup,
+) -> String {
+    let condition_wgpu = Into::<WebGPUTensor>::into(condition);
+    let lhs_wgpu = Into::<WebGPUTensor>::into(lhs);
+    let rhs_wgpu = Into::<WebGPUTensor>::into(rhs);
+    let output_wgpu = Into::<WebGPUTensor>::into(output);
+
+    format!(
+        "
+{condition_interface}
+
+{lhs_interface}
+
+{rhs_interface}
+
+{output_interface}
+
+{workgroup_stride}
+@compute {workgroup_size}
+fn {entry_point}(
+    @builtin(global_invocation_id) global_id: vec3u
+) {{
+    {index}
+
+    // Guard against out-of-bounds work group sizes
+    if index >= {output_length}u {{
+        return;
+    }}
+
+    var condition_mapped_index = index;
+    {map_condition_index}
+
+    var lhs_mapped_index = index;
+    {map_lhs_index}
+
+    var rhs_mapped_index = index;
+    {map_rhs_index}
+
+    {output_tensor_name}[index] = {output};
+}}
+",
+        workgroup_stride = workgroups.serialize_strides("WORKGROUP_STRIDE"),
+        condition_interface =
+            condition_wgpu.serialize_type(&wgsl_from_tensortype(condition.datatype()), "0", "read"),
+        lhs_interface = lhs_wgpu.serialize_type(&wgsl_from_tensortype(lhs.datatype()), "1", "read"),
+        rhs_interface = rhs_wgpu.serialize_type(&wgsl_from_tensortype(rhs.datatype()), "2", "read"),
+        output_interface =
+            output_wgpu.serialize_type(&wgsl_from_tensortype(output.datatype()), "3", "read_write"),
+        workgroup_size = WORKGROUP_SIZE.serialize_decorator(),
+        entry_point = "main",
+        index = compute_index("index", "global_id", "WORKGROUP_STRIDE"),
+        output_length = output.len(),
+        output_tensor_name = output_wgpu.name(),
+        map_condition_index = map_index("condition_mapped_index", condition.viewtracker()),
+        map_lhs_index = map_index("lhs_mapped_index", lhs.viewtracker()),
+        map_rhs_index = map_index("rhs_mapped_index", rhs.viewtracker()),
+        output = {
+            let mapped_condition_data = read_mapped(
+                &condition_wgpu.name(),
+                "condition_mapped_index",
+                condition.viewtracker(),
+                condition.datatype(),
+            );
+            let mapped_lhs_data = read_mapped(
+                &lhs_wgpu.name(),
+                "lhs_mapped_index",
+                lhs.viewtracker(),
+                lhs.datatype(),
+            );
+            let mapped_rhs_data = read_mapped(
+                &rhs_wgpu.name(),
+                "rhs_mapped_index",
+                rhs.viewtracker(),
+                rhs.datatype(),
+            );
+            let output = build_webgpu_operation(op)(
+                &mapped_condition_data,
+                &mapped_lhs_data,
+                &mapped_rhs_data,
+            );
+            output
+        },
+    )
+}
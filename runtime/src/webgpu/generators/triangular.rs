@@ -0,0 +1,104 @@
+use tensor::primitives::tensor::{Tensor, TriangularSolveSpec};
+
+use crate::webgpu::generators::*;
+use crate::webgpu::WebGPUTensor;
+use crate::webgpu::WebGPUWorkGroup;
+use crate::webgpu::WORKGROUP_SIZE;
+
+pub fn build_shader(
+    spec: &TriangularSolveSpec,
+    matrix: &Tensor,
+    rhs: &Tensor,
+    output: &Tensor,
+    workgroups: &WebGPUWorkGroup,
+) -> String {
+    let matrix_wgpu = Into::<WebGPUTensor>::into(matrix);
+    let rhs_wgpu = Into::<WebGPUTensor>::into(rhs);
+    let output_wgpu = Into::<WebGPUTensor>::into(output);
+
+    let [.., n, k] = rhs.shape() else {
+        panic!("TriangularSolve requires rhs to be at least 2D");
+    };
+
+    // Upper triangular systems are substituted from the last row backwards
+    let (row, known_columns) = match spec.upper {
+        true => ("N - 1u - step", "row + 1u; column < N"),
+        false => ("step", "0u; column < row"),
+    };
+    let solution = match spec.unit_diagonal {
+        true => "remainder".to_string(),
+        false => "remainder / load_matrix(matrix_offset + row * N + row)".to_string(),
+    };
+
+    // Each invocation substitutes one column of the right hand side, reading back the rows of
+    // the solution it has already written
+    format!(
+        "
+{matrix_interface}
+
+{rhs_interface}
+
+{output_interface}
+
+const N: u32 = {n}u;
+const K: u32 = {k}u;
+
+fn load_matrix(index: u32) -> f32 {{
+    var mapped_index = index;
+    {map_matrix_index}
+    return f32({read_matrix});
+}}
+
+fn load_rhs(index: u32) -> f32 {{
+    var mapped_index = index;
+    {map_rhs_index}
+    return f32({read_rhs});
+}}
+
+{workgroup_stride}
+@compute {workgroup_size}
+fn {entry_point}(
+    @builtin(global_invocation_id) global_id: vec3u
+) {{
+    {index}
+
+    // Guard against out-of-bounds work group sizes
+    if index >= {columns}u {{
+        return;
+    }}
+
+    let batch = index / K;
+    let matrix_offset = batch * N * N;
+    let offset = batch * N * K + index % K;
+    for (var step = 0u; step < N; step++) {{
+        let row = {row};
+        var remainder = load_rhs(offset + row * K);
+        for (var column = {known_columns}; column++) {{
+            remainder -= load_matrix(matrix_offset + row * N + column) * {output_tensor_name}[offset + column * K];
+        }}
+        {output_tensor_name}[offset + row * K] = {solution};
+    }}
+}}
+",
+        matrix_interface =
+            matrix_wgpu.serialize_type(&wgsl_from_tensortype(matrix.datatype()), "0", "read"),
+        rhs_interface = rhs_wgpu.serialize_type(&wgsl_from_tensortype(rhs.datatype()), "1", "read"),
+        output_interface =
+            output_wgpu.serialize_type(&wgsl_from_tensortype(output.datatype()), "2", "read_write"),
+        n = n,
+        k = k,
+        map_matrix_index = map_index("mapped_index", matrix.viewtracker()),
+        map_rhs_index = map_index("mapped_index", rhs.viewtracker()),
+        read_matrix = read_mapped(&matrix_wgpu.name(), "mapped_index", matrix.viewtracker(), matrix.datatype()),
+        read_rhs = read_mapped(&rhs_wgpu.name(), "mapped_index", rhs.viewtracker(), rhs.datatype()),
+        output_tensor_name = output_wgpu.name(),
+        row = row,
+        known_columns = known_columns,
+        solution = solution,
+        workgroup_stride = workgroups.serialize_strides("WORKGROUP_STRIDE"),
+        workgroup_size = WORKGROUP_SIZE.serialize_decorator(),
+        entry_point = "main",
+        index = compute_index("index", "global_id", "WORKGROUP_STRIDE"),
+        columns = output.len() / n,
+    )
+}
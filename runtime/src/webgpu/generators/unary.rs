@@ -18,11 +18,33 @@ fn build_webgpu_operation<'a>(
         UnaryType::ABS => |input| format!("abs({input})", input = input),
         UnaryType::FLOOR => |input| format!("floor({input})", input = input),
         UnaryType::CEIL => |input| format!("ceil({input})", input = input),
+        UnaryType::ASIN => |input| format!("asin({input})", input = input),
+        UnaryType::ACOS => |input| format!("acos({input})", input = input),
+        UnaryType::ATAN => |input| format!("atan({input})", input = input),
+        UnaryType::ASINH => |input| format!("asinh({input})", input = input),
+        UnaryType::ACOSH => |input| format!("acosh({input})", input = input),
+        UnaryType::ATANH => |input| format!("atanh({input})", input = input),
+        // Compare the bit patterns so fast-math cannot assume values are finite
+        UnaryType::ISNAN => |input| {
+            format!(
+                "f32((bitcast<u32>({input}) & 0x7fffffffu) > 0x7f800000u)",
+                input = input
+            )
+        },
+        UnaryType::ISINF => |input| {
+            format!(
+                "f32((bitcast<u32>({input}) & 0x7fffffffu) == 0x7f800000u)",
+                input = input
+            )
+        },
+        UnaryType::BITWISENOT => |input| format!("~{input}", input = input),
         UnaryType::CAST => match output_datatype {
             TensorType::F16 => |input| format!("f16({input})", input = input),
             TensorType::F32 => |input| format!("f32({input})", input = input),
             TensorType::U32 => |input| format!("u32({input})", input = input),
-            TensorType::I32 => |input| format!("i32({input})", input = input),
+            TensorType::I32 | TensorType::I8 | TensorType::U8 => {
+                |input| format!("i32({input})", input = input)
+            }
         },
     }
 }
@@ -72,7 +94,12 @@ fn {entry_point}(
         output_tensor_name = output_wgpu.name(),
         map_index = map_index("mapped_index", input.viewtracker()),
         output = {
-            let input_data = format!("{}[mapped_index]", input_wgpu.name());
+            let input_data = read_mapped(
+                &input_wgpu.name(),
+                "mapped_index",
+                input.viewtracker(),
+                input.datatype(),
+            );
             let output = build_webgpu_operation(op, output.datatype())(&input_data);
             output
         }
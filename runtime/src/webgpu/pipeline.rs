@@ -8,7 +8,7 @@ use spirv_tools::opt::Optimizer;
 #[cfg(feature = "dtensor_spirv_passthrough")]
 use spirv_tools::val::Validator;
 use tensor::ir::mlir::{ShaderIRBuilder, ShaderIREvaluation, ShaderIROp};
-use tensor::primitives::tensor::{OperationSpec, Tensor, TensorInput};
+use tensor::primitives::tensor::{DataDependent, OperationSpec, Tensor, TensorInput};
 use tensor::topograph::{GraphDependencies, GraphView};
 
 use crate::webgpu::benchmark;
@@ -65,7 +65,25 @@ impl WebGPUEvaluation for Tensor {
             } else if let TensorInput::ExplicitInput(_) = tensor.data() {
                 intermediate_results.insert(tensor.id(), tensor.clone());
             } else if let TensorInput::OperationResult(operation) = tensor.data() {
+                // Lines which do not fit in workgroup memory are sorted on the host
+                if let OperationSpec::SortOp(op) = &operation {
+                    if generators::sort::sorts_on_host(op, tensor) {
+                        let input = lookup.get(&op.input.id()).unwrap();
+                        let input = input.Identity().evaluate_webgpu(wgpu_device);
+                        let result = generators::sort::host_sort(op, &input, tensor);
+                        let _ = tensor.update(&result.data());
+                        intermediate_results.insert(tensor.id(), tensor.clone());
+                        continue;
+                    }
+                }
+
                 let workgroups = Into::<WebGPUWorkGroup>::into(tensor.view());
+                let workgroups = match &operation {
+                    OperationSpec::ScanOp(op) => generators::scan::workgroups(op, tensor),
+                    OperationSpec::SortOp(op) => generators::sort::workgroups(op, tensor),
+                    OperationSpec::MatMulOp(_) => generators::matmul::workgroups(tensor),
+                    _ => workgroups,
+                };
 
                 #[cfg(feature = "dtensor_shader_stitch")]
                 let (shader, dependencies) = {
@@ -93,6 +111,23 @@ impl WebGPUEvaluation for Tensor {
                                 vec![op.lhs.id(), op.rhs.id()],
                             )
                         }
+                        OperationSpec::TernaryOp(op) => {
+                            let condition = intermediate_results.get(&op.condition.id()).unwrap();
+                            let lhs = intermediate_results.get(&op.lhs.id()).unwrap();
+                            let rhs = intermediate_results.get(&op.rhs.id()).unwrap();
+
+                            (
+                                generators::ternary::build_shader(
+                                    op.op,
+                                    condition,
+                                    lhs,
+                                    rhs,
+                                    tensor,
+                                    &workgroups,
+                                ),
+                                vec![op.condition.id(), op.lhs.id(), op.rhs.id()],
+                            )
+                        }
                         OperationSpec::ReduceOp(op) => {
                             let input = intermediate_results.get(&op.input.id()).unwrap();
 
@@ -107,6 +142,107 @@ impl WebGPUEvaluation for Tensor {
                                 vec![op.input.id()],
                             )
                         }
+                        OperationSpec::ScanOp(op) => {
+                            let input = intermediate_results.get(&op.input.id()).unwrap();
+
+                            (
+                                generators::scan::build_shader(&op, input, tensor, &workgroups),
+                                vec![op.input.id()],
+                            )
+                        }
+                        OperationSpec::SortOp(op) => {
+                            let input = intermediate_results.get(&op.input.id()).unwrap();
+
+                            (
+                                generators::sort::build_shader(&op, input, tensor, &workgroups),
+                                vec![op.input.id()],
+                            )
+                        }
+                        OperationSpec::MatMulOp(op) => {
+                            let lhs = intermediate_results.get(&op.lhs.id()).unwrap();
+                            let rhs = intermediate_results.get(&op.rhs.id()).unwrap();
+
+                            (
+                                generators::matmul::build_shader(lhs, rhs, tensor, &workgroups),
+                                vec![op.lhs.id(), op.rhs.id()],
+                            )
+                        }
+                        OperationSpec::ConcatOp(op) => {
+                            let inputs = op
+                                .inputs
+                                .iter()
+                                .map(|input| intermediate_results.get(&input.id()).unwrap())
+                                .collect::<Vec<_>>();
+
+                            (
+                                generators::concat::build_shader(
+                                    &op,
+                                    &inputs[..],
+                                    tensor,
+                                    &workgroups,
+                                ),
+                                op.inputs.iter().fold(vec![], |mut input_ids, input| {
+                                    if !input_ids.contains(&input.id()) {
+                                        input_ids.push(input.id());
+                                    }
+                                    input_ids
+                                }),
+                            )
+                        }
+                        OperationSpec::TakeOp(op) => {
+                            let input = intermediate_results.get(&op.input.id()).unwrap();
+                            let indices = intermediate_results.get(&op.indices.id()).unwrap();
+
+                            (
+                                generators::take::build_shader(input, indices, tensor, &workgroups),
+                                vec![op.input.id(), op.indices.id()],
+                            )
+                        }
+                        OperationSpec::AttentionOp(op) => {
+                            let query = intermediate_results.get(&op.query.id()).unwrap();
+                            let key = intermediate_results.get(&op.key.id()).unwrap();
+                            let value = intermediate_results.get(&op.value.id()).unwrap();
+                            let mask = op
+                                .mask
+                                .as_ref()
+                                .map(|mask| intermediate_results.get(&mask.id()).unwrap());
+
+                            (
+                                generators::attention::build_shader(
+                                    &op,
+                                    query,
+                                    key,
+                                    value,
+                                    mask,
+                                    tensor,
+                                    &workgroups,
+                                ),
+                                op.dependencies().iter().map(|input| input.id()).collect(),
+                            )
+                        }
+                        OperationSpec::LUOp(op) => {
+                            let input = intermediate_results.get(&op.input.id()).unwrap();
+
+                            (
+                                generators::lu::build_shader(&op, input, tensor, &workgroups),
+                                vec![op.input.id()],
+                            )
+                        }
+                        OperationSpec::TriangularSolveOp(op) => {
+                            let matrix = intermediate_results.get(&op.matrix.id()).unwrap();
+                            let rhs = intermediate_results.get(&op.rhs.id()).unwrap();
+
+                            (
+                                generators::triangular::build_shader(
+                                    &op,
+                                    matrix,
+                                    rhs,
+                                    tensor,
+                                    &workgroups,
+                                ),
+                                vec![op.matrix.id(), op.rhs.id()],
+                            )
+                        }
                     };
 
                     let dependencies = inputs
@@ -126,8 +262,82 @@ impl WebGPUEvaluation for Tensor {
                     (shader, dependencies)
                 };
 
+                // Sequential and workgroup cooperative kernels cannot be unrolled into shader IR
+                // and always run on their own
                 #[cfg(not(feature = "dtensor_shader_stitch"))]
-                let (shader, dependencies) = {
+                let kernel = match &operation {
+                    OperationSpec::ScanOp(op) => {
+                        let input = lookup.get(&op.input.id()).unwrap();
+
+                        Some((
+                            generators::scan::build_shader(op, input, tensor, &workgroups),
+                            vec![input],
+                        ))
+                    }
+                    OperationSpec::SortOp(op) => {
+                        let input = lookup.get(&op.input.id()).unwrap();
+
+                        Some((
+                            generators::sort::build_shader(op, input, tensor, &workgroups),
+                            vec![input],
+                        ))
+                    }
+                    OperationSpec::MatMulOp(op) => {
+                        let lhs = lookup.get(&op.lhs.id()).unwrap();
+                        let rhs = lookup.get(&op.rhs.id()).unwrap();
+
+                        Some((
+                            generators::matmul::build_shader(lhs, rhs, tensor, &workgroups),
+                            vec![lhs, rhs],
+                        ))
+                    }
+                    OperationSpec::AttentionOp(op) => {
+                        let query = lookup.get(&op.query.id()).unwrap();
+                        let key = lookup.get(&op.key.id()).unwrap();
+                        let value = lookup.get(&op.value.id()).unwrap();
+                        let mask = op.mask.as_ref().map(|mask| lookup.get(&mask.id()).unwrap());
+
+                        Some((
+                            generators::attention::build_shader(
+                                op,
+                                query,
+                                key,
+                                value,
+                                mask,
+                                tensor,
+                                &workgroups,
+                            ),
+                            [query, key, value].into_iter().chain(mask).collect(),
+                        ))
+                    }
+                    OperationSpec::LUOp(op) => {
+                        let input = lookup.get(&op.input.id()).unwrap();
+
+                        Some((
+                            generators::lu::build_shader(op, input, tensor, &workgroups),
+                            vec![input],
+                        ))
+                    }
+                    OperationSpec::TriangularSolveOp(op) => {
+                        let matrix = lookup.get(&op.matrix.id()).unwrap();
+                        let rhs = lookup.get(&op.rhs.id()).unwrap();
+
+                        Some((
+                            generators::triangular::build_shader(
+                                op,
+                                matrix,
+                                rhs,
+                                tensor,
+                                &workgroups,
+                            ),
+                            vec![matrix, rhs],
+                        ))
+                    }
+                    _ => None,
+                };
+
+                #[cfg(not(feature = "dtensor_shader_stitch"))]
+                let (shader, dependencies) = kernel.unwrap_or_else(|| {
                     let shader_ir = tensor.build_shader_ir();
                     let mut dependencies = shader_ir
                         .linearize()
@@ -205,7 +415,7 @@ impl WebGPUEvaluation for Tensor {
                 );
 
                     (shader, dependencies)
-                };
+                });
 
                 #[cfg(feature = "dtensor_spirv_passthrough")]
                 let compute_shader = {
@@ -326,6 +536,20 @@ impl WebGPUEvaluation for Tensor {
     }
 }
 
+impl WebGPUEvaluation for DataDependent {
+    fn evaluate_webgpu(&self, wgpu_device: &WebGPUDevice) -> Tensor {
+        // Count pass sizes the output, the fill pass then has a fixed shape
+        let count = self.count.evaluate_webgpu(wgpu_device).load::<i32>()[0];
+        let fill = self.resolve(count);
+
+        // Empty results are built on the host and have nothing to dispatch
+        if fill.len() == 0 {
+            return fill;
+        }
+        fill.evaluate_webgpu(wgpu_device)
+    }
+}
+
 pub fn webgpu_tensor_pipeline<'a>(
     pipeline: &WebGPUPipeline<'a>,
     wgpu_device: &WebGPUDevice,
@@ -49,14 +49,35 @@ pub enum ShaderIREvaluation {
     ABS,
     FLOOR,
     CEIL,
+    BITWISENOT,
+    ASIN,
+    ACOS,
+    ATAN,
+    ASINH,
+    ACOSH,
+    ATANH,
+    ISNAN,
+    ISINF,
     ADD,
     SUB,
     MULTIPLY,
     DIVIDE,
     MAX,
+    MIN,
     MOD,
     EQUAL,
     LESSTHAN,
+    BITWISEAND,
+    BITWISEOR,
+    BITWISEXOR,
+    SHIFTLEFT,
+    SHIFTRIGHT,
+    ATAN2,
+    SELECT,
+    ANY,
+    ALL,
+    ARGMAX,
+    ARGMIN,
 }
 
 impl ShaderIREvaluation {
@@ -73,14 +94,35 @@ impl ShaderIREvaluation {
             ShaderIREvaluation::ABS => 1,
             ShaderIREvaluation::FLOOR => 1,
             ShaderIREvaluation::CEIL => 1,
+            ShaderIREvaluation::BITWISENOT => 1,
+            ShaderIREvaluation::ASIN => 1,
+            ShaderIREvaluation::ACOS => 1,
+            ShaderIREvaluation::ATAN => 1,
+            ShaderIREvaluation::ASINH => 1,
+            ShaderIREvaluation::ACOSH => 1,
+            ShaderIREvaluation::ATANH => 1,
+            ShaderIREvaluation::ISNAN => 1,
+            ShaderIREvaluation::ISINF => 1,
             ShaderIREvaluation::ADD => 2,
             ShaderIREvaluation::SUB => 2,
             ShaderIREvaluation::MULTIPLY => 2,
             ShaderIREvaluation::DIVIDE => 2,
             ShaderIREvaluation::MAX => 2,
+            ShaderIREvaluation::MIN => 2,
             ShaderIREvaluation::MOD => 2,
             ShaderIREvaluation::EQUAL => 2,
             ShaderIREvaluation::LESSTHAN => 2,
+            ShaderIREvaluation::BITWISEAND => 2,
+            ShaderIREvaluation::BITWISEOR => 2,
+            ShaderIREvaluation::BITWISEXOR => 2,
+            ShaderIREvaluation::SHIFTLEFT => 2,
+            ShaderIREvaluation::SHIFTRIGHT => 2,
+            ShaderIREvaluation::ATAN2 => 2,
+            ShaderIREvaluation::SELECT => 3,
+            ShaderIREvaluation::ANY => 2,
+            ShaderIREvaluation::ALL => 2,
+            ShaderIREvaluation::ARGMAX => 2,
+            ShaderIREvaluation::ARGMIN => 2,
         }
     }
 }
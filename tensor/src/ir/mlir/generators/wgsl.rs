@@ -7,6 +7,12 @@ impl ShaderIR {
             .into_iter()
             .map(|ir| match &ir.0.op {
                 ShaderIROp::Const => match &ir.0.evaltype {
+                    // Non-finite values have no WGSL literal
+                    Some(ShaderIREvaluation::F32(float)) if !float.is_finite() => format!(
+                        "let {} = bitcast<f32>({:#010x}u);",
+                        ir.variable(),
+                        float.to_bits()
+                    ),
                     Some(ShaderIREvaluation::F32(float)) => {
                         format!("let {} = {}f;", ir.variable(), float)
                     }
@@ -67,6 +73,58 @@ impl ShaderIR {
                         ir.variable(),
                         ir.inputs()[0].variable()
                     ),
+                    ShaderIREvaluation::BITWISENOT => {
+                        format!("let {} = ~{};", ir.variable(), ir.inputs()[0].variable())
+                    }
+                    ShaderIREvaluation::ASIN => format!(
+                        "let {} = asin({});",
+                        ir.variable(),
+                        ir.inputs()[0].variable()
+                    ),
+                    ShaderIREvaluation::ACOS => format!(
+                        "let {} = acos({});",
+                        ir.variable(),
+                        ir.inputs()[0].variable()
+                    ),
+                    ShaderIREvaluation::ATAN => format!(
+                        "let {} = atan({});",
+                        ir.variable(),
+                        ir.inputs()[0].variable()
+                    ),
+                    ShaderIREvaluation::ASINH => format!(
+                        "let {} = asinh({});",
+                        ir.variable(),
+                        ir.inputs()[0].variable()
+                    ),
+                    ShaderIREvaluation::ACOSH => format!(
+                        "let {} = acosh({});",
+                        ir.variable(),
+                        ir.inputs()[0].variable()
+                    ),
+                    ShaderIREvaluation::ATANH => format!(
+                        "let {} = atanh({});",
+                        ir.variable(),
+                        ir.inputs()[0].variable()
+                    ),
+                    // Compare the bit patterns so fast-math cannot assume values are finite
+                    ShaderIREvaluation::ISNAN => format!(
+                        "let {} = {}((bitcast<u32>({}) & 0x7fffffffu) > 0x7f800000u);",
+                        ir.variable(),
+                        match ir.datatype() {
+                            ShaderIRType::F32 => "f32",
+                            ShaderIRType::I32 => "i32",
+                        },
+                        ir.inputs()[0].variable()
+                    ),
+                    ShaderIREvaluation::ISINF => format!(
+                        "let {} = {}((bitcast<u32>({}) & 0x7fffffffu) == 0x7f800000u);",
+                        ir.variable(),
+                        match ir.datatype() {
+                            ShaderIRType::F32 => "f32",
+                            ShaderIRType::I32 => "i32",
+                        },
+                        ir.inputs()[0].variable()
+                    ),
                     ShaderIREvaluation::ADD => format!(
                         "let {} = {} + {};",
                         ir.variable(),
@@ -97,6 +155,12 @@ impl ShaderIR {
                         ir.inputs()[0].variable(),
                         ir.inputs()[1].variable()
                     ),
+                    ShaderIREvaluation::MIN => format!(
+                        "let {} = min({}, {});",
+                        ir.variable(),
+                        ir.inputs()[0].variable(),
+                        ir.inputs()[1].variable()
+                    ),
                     ShaderIREvaluation::MOD => format!(
                         "let {} = {} % {};",
                         ir.variable(),
@@ -114,11 +178,59 @@ impl ShaderIR {
                         ir.inputs()[1].variable()
                     ),
                     ShaderIREvaluation::LESSTHAN => format!(
-                        "let {} = {} < {};",
+                        "let {} = {}({} < {});",
+                        ir.variable(),
+                        match ir.datatype() {
+                            ShaderIRType::F32 => "f32",
+                            ShaderIRType::I32 => "i32",
+                        },
+                        ir.inputs()[0].variable(),
+                        ir.inputs()[1].variable()
+                    ),
+                    ShaderIREvaluation::BITWISEAND => format!(
+                        "let {} = {} & {};",
+                        ir.variable(),
+                        ir.inputs()[0].variable(),
+                        ir.inputs()[1].variable()
+                    ),
+                    ShaderIREvaluation::BITWISEOR => format!(
+                        "let {} = {} | {};",
+                        ir.variable(),
+                        ir.inputs()[0].variable(),
+                        ir.inputs()[1].variable()
+                    ),
+                    ShaderIREvaluation::BITWISEXOR => format!(
+                        "let {} = {} ^ {};",
+                        ir.variable(),
+                        ir.inputs()[0].variable(),
+                        ir.inputs()[1].variable()
+                    ),
+                    // WGSL only shifts by unsigned amounts
+                    ShaderIREvaluation::SHIFTLEFT => format!(
+                        "let {} = {} << u32({});",
+                        ir.variable(),
+                        ir.inputs()[0].variable(),
+                        ir.inputs()[1].variable()
+                    ),
+                    ShaderIREvaluation::SHIFTRIGHT => format!(
+                        "let {} = {} >> u32({});",
                         ir.variable(),
                         ir.inputs()[0].variable(),
                         ir.inputs()[1].variable()
                     ),
+                    ShaderIREvaluation::ATAN2 => format!(
+                        "let {} = atan2({}, {});",
+                        ir.variable(),
+                        ir.inputs()[0].variable(),
+                        ir.inputs()[1].variable()
+                    ),
+                    ShaderIREvaluation::SELECT => format!(
+                        "let {} = select({}, {}, bool({}));",
+                        ir.variable(),
+                        ir.inputs()[2].variable(),
+                        ir.inputs()[1].variable(),
+                        ir.inputs()[0].variable()
+                    ),
                     _ => panic!(),
                 },
                 ShaderIROp::Load => format!(
@@ -134,6 +246,7 @@ impl ShaderIR {
                 ShaderIROp::ReduceBegin => {
                     format!(
                         "var {var_name}_acc: {acc_type};
+var {var_name}_arg = 0i;
 for (var {var_name} = 0i; {var_name} < {length}i; {var_name}++) {{",
                         acc_type = match ir.0.datatype {
                             ShaderIRType::F32 => "f32",
@@ -146,31 +259,76 @@ for (var {var_name} = 0i; {var_name} < {length}i; {var_name}++) {{",
                         },
                     )
                 }
-                ShaderIROp::ReduceEnd => format!(
-                    "if {reduce} == 0i {{
+                ShaderIROp::ReduceEnd => {
+                    let acc_type = match ir.0.datatype {
+                        ShaderIRType::F32 => "f32",
+                        ShaderIRType::I32 => "i32",
+                    };
+                    let reduce = ir.inputs()[0].variable();
+                    let element = ir.inputs()[1].variable();
+
+                    match &ir.0.evaltype {
+                        // Index-tracking reductions keep the first extremum along the axis
+                        Some(ShaderIREvaluation::ARGMAX) | Some(ShaderIREvaluation::ARGMIN) => {
+                            format!(
+                                "if {reduce} == 0i || {element} {comparison} {reduce}_acc {{
 {reduce}_acc = {element};
+{reduce}_arg = {reduce};
+}}
+}}
+let {self_name} = {acc_type}({reduce}_arg);",
+                                self_name = ir.variable(),
+                                reduce = reduce,
+                                element = element,
+                                acc_type = acc_type,
+                                comparison = match &ir.0.evaltype {
+                                    Some(ShaderIREvaluation::ARGMAX) => ">",
+                                    _ => "<",
+                                },
+                            )
+                        }
+                        _ => format!(
+                            "if {reduce} == 0i {{
+{reduce}_acc = {initial};
 }} else {{
 {reduce}_acc = {expression};
 }}
 }}
 let {self_name} = {reduce}_acc;",
-                    self_name = ir.variable(),
-                    reduce = ir.inputs()[0].variable(),
-                    element = ir.inputs()[1].variable(),
-                    expression = match &ir.0.evaltype {
-                        Some(ShaderIREvaluation::ADD) => format!(
-                            "{}_acc + {}",
-                            ir.inputs()[0].variable(),
-                            ir.inputs()[1].variable()
+                            self_name = ir.variable(),
+                            reduce = reduce,
+                            initial = match &ir.0.evaltype {
+                                Some(ShaderIREvaluation::ANY) | Some(ShaderIREvaluation::ALL) => {
+                                    format!("{}({} != {}(0))", acc_type, element, acc_type)
+                                }
+                                _ => element.clone(),
+                            },
+                            expression = match &ir.0.evaltype {
+                                Some(ShaderIREvaluation::ADD) => {
+                                    format!("{}_acc + {}", reduce, element)
+                                }
+                                Some(ShaderIREvaluation::MULTIPLY) => {
+                                    format!("{}_acc * {}", reduce, element)
+                                }
+                                Some(ShaderIREvaluation::MAX) => {
+                                    format!("max({}_acc, {})", reduce, element)
+                                }
+                                Some(ShaderIREvaluation::MIN) => {
+                                    format!("min({}_acc, {})", reduce, element)
+                                }
+                                Some(ShaderIREvaluation::ANY) => format!(
+                                    "max({}_acc, {}({} != {}(0)))",
+                                    reduce, acc_type, element, acc_type
+                                ),
+                                Some(ShaderIREvaluation::ALL) => format!(
+                                    "min({}_acc, {}({} != {}(0)))",
+                                    reduce, acc_type, element, acc_type
+                                ),
+                                _ => panic!(),
+                            }
                         ),
-                        Some(ShaderIREvaluation::MAX) => format!(
-                            "max({}_acc, {})",
-                            ir.inputs()[0].variable(),
-                            ir.inputs()[1].variable()
-                        ),
-                        _ => panic!(),
                     }
-                ),
+                }
                 ShaderIROp::Store => format!(
                     "tensor_{tensor_id}[index] = {value};",
                     tensor_id = match ir.0.evaltype {
@@ -1,10 +1,11 @@
 use crate::primitives::tensor::*;
-use crate::primitives::tensorview::{TensorView, TensorViewTracker};
+use crate::primitives::tensorview::{PadMode, TensorView, TensorViewTracker, ViewType};
 
 use crate::ir::mlir::*;
 
 trait SerializeShaderIR {
     fn shader_ir(&self, index_ir: &ShaderIR) -> ShaderIR;
+    fn predicated_shader_ir(&self, index_ir: &ShaderIR) -> (ShaderIR, Vec<ShaderIRPredicate>);
 }
 
 trait UnrollShaderIR {
@@ -23,6 +24,12 @@ struct ShaderIRIndex {
     tensor: Tensor,
 }
 
+#[derive(Clone, Debug)]
+struct ShaderIRPredicate {
+    predicate: ShaderIR,
+    fallback: f32,
+}
+
 #[derive(Clone, Debug)]
 struct ShaderIRReduction {
     reduce_begin: ShaderIR,
@@ -50,6 +57,50 @@ impl VirtualShaderIR {
         VirtualShaderIR::Value(ir)
     }
 
+    pub fn view(index_ir: &ShaderIR, tensor: &Tensor) -> Vec<VirtualShaderIR> {
+        VirtualShaderIR::tracked_view(index_ir, tensor, tensor.viewtracker())
+    }
+
+    pub fn tracked_view(
+        index_ir: &ShaderIR,
+        tensor: &Tensor,
+        viewtracker: &TensorViewTracker,
+    ) -> Vec<VirtualShaderIR> {
+        let ir_type = Into::<ShaderIRType>::into(tensor.datatype());
+        let (index, predicates) = viewtracker.predicated_shader_ir(index_ir);
+
+        // Each predicate selects between the read and its fallback, nested from the
+        // innermost view outwards so the outermost out-of-range read takes precedence
+        predicates.into_iter().rev().fold(
+            vec![VirtualShaderIR::index(index, tensor.clone())],
+            |read,
+             ShaderIRPredicate {
+                 predicate,
+                 fallback,
+             }| {
+                let fallback_ir = ShaderIR::new(
+                    ShaderIROp::Const,
+                    ir_type,
+                    &[],
+                    Some(match ir_type {
+                        ShaderIRType::F32 => ShaderIREvaluation::F32(fallback),
+                        ShaderIRType::I32 => ShaderIREvaluation::I32(fallback as i32),
+                    }),
+                );
+
+                [
+                    vec![
+                        VirtualShaderIR::op(ir_type, ShaderIREvaluation::SELECT),
+                        VirtualShaderIR::value(predicate),
+                    ],
+                    read,
+                    vec![VirtualShaderIR::value(fallback_ir)],
+                ]
+                .concat()
+            },
+        )
+    }
+
     pub fn reduce(reduce_begin: ShaderIR, reduction: ShaderIREvaluation) -> VirtualShaderIR {
         VirtualShaderIR::ReduceEnd(ShaderIRReduction {
             reduce_begin,
@@ -64,46 +115,7 @@ impl ShaderIRBuilder for Tensor {
         let magic_index = ShaderIR::new(ShaderIROp::MagicIndex, ShaderIRType::I32, &[], None);
         let magic_metadata = VirtualShaderIR::index(magic_index, self.clone());
 
-        // Compute indices using two-level queue, constructed via postfix (reverse-polish notation)
-        let mut inputs = Vec::new();
-        let mut queue = Vec::from([magic_metadata]);
-        while let Some(metadata) = queue.pop() {
-            match metadata {
-                VirtualShaderIR::Operator(operation) => {
-                    let args = (0..operation.op.n_dependencies())
-                        .map(|_| inputs.pop().unwrap())
-                        .collect::<Vec<_>>();
-
-                    let result = ShaderIR::new(
-                        ShaderIROp::Evaluate,
-                        operation.datatype,
-                        &args[..],
-                        Some(operation.op),
-                    );
-                    inputs.push(result);
-                }
-                VirtualShaderIR::TranslatedIndex(ref index_ir) => {
-                    let ir_type = Into::<ShaderIRType>::into(index_ir.tensor.datatype());
-                    let mut unrolled_ir = index_ir.tensor.unroll_ir(&index_ir, ir_type);
-                    queue.append(&mut unrolled_ir);
-                }
-                VirtualShaderIR::Value(value) => inputs.push(value),
-                VirtualShaderIR::ReduceEnd(reduce) => {
-                    let reduce_value = inputs.pop().unwrap();
-                    let result = ShaderIR::new(
-                        ShaderIROp::ReduceEnd,
-                        reduce_value.datatype(),
-                        &[reduce.reduce_begin, reduce_value],
-                        Some(reduce.reduction),
-                    );
-
-                    inputs.push(result);
-                }
-            }
-        }
-
-        assert!(inputs.len() == 1);
-        let final_index = inputs.pop().unwrap();
+        let final_index = evaluate_virtual_ir(vec![magic_metadata]);
         ShaderIR::new(
             ShaderIROp::Store,
             Into::<ShaderIRType>::into(self.datatype()),
@@ -113,11 +125,54 @@ impl ShaderIRBuilder for Tensor {
     }
 }
 
+// Collapses a virtual expression into the ShaderIR of its value
+fn evaluate_virtual_ir(mut queue: Vec<VirtualShaderIR>) -> ShaderIR {
+    // Compute indices using two-level queue, constructed via postfix (reverse-polish notation)
+    let mut inputs = Vec::new();
+    while let Some(metadata) = queue.pop() {
+        match metadata {
+            VirtualShaderIR::Operator(operation) => {
+                let args = (0..operation.op.n_dependencies())
+                    .map(|_| inputs.pop().unwrap())
+                    .collect::<Vec<_>>();
+
+                let result = ShaderIR::new(
+                    ShaderIROp::Evaluate,
+                    operation.datatype,
+                    &args[..],
+                    Some(operation.op),
+                );
+                inputs.push(result);
+            }
+            VirtualShaderIR::TranslatedIndex(ref index_ir) => {
+                let ir_type = Into::<ShaderIRType>::into(index_ir.tensor.datatype());
+                let mut unrolled_ir = index_ir.tensor.unroll_ir(index_ir, ir_type);
+                queue.append(&mut unrolled_ir);
+            }
+            VirtualShaderIR::Value(value) => inputs.push(value),
+            VirtualShaderIR::ReduceEnd(reduce) => {
+                let reduce_value = inputs.pop().unwrap();
+                let result = ShaderIR::new(
+                    ShaderIROp::ReduceEnd,
+                    reduce_value.datatype(),
+                    &[reduce.reduce_begin, reduce_value],
+                    Some(reduce.reduction),
+                );
+
+                inputs.push(result);
+            }
+        }
+    }
+
+    assert!(inputs.len() == 1);
+    inputs.pop().unwrap()
+}
+
 impl Into<ShaderIRType> for TensorType {
     fn into(self) -> ShaderIRType {
         match &self {
             TensorType::F32 => ShaderIRType::F32,
-            TensorType::I32 => ShaderIRType::I32,
+            TensorType::I32 | TensorType::I8 | TensorType::U8 => ShaderIRType::I32,
             _ => panic!("{:?} is unsupported as ShaderIRType", self),
         }
     }
@@ -127,11 +182,20 @@ impl Into<ShaderIREvaluation> for UnaryType {
     fn into(self) -> ShaderIREvaluation {
         match &self {
             UnaryType::ABS => ShaderIREvaluation::ABS,
+            UnaryType::ACOS => ShaderIREvaluation::ACOS,
+            UnaryType::ACOSH => ShaderIREvaluation::ACOSH,
+            UnaryType::ASIN => ShaderIREvaluation::ASIN,
+            UnaryType::ASINH => ShaderIREvaluation::ASINH,
+            UnaryType::ATAN => ShaderIREvaluation::ATAN,
+            UnaryType::ATANH => ShaderIREvaluation::ATANH,
+            UnaryType::BITWISENOT => ShaderIREvaluation::BITWISENOT,
             UnaryType::CAST => ShaderIREvaluation::CAST,
             UnaryType::CEIL => ShaderIREvaluation::CEIL,
             UnaryType::EXP2 => ShaderIREvaluation::EXP2,
             UnaryType::FLOOR => ShaderIREvaluation::FLOOR,
             UnaryType::IDENTITY => ShaderIREvaluation::IDENTITY,
+            UnaryType::ISINF => ShaderIREvaluation::ISINF,
+            UnaryType::ISNAN => ShaderIREvaluation::ISNAN,
             UnaryType::LOG2 => ShaderIREvaluation::LOG2,
             UnaryType::SIN => ShaderIREvaluation::SIN,
             UnaryType::SQRT => ShaderIREvaluation::SQRT,
@@ -143,26 +207,56 @@ impl Into<ShaderIREvaluation> for BinaryType {
     fn into(self) -> ShaderIREvaluation {
         match &self {
             BinaryType::ADD => ShaderIREvaluation::ADD,
+            BinaryType::ATAN2 => ShaderIREvaluation::ATAN2,
+            BinaryType::BITWISEAND => ShaderIREvaluation::BITWISEAND,
+            BinaryType::BITWISEOR => ShaderIREvaluation::BITWISEOR,
+            BinaryType::BITWISEXOR => ShaderIREvaluation::BITWISEXOR,
             BinaryType::DIVIDE => ShaderIREvaluation::DIVIDE,
             BinaryType::EQUAL => ShaderIREvaluation::EQUAL,
             BinaryType::LESSTHAN => ShaderIREvaluation::LESSTHAN,
             BinaryType::MAX => ShaderIREvaluation::MAX,
             BinaryType::MOD => ShaderIREvaluation::MOD,
             BinaryType::MULTIPLY => ShaderIREvaluation::MULTIPLY,
+            BinaryType::SHIFTLEFT => ShaderIREvaluation::SHIFTLEFT,
+            BinaryType::SHIFTRIGHT => ShaderIREvaluation::SHIFTRIGHT,
             BinaryType::SUB => ShaderIREvaluation::SUB,
         }
     }
 }
 
+impl Into<ShaderIREvaluation> for TernaryType {
+    fn into(self) -> ShaderIREvaluation {
+        match &self {
+            TernaryType::SELECT => ShaderIREvaluation::SELECT,
+        }
+    }
+}
+
 impl Into<ShaderIREvaluation> for ReduceType {
     fn into(self) -> ShaderIREvaluation {
         match &self {
+            ReduceType::ALL => ShaderIREvaluation::ALL,
+            ReduceType::ANY => ShaderIREvaluation::ANY,
+            ReduceType::ARGMAX => ShaderIREvaluation::ARGMAX,
+            ReduceType::ARGMIN => ShaderIREvaluation::ARGMIN,
             ReduceType::MAX => ShaderIREvaluation::MAX,
+            ReduceType::MIN => ShaderIREvaluation::MIN,
+            ReduceType::PROD => ShaderIREvaluation::MULTIPLY,
             ReduceType::SUM => ShaderIREvaluation::ADD,
         }
     }
 }
 
+impl Into<ShaderIREvaluation> for ScanType {
+    fn into(self) -> ShaderIREvaluation {
+        match &self {
+            ScanType::MAX => ShaderIREvaluation::MAX,
+            ScanType::PROD => ShaderIREvaluation::MULTIPLY,
+            ScanType::SUM => ShaderIREvaluation::ADD,
+        }
+    }
+}
+
 impl UnrollShaderIR for Tensor {
     fn unroll_ir(&self, index_ir: &ShaderIRIndex, ir_type: ShaderIRType) -> Vec<VirtualShaderIR> {
         match self.data() {
@@ -245,8 +339,20 @@ impl UnrollShaderIR for OperationSpec {
     fn unroll_ir(&self, inputs: &ShaderIRIndex, ir_type: ShaderIRType) -> Vec<VirtualShaderIR> {
         match self {
             OperationSpec::BinaryOp(spec) => spec.unroll_ir(inputs, ir_type),
+            OperationSpec::TernaryOp(spec) => spec.unroll_ir(inputs, ir_type),
             OperationSpec::ReduceOp(spec) => spec.unroll_ir(inputs, ir_type),
             OperationSpec::UnaryOp(spec) => spec.unroll_ir(inputs, ir_type),
+            OperationSpec::ScanOp(spec) => spec.unroll_ir(inputs, ir_type),
+            OperationSpec::SortOp(spec) => spec.unroll_ir(inputs, ir_type),
+            OperationSpec::MatMulOp(spec) => spec.unroll_ir(inputs, ir_type),
+            OperationSpec::ConcatOp(spec) => spec.unroll_ir(inputs, ir_type),
+            OperationSpec::TakeOp(spec) => spec.unroll_ir(inputs, ir_type),
+            OperationSpec::AttentionOp(spec) => spec.unroll_ir(inputs, ir_type),
+            // Sequential eliminations cannot be inlined, so they are always evaluated by their
+            // own kernels before being read
+            OperationSpec::LUOp(_) | OperationSpec::TriangularSolveOp(_) => {
+                panic!("LU and TriangularSolve must be evaluated by their own kernels")
+            }
         }
     }
 }
@@ -255,13 +361,11 @@ impl UnrollShaderIR for UnarySpec {
     fn unroll_ir(&self, index_ir: &ShaderIRIndex, ir_type: ShaderIRType) -> Vec<VirtualShaderIR> {
         let index_ir = &index_ir.index;
         let evaltype = Into::<ShaderIREvaluation>::into(self.op);
-        vec![
-            VirtualShaderIR::op(ir_type, evaltype),
-            VirtualShaderIR::index(
-                self.input.viewtracker().shader_ir(index_ir),
-                self.input.clone(),
-            ),
+        [
+            vec![VirtualShaderIR::op(ir_type, evaltype)],
+            VirtualShaderIR::view(index_ir, &self.input),
         ]
+        .concat()
     }
 }
 
@@ -269,11 +373,26 @@ impl UnrollShaderIR for BinarySpec {
     fn unroll_ir(&self, index_ir: &ShaderIRIndex, ir_type: ShaderIRType) -> Vec<VirtualShaderIR> {
         let index_ir = &index_ir.index;
         let evaltype = Into::<ShaderIREvaluation>::into(self.op);
-        vec![
-            VirtualShaderIR::op(ir_type, evaltype),
-            VirtualShaderIR::index(self.lhs.viewtracker().shader_ir(index_ir), self.lhs.clone()),
-            VirtualShaderIR::index(self.rhs.viewtracker().shader_ir(index_ir), self.rhs.clone()),
+        [
+            vec![VirtualShaderIR::op(ir_type, evaltype)],
+            VirtualShaderIR::view(index_ir, &self.lhs),
+            VirtualShaderIR::view(index_ir, &self.rhs),
+        ]
+        .concat()
+    }
+}
+
+impl UnrollShaderIR for TernarySpec {
+    fn unroll_ir(&self, index_ir: &ShaderIRIndex, ir_type: ShaderIRType) -> Vec<VirtualShaderIR> {
+        let index_ir = &index_ir.index;
+        let evaltype = Into::<ShaderIREvaluation>::into(self.op);
+        [
+            vec![VirtualShaderIR::op(ir_type, evaltype)],
+            VirtualShaderIR::view(index_ir, &self.condition),
+            VirtualShaderIR::view(index_ir, &self.lhs),
+            VirtualShaderIR::view(index_ir, &self.rhs),
         ]
+        .concat()
     }
 }
 
@@ -329,80 +448,541 @@ impl UnrollShaderIR for ReduceSpec {
             Some(ShaderIREvaluation::ADD),
         );
 
-        vec![
-            VirtualShaderIR::reduce(reduce_begin, evaltype),
-            VirtualShaderIR::index(
-                self.input.viewtracker().shader_ir(&loop_index_ir),
-                self.input.clone(),
+        [
+            vec![VirtualShaderIR::reduce(reduce_begin, evaltype)],
+            VirtualShaderIR::view(&loop_index_ir, &self.input),
+        ]
+        .concat()
+    }
+}
+
+impl UnrollShaderIR for ConcatSpec {
+    fn unroll_ir(&self, index_ir: &ShaderIRIndex, ir_type: ShaderIRType) -> Vec<VirtualShaderIR> {
+        let index_ir = &index_ir.index;
+        let axis = self.axis as usize;
+        let ranges = self.ranges();
+
+        let mut output_shape = self.inputs[0].shape().to_vec();
+        output_shape[axis] = ranges.last().unwrap().1;
+        let output_view = TensorView::from_contiguous_shape(&output_shape[..]);
+
+        // index / contiguous_stride % shape along the concatenated axis
+        let coordinate_ir = index_evaluate(
+            ShaderIREvaluation::MOD,
+            &[
+                index_evaluate(
+                    ShaderIREvaluation::DIVIDE,
+                    &[
+                        index_ir.clone(),
+                        index_const(output_view.contiguous_stride()[axis]),
+                    ],
+                ),
+                index_const(output_shape[axis]),
+            ],
+        );
+
+        // Every input is read at its offset along the axis, clamped so reads stay in bounds
+        let reads = self
+            .inputs
+            .iter()
+            .zip(ranges.iter())
+            .map(|(input, &(begin, end))| {
+                let padding = (0..input.ndim())
+                    .map(|dimension| {
+                        if dimension == self.axis {
+                            (begin, output_shape[axis] - end)
+                        } else {
+                            (0, 0)
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                let viewtracker = input
+                    .viewtracker()
+                    .track_view(&input.view().pad(&padding[..], PadMode::Edge));
+
+                VirtualShaderIR::tracked_view(index_ir, input, &viewtracker)
+            })
+            .collect::<Vec<_>>();
+
+        // Select the first input whose range contains the coordinate
+        let (last_read, reads) = reads.split_last().unwrap();
+        reads.iter().zip(ranges.iter()).rev().fold(
+            last_read.clone(),
+            |selected, (read, &(_, end))| {
+                let predicate_ir = index_evaluate(
+                    ShaderIREvaluation::LESSTHAN,
+                    &[coordinate_ir.clone(), index_const(end)],
+                );
+
+                [
+                    vec![
+                        VirtualShaderIR::op(ir_type, ShaderIREvaluation::SELECT),
+                        VirtualShaderIR::value(predicate_ir),
+                    ],
+                    read.clone(),
+                    selected,
+                ]
+                .concat()
+            },
+        )
+    }
+}
+
+impl UnrollShaderIR for ScanSpec {
+    fn unroll_ir(&self, index_ir: &ShaderIRIndex, ir_type: ShaderIRType) -> Vec<VirtualShaderIR> {
+        let index_ir = &index_ir.index;
+        let evaltype = Into::<ShaderIREvaluation>::into(self.op);
+        let axis = self.axis as usize;
+        let axis_shape = self.input.shape()[axis];
+        let axis_stride = self.input.view().contiguous_stride()[axis];
+
+        // index / contiguous_stride % shape along the scanned axis
+        let coordinate_ir = index_evaluate(
+            ShaderIREvaluation::MOD,
+            &[
+                index_evaluate(
+                    ShaderIREvaluation::DIVIDE,
+                    &[index_ir.clone(), index_const(axis_stride)],
+                ),
+                index_const(axis_shape),
+            ],
+        );
+        // Index of the first element along the axis
+        let base_ir = index_evaluate(
+            ShaderIREvaluation::SUB,
+            &[
+                index_ir.clone(),
+                index_evaluate(
+                    ShaderIREvaluation::MULTIPLY,
+                    &[coordinate_ir.clone(), index_const(axis_stride)],
+                ),
+            ],
+        );
+
+        let reduce_begin = ShaderIR::new(
+            ShaderIROp::ReduceBegin,
+            ir_type,
+            std::slice::from_ref(&base_ir),
+            Some(ShaderIREvaluation::I32(axis_shape)),
+        );
+        let loop_index_ir = index_evaluate(
+            ShaderIREvaluation::ADD,
+            &[
+                base_ir,
+                index_evaluate(
+                    ShaderIREvaluation::MULTIPLY,
+                    &[reduce_begin.clone(), index_const(axis_stride)],
+                ),
+            ],
+        );
+
+        // Every element along the axis is visited, those outside of the scanned range
+        // contribute the identity of the scan instead
+        let predicate_ir = match (self.reverse, self.exclusive) {
+            (false, false) => index_evaluate(
+                ShaderIREvaluation::LESSTHAN,
+                &[
+                    reduce_begin.clone(),
+                    index_evaluate(ShaderIREvaluation::ADD, &[coordinate_ir, index_const(1)]),
+                ],
+            ),
+            (false, true) => index_evaluate(
+                ShaderIREvaluation::LESSTHAN,
+                &[reduce_begin.clone(), coordinate_ir],
+            ),
+            (true, false) => index_evaluate(
+                ShaderIREvaluation::LESSTHAN,
+                &[
+                    index_evaluate(ShaderIREvaluation::SUB, &[coordinate_ir, index_const(1)]),
+                    reduce_begin.clone(),
+                ],
             ),
+            (true, true) => index_evaluate(
+                ShaderIREvaluation::LESSTHAN,
+                &[coordinate_ir, reduce_begin.clone()],
+            ),
+        };
+        let identity = |value: f32| {
+            vec![VirtualShaderIR::value(ShaderIR::new(
+                ShaderIROp::Const,
+                ir_type,
+                &[],
+                Some(match ir_type {
+                    ShaderIRType::F32 => ShaderIREvaluation::F32(value),
+                    ShaderIRType::I32 => ShaderIREvaluation::I32(value as i32),
+                }),
+            ))]
+        };
+        let fallback = match self.op {
+            ScanType::SUM => identity(0.0),
+            ScanType::PROD => identity(1.0),
+            // The element itself is always within an inclusive scan
+            ScanType::MAX => VirtualShaderIR::view(index_ir, &self.input),
+        };
+
+        [
+            vec![
+                VirtualShaderIR::reduce(reduce_begin, evaltype),
+                VirtualShaderIR::op(ir_type, ShaderIREvaluation::SELECT),
+                VirtualShaderIR::value(predicate_ir),
+            ],
+            VirtualShaderIR::view(&loop_index_ir, &self.input),
+            fallback,
         ]
+        .concat()
     }
 }
 
-impl SerializeShaderIR for TensorViewTracker {
-    fn shader_ir(&self, index_ir: &ShaderIR) -> ShaderIR {
+impl UnrollShaderIR for SortSpec {
+    fn unroll_ir(&self, index_ir: &ShaderIRIndex, ir_type: ShaderIRType) -> Vec<VirtualShaderIR> {
+        let index_ir = &index_ir.index;
+        let axis = self.axis as usize;
+        let axis_shape = self.input.shape()[axis];
+        let axis_stride = self.input.view().contiguous_stride()[axis];
+
+        // index / contiguous_stride % shape along the sorted axis
+        let position_ir = index_evaluate(
+            ShaderIREvaluation::MOD,
+            &[
+                index_evaluate(
+                    ShaderIREvaluation::DIVIDE,
+                    &[index_ir.clone(), index_const(axis_stride)],
+                ),
+                index_const(axis_shape),
+            ],
+        );
+        // Index of the first element along the axis
+        let base_ir = index_evaluate(
+            ShaderIREvaluation::SUB,
+            &[
+                index_ir.clone(),
+                index_evaluate(
+                    ShaderIREvaluation::MULTIPLY,
+                    &[position_ir.clone(), index_const(axis_stride)],
+                ),
+            ],
+        );
+        let element_index_ir = |element: &ShaderIR| {
+            index_evaluate(
+                ShaderIREvaluation::ADD,
+                &[
+                    base_ir.clone(),
+                    index_evaluate(
+                        ShaderIREvaluation::MULTIPLY,
+                        &[element.clone(), index_const(axis_stride)],
+                    ),
+                ],
+            )
+        };
+
+        // The candidate is written to the output position matching its rank
+        let candidate = ShaderIR::new(
+            ShaderIROp::ReduceBegin,
+            ir_type,
+            std::slice::from_ref(&base_ir),
+            Some(ShaderIREvaluation::I32(axis_shape)),
+        );
+        let candidate_index_ir = element_index_ir(&candidate);
+        let other = ShaderIR::new(
+            ShaderIROp::ReduceBegin,
+            ShaderIRType::I32,
+            std::slice::from_ref(&candidate_index_ir),
+            Some(ShaderIREvaluation::I32(axis_shape)),
+        );
+        let other_index_ir = element_index_ir(&other);
+
+        // Elements ordered before the candidate, ties are broken by their position
+        let (lhs_index_ir, rhs_index_ir) = if self.descending {
+            (&candidate_index_ir, &other_index_ir)
+        } else {
+            (&other_index_ir, &candidate_index_ir)
+        };
+        let rank = [
+            vec![
+                VirtualShaderIR::reduce(other.clone(), ShaderIREvaluation::ADD),
+                VirtualShaderIR::op(ShaderIRType::I32, ShaderIREvaluation::ADD),
+                VirtualShaderIR::op(ShaderIRType::I32, ShaderIREvaluation::LESSTHAN),
+            ],
+            VirtualShaderIR::view(lhs_index_ir, &self.input),
+            VirtualShaderIR::view(rhs_index_ir, &self.input),
+            vec![
+                VirtualShaderIR::op(ShaderIRType::I32, ShaderIREvaluation::MULTIPLY),
+                VirtualShaderIR::op(ShaderIRType::I32, ShaderIREvaluation::EQUAL),
+            ],
+            VirtualShaderIR::view(&other_index_ir, &self.input),
+            VirtualShaderIR::view(&candidate_index_ir, &self.input),
+            vec![VirtualShaderIR::value(index_evaluate(
+                ShaderIREvaluation::LESSTHAN,
+                &[other, candidate.clone()],
+            ))],
+        ]
+        .concat();
+
+        let selected = match self.op {
+            SortType::VALUES => VirtualShaderIR::view(&candidate_index_ir, &self.input),
+            SortType::INDICES => vec![VirtualShaderIR::value(candidate.clone())],
+        };
         let zero = ShaderIR::new(
             ShaderIROp::Const,
-            ShaderIRType::I32,
+            ir_type,
             &[],
-            Some(ShaderIREvaluation::I32(0)),
+            Some(match ir_type {
+                ShaderIRType::F32 => ShaderIREvaluation::F32(0.0),
+                ShaderIRType::I32 => ShaderIREvaluation::I32(0),
+            }),
         );
 
-        self.serialized_history_fifo()
-            .iter()
-            .fold(index_ir.clone(), |previous_index_ir, view| {
-                view.shape
+        [
+            vec![
+                VirtualShaderIR::reduce(candidate, ShaderIREvaluation::ADD),
+                VirtualShaderIR::op(ir_type, ShaderIREvaluation::SELECT),
+                VirtualShaderIR::op(ShaderIRType::I32, ShaderIREvaluation::EQUAL),
+            ],
+            rank,
+            vec![VirtualShaderIR::value(position_ir)],
+            selected,
+            vec![VirtualShaderIR::value(zero)],
+        ]
+        .concat()
+    }
+}
+
+impl UnrollShaderIR for MatMulSpec {
+    fn unroll_ir(&self, index_ir: &ShaderIRIndex, ir_type: ShaderIRType) -> Vec<VirtualShaderIR> {
+        let index_ir = &index_ir.index;
+        let [.., n, k] = self.lhs.shape() else {
+            panic!("MatMul requires input to be at least 2D");
+        };
+        let [.., _, m] = self.rhs.shape() else {
+            panic!("MatMul requires tensor to be at least 2D");
+        };
+
+        // Output index (..., row, column) splits into the row of lhs and the column of rhs
+        let row_ir = index_evaluate(
+            ShaderIREvaluation::DIVIDE,
+            &[index_ir.clone(), index_const(*m)],
+        );
+        let lhs_base_ir = index_evaluate(ShaderIREvaluation::MULTIPLY, &[row_ir, index_const(*k)]);
+        let rhs_base_ir = index_evaluate(
+            ShaderIREvaluation::ADD,
+            &[
+                index_evaluate(
+                    ShaderIREvaluation::MULTIPLY,
+                    &[
+                        index_evaluate(
+                            ShaderIREvaluation::DIVIDE,
+                            &[index_ir.clone(), index_const(n * m)],
+                        ),
+                        index_const(k * m),
+                    ],
+                ),
+                index_evaluate(
+                    ShaderIREvaluation::MOD,
+                    &[index_ir.clone(), index_const(*m)],
+                ),
+            ],
+        );
+
+        let reduce_begin = ShaderIR::new(
+            ShaderIROp::ReduceBegin,
+            ir_type,
+            &[lhs_base_ir.clone(), rhs_base_ir.clone()],
+            Some(ShaderIREvaluation::I32(*k)),
+        );
+        let lhs_index_ir = index_evaluate(
+            ShaderIREvaluation::ADD,
+            &[lhs_base_ir, reduce_begin.clone()],
+        );
+        let rhs_index_ir = index_evaluate(
+            ShaderIREvaluation::ADD,
+            &[
+                rhs_base_ir,
+                index_evaluate(
+                    ShaderIREvaluation::MULTIPLY,
+                    &[reduce_begin.clone(), index_const(*m)],
+                ),
+            ],
+        );
+
+        [
+            vec![
+                VirtualShaderIR::reduce(reduce_begin, ShaderIREvaluation::ADD),
+                VirtualShaderIR::op(ir_type, ShaderIREvaluation::MULTIPLY),
+            ],
+            VirtualShaderIR::view(&lhs_index_ir, &self.lhs),
+            VirtualShaderIR::view(&rhs_index_ir, &self.rhs),
+        ]
+        .concat()
+    }
+}
+
+impl UnrollShaderIR for TakeSpec {
+    fn unroll_ir(&self, index_ir: &ShaderIRIndex, _: ShaderIRType) -> Vec<VirtualShaderIR> {
+        // The index is data, so it is evaluated before the input can be read at it
+        let taken_index_ir =
+            evaluate_virtual_ir(VirtualShaderIR::view(&index_ir.index, &self.indices));
+        VirtualShaderIR::view(&taken_index_ir, &self.input)
+    }
+}
+
+impl UnrollShaderIR for AttentionSpec {
+    fn unroll_ir(&self, index_ir: &ShaderIRIndex, _: ShaderIRType) -> Vec<VirtualShaderIR> {
+        // Fused shaders inline every reduction, so the decomposition is read in place
+        vec![VirtualShaderIR::index(
+            index_ir.index.clone(),
+            self.decomposed(),
+        )]
+    }
+}
+
+fn index_const(value: ViewType) -> ShaderIR {
+    ShaderIR::new(
+        ShaderIROp::Const,
+        ShaderIRType::I32,
+        &[],
+        Some(ShaderIREvaluation::I32(value)),
+    )
+}
+
+fn index_evaluate(op: ShaderIREvaluation, inputs: &[ShaderIR]) -> ShaderIR {
+    ShaderIR::new(ShaderIROp::Evaluate, ShaderIRType::I32, inputs, Some(op))
+}
+
+fn padded_coordinate_ir(
+    coordinate: ShaderIR,
+    shape: ViewType,
+    (padding_pre, padding_post): (ViewType, ViewType),
+    mode: PadMode,
+) -> (ShaderIR, Option<ShaderIR>) {
+    if padding_pre == 0 && padding_post == 0 {
+        return (coordinate, None);
+    }
+
+    // Coordinate within the unpadded axis, which may fall outside of [0, extent)
+    let extent = shape - padding_pre - padding_post;
+    let source = index_evaluate(
+        ShaderIREvaluation::SUB,
+        &[coordinate, index_const(padding_pre)],
+    );
+    let clamped = index_evaluate(
+        ShaderIREvaluation::MIN,
+        &[
+            index_evaluate(ShaderIREvaluation::MAX, &[source.clone(), index_const(0)]),
+            index_const(extent - 1),
+        ],
+    );
+
+    match mode {
+        // Out-of-range reads are clamped to stay in bounds, the predicate discards them
+        PadMode::Constant(_) => {
+            let predicate = index_evaluate(ShaderIREvaluation::EQUAL, &[clamped.clone(), source]);
+            (clamped, Some(predicate))
+        }
+        // (extent - 1) - |(|source| % period) - (extent - 1)|
+        PadMode::Reflect if extent > 1 => {
+            let period = 2 * (extent - 1);
+            let folded = index_evaluate(
+                ShaderIREvaluation::MOD,
+                &[
+                    index_evaluate(ShaderIREvaluation::ABS, &[source]),
+                    index_const(period),
+                ],
+            );
+            let distance = index_evaluate(
+                ShaderIREvaluation::ABS,
+                &[index_evaluate(
+                    ShaderIREvaluation::SUB,
+                    &[folded, index_const(extent - 1)],
+                )],
+            );
+            let reflected = index_evaluate(
+                ShaderIREvaluation::SUB,
+                &[index_const(extent - 1), distance],
+            );
+            (reflected, None)
+        }
+        PadMode::Reflect | PadMode::Edge => (clamped, None),
+        // ((source % extent) + extent) % extent
+        PadMode::Wrap => {
+            let remainder = index_evaluate(ShaderIREvaluation::MOD, &[source, index_const(extent)]);
+            let wrapped = index_evaluate(
+                ShaderIREvaluation::MOD,
+                &[
+                    index_evaluate(ShaderIREvaluation::ADD, &[remainder, index_const(extent)]),
+                    index_const(extent),
+                ],
+            );
+            (wrapped, None)
+        }
+    }
+}
+
+impl SerializeShaderIR for TensorViewTracker {
+    fn shader_ir(&self, index_ir: &ShaderIR) -> ShaderIR {
+        let (index, predicates) = self.predicated_shader_ir(index_ir);
+        assert!(
+            predicates.is_empty(),
+            "Constant padded views must be read with their predicates"
+        );
+        index
+    }
+
+    fn predicated_shader_ir(&self, index_ir: &ShaderIR) -> (ShaderIR, Vec<ShaderIRPredicate>) {
+        self.serialized_history_fifo().iter().fold(
+            (index_ir.clone(), Vec::new()),
+            |(previous_index_ir, mut predicates), view| {
+                let (padding, mode) = match &view.padding {
+                    Some(padding) => (padding.padding.to_vec(), padding.mode),
+                    None => (vec![(0, 0); view.shape.len()], PadMode::Constant(0.0)),
+                };
+
+                let (index, predicate) = view
+                    .shape
                     .iter()
                     .zip(view.stride.iter().zip(view.contiguous_stride().iter()))
+                    .zip(padding)
                     .fold(
-                        zero.clone(),
-                        |partial_index, (&shape, (&stride, &contiguous_stride))| {
-                            let shape_ir = ShaderIR::new(
-                                ShaderIROp::Const,
-                                ShaderIRType::I32,
-                                &[],
-                                Some(ShaderIREvaluation::I32(shape as i32)),
-                            );
-                            let stride_ir = ShaderIR::new(
-                                ShaderIROp::Const,
-                                ShaderIRType::I32,
-                                &[],
-                                Some(ShaderIREvaluation::I32(stride as i32)),
-                            );
-                            let contiguous_stride_ir = ShaderIR::new(
-                                ShaderIROp::Const,
-                                ShaderIRType::I32,
-                                &[],
-                                Some(ShaderIREvaluation::I32(contiguous_stride as i32)),
-                            );
+                        (index_const(0), None),
+                        |(partial_index, partial_predicate),
+                         ((&shape, (&stride, &contiguous_stride)), padding)| {
                             // index / contiguous_stride % shape * stride
-                            let x0 = ShaderIR::new(
-                                ShaderIROp::Evaluate,
-                                ShaderIRType::I32,
-                                &[previous_index_ir.clone(), contiguous_stride_ir],
-                                Some(ShaderIREvaluation::DIVIDE),
+                            let coordinate = index_evaluate(
+                                ShaderIREvaluation::MOD,
+                                &[
+                                    index_evaluate(
+                                        ShaderIREvaluation::DIVIDE,
+                                        &[previous_index_ir.clone(), index_const(contiguous_stride)],
+                                    ),
+                                    index_const(shape),
+                                ],
                             );
-                            let x1 = ShaderIR::new(
-                                ShaderIROp::Evaluate,
-                                ShaderIRType::I32,
-                                &[x0, shape_ir],
-                                Some(ShaderIREvaluation::MOD),
-                            );
-                            let x2 = ShaderIR::new(
-                                ShaderIROp::Evaluate,
-                                ShaderIRType::I32,
-                                &[x1, stride_ir],
-                                Some(ShaderIREvaluation::MULTIPLY),
+                            let (coordinate, predicate) =
+                                padded_coordinate_ir(coordinate, shape, padding, mode);
+                            let offset = index_evaluate(
+                                ShaderIREvaluation::MULTIPLY,
+                                &[coordinate, index_const(stride)],
                             );
 
-                            ShaderIR::new(
-                                ShaderIROp::Evaluate,
-                                ShaderIRType::I32,
-                                &[partial_index, x2],
-                                Some(ShaderIREvaluation::ADD),
-                            )
+                            let index =
+                                index_evaluate(ShaderIREvaluation::ADD, &[partial_index, offset]);
+                            let predicate = match (partial_predicate, predicate) {
+                                (Some(lhs), Some(rhs)) => {
+                                    Some(index_evaluate(ShaderIREvaluation::MULTIPLY, &[lhs, rhs]))
+                                }
+                                (lhs, rhs) => lhs.or(rhs),
+                            };
+                            (index, predicate)
                         },
-                    )
-            })
+                    );
+
+                if let (Some(predicate), PadMode::Constant(fallback)) = (predicate, mode) {
+                    predicates.push(ShaderIRPredicate {
+                        predicate,
+                        fallback,
+                    });
+                }
+                (index, predicates)
+            },
+        )
     }
 }
@@ -1,5 +1,5 @@
-use crate::topograph::{GraphDependencies, UniqueIdentifier};
 use crate::primitives::tensor::*;
+use crate::topograph::{GraphDependencies, UniqueIdentifier};
 
 impl UniqueIdentifier for Tensor {
     type Id = u32;
@@ -22,6 +22,13 @@ impl GraphDependencies for BinarySpec {
     }
 }
 
+impl GraphDependencies for TernarySpec {
+    type Dependency = Tensor;
+    fn dependencies(&self) -> Vec<Self::Dependency> {
+        vec![self.condition.clone(), self.lhs.clone(), self.rhs.clone()]
+    }
+}
+
 impl GraphDependencies for ReduceSpec {
     type Dependency = Tensor;
     fn dependencies(&self) -> Vec<Self::Dependency> {
@@ -29,13 +36,81 @@ impl GraphDependencies for ReduceSpec {
     }
 }
 
+impl GraphDependencies for ScanSpec {
+    type Dependency = Tensor;
+    fn dependencies(&self) -> Vec<Self::Dependency> {
+        vec![self.input.clone()]
+    }
+}
+
+impl GraphDependencies for SortSpec {
+    type Dependency = Tensor;
+    fn dependencies(&self) -> Vec<Self::Dependency> {
+        vec![self.input.clone()]
+    }
+}
+
+impl GraphDependencies for MatMulSpec {
+    type Dependency = Tensor;
+    fn dependencies(&self) -> Vec<Self::Dependency> {
+        vec![self.lhs.clone(), self.rhs.clone()]
+    }
+}
+
+impl GraphDependencies for ConcatSpec {
+    type Dependency = Tensor;
+    fn dependencies(&self) -> Vec<Self::Dependency> {
+        self.inputs.clone()
+    }
+}
+
+impl GraphDependencies for TakeSpec {
+    type Dependency = Tensor;
+    fn dependencies(&self) -> Vec<Self::Dependency> {
+        vec![self.input.clone(), self.indices.clone()]
+    }
+}
+
+impl GraphDependencies for AttentionSpec {
+    type Dependency = Tensor;
+    fn dependencies(&self) -> Vec<Self::Dependency> {
+        [self.query.clone(), self.key.clone(), self.value.clone()]
+            .into_iter()
+            .chain(self.mask.clone())
+            .collect()
+    }
+}
+
+impl GraphDependencies for LUSpec {
+    type Dependency = Tensor;
+    fn dependencies(&self) -> Vec<Self::Dependency> {
+        vec![self.input.clone()]
+    }
+}
+
+impl GraphDependencies for TriangularSolveSpec {
+    type Dependency = Tensor;
+    fn dependencies(&self) -> Vec<Self::Dependency> {
+        vec![self.matrix.clone(), self.rhs.clone()]
+    }
+}
+
 impl GraphDependencies for OperationSpec {
     type Dependency = Tensor;
     fn dependencies(&self) -> Vec<Self::Dependency> {
         match self {
             OperationSpec::UnaryOp(spec) => spec.dependencies(),
             OperationSpec::BinaryOp(spec) => spec.dependencies(),
+            OperationSpec::TernaryOp(spec) => spec.dependencies(),
             OperationSpec::ReduceOp(spec) => spec.dependencies(),
+            OperationSpec::ScanOp(spec) => spec.dependencies(),
+            OperationSpec::SortOp(spec) => spec.dependencies(),
+            OperationSpec::MatMulOp(spec) => spec.dependencies(),
+            OperationSpec::ConcatOp(spec) => spec.dependencies(),
+            OperationSpec::TakeOp(spec) => spec.dependencies(),
+            OperationSpec::AttentionOp(spec) => spec.dependencies(),
+            OperationSpec::LUOp(spec) => spec.dependencies(),
+            OperationSpec::TriangularSolveOp(spec) => spec.dependencies(),
         }
     }
 }
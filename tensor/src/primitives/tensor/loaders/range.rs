@@ -6,11 +6,11 @@ pub trait RangeLoader {
 
 impl RangeLoader for &Tensor {
     fn load<T: bytemuck::Pod>(self, spec: &RangeSpec) -> Vec<T> {
-        let data = (spec.start..spec.end)
-            .step_by(spec.step as usize)
-            .map(TryInto::<i32>::try_into)
-            .map(Result::unwrap)
-            .collect::<Vec<_>>();
+        // Steps may be negative, so elements are counted rather than stepped through
+        let length = (spec.end - spec.start) / spec.step;
+        let data = (0..length)
+            .map(|index| spec.start + index * spec.step)
+            .collect::<Vec<i32>>();
         bytemuck::cast_slice(&data[..]).to_vec()
     }
 }
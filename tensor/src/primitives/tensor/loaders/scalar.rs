@@ -11,7 +11,9 @@ impl ScalarLoader for &Tensor {
                 .to_ne_bytes()
                 .to_vec(),
             TensorType::F32 => value.parse::<f32>().unwrap().to_ne_bytes().to_vec(),
-            TensorType::I32 => value.parse::<i32>().unwrap().to_ne_bytes().to_vec(),
+            TensorType::I32 | TensorType::I8 | TensorType::U8 => {
+                value.parse::<i32>().unwrap().to_ne_bytes().to_vec()
+            }
             TensorType::U32 => value.parse::<u32>().unwrap().to_ne_bytes().to_vec(),
         };
 
@@ -5,9 +5,36 @@ pub use binary::*;
 mod unary;
 pub use unary::*;
 
+mod ternary;
+pub use ternary::*;
+
 mod reduce;
 pub use reduce::*;
 
+mod scan;
+pub use scan::*;
+
+mod sort;
+pub use sort::*;
+
+mod matmul;
+pub use matmul::*;
+
+mod concat;
+pub use concat::*;
+
+mod take;
+pub use take::*;
+
+mod attention;
+pub use attention::*;
+
+mod lu;
+pub use lu::*;
+
+mod triangular;
+pub use triangular::*;
+
 mod view;
 pub use view::*;
 
@@ -31,7 +58,16 @@ pub enum TensorInput {
 pub enum OperationSpec {
     UnaryOp(UnarySpec),
     BinaryOp(BinarySpec),
+    TernaryOp(TernarySpec),
     ReduceOp(ReduceSpec),
+    ScanOp(ScanSpec),
+    SortOp(SortSpec),
+    MatMulOp(MatMulSpec),
+    ConcatOp(ConcatSpec),
+    TakeOp(TakeSpec),
+    AttentionOp(AttentionSpec),
+    LUOp(LUSpec),
+    TriangularSolveOp(TriangularSolveSpec),
 }
 
 impl TensorInput {
@@ -0,0 +1,145 @@
+use crate::primitives::tensor::{Tensor, TensorType};
+use crate::primitives::tensorview::TensorView;
+
+use super::{OperationSpec, TensorInput};
+
+#[derive(Clone, Debug)]
+pub struct AttentionSpec {
+    // [..., L, E]
+    pub query: Tensor,
+    // [..., S, E]
+    pub key: Tensor,
+    // [..., S, Ev]
+    pub value: Tensor,
+    // Additive [..., L, S]
+    pub mask: Option<Tensor>,
+    // Query i only attends to keys j <= i
+    pub causal: bool,
+    pub scale: f32,
+}
+
+impl TensorInput {
+    pub fn attention(
+        query: Tensor,
+        key: Tensor,
+        value: Tensor,
+        mask: Option<Tensor>,
+        causal: bool,
+        scale: f32,
+    ) -> TensorInput {
+        TensorInput::OperationResult(OperationSpec::AttentionOp(AttentionSpec {
+            query,
+            key,
+            value,
+            mask,
+            causal,
+            scale,
+        }))
+    }
+}
+
+impl AttentionSpec {
+    // Scaled and masked query-key scores [..., L, S]
+    pub fn scores(&self) -> Tensor {
+        let ndim = self.key.ndim();
+        let transposed = (0..ndim - 2)
+            .chain([ndim - 1, ndim - 2])
+            .collect::<Vec<_>>();
+        let scores = self
+            .query
+            .MatMul(&self.key.transpose(&transposed))
+            .Multiply(&Tensor::scalar(self.scale));
+
+        let scores = match &self.mask {
+            Some(mask) => scores.Add(mask),
+            None => scores,
+        };
+
+        if self.causal {
+            let [.., l, s] = scores.shape() else {
+                panic!("Attention requires scores to be at least 2D");
+            };
+            let future = Tensor::arange(&[*l, 1]).LessThan(&Tensor::arange(&[1, *s]));
+            future.Where(&Tensor::scalar(f32::NEG_INFINITY), &scores)
+        } else {
+            scores
+        }
+    }
+
+    // Softmax of the scores over the keys [..., L, S]
+    pub fn weights(&self) -> Tensor {
+        let scores = self.scores();
+        scores.Softmax(scores.ndim() - 1)
+    }
+
+    // Materialized equivalent of the fused attention
+    pub fn decomposed(&self) -> Tensor {
+        self.weights().MatMul(&self.value)
+    }
+}
+
+impl Tensor {
+    // Inputs are expected to share batch dimensions, see ScaledDotProductAttention
+    pub fn Attention(
+        &self,
+        key: &Tensor,
+        value: &Tensor,
+        mask: Option<&Tensor>,
+        causal: bool,
+        scale: f32,
+    ) -> Tensor {
+        let [batch_size @ .., l, e] = self.shape() else {
+            panic!("Attention requires query to be at least 2D");
+        };
+        let [key_batch_size @ .., s, key_e] = key.shape() else {
+            panic!("Attention requires key to be at least 2D");
+        };
+        let [value_batch_size @ .., value_s, ev] = value.shape() else {
+            panic!("Attention requires value to be at least 2D");
+        };
+
+        assert!(
+            batch_size == key_batch_size && batch_size == value_batch_size,
+            "Attention expects matching batch dimensions, found {:?}, {:?} and {:?}",
+            self.shape(),
+            key.shape(),
+            value.shape()
+        );
+        assert!(
+            e == key_e && s == value_s,
+            "Failed to attend with query {:?}, key {:?} and value {:?}",
+            self.shape(),
+            key.shape(),
+            value.shape()
+        );
+        assert!(
+            [self, key, value]
+                .into_iter()
+                .chain(mask)
+                .all(|tensor| tensor.datatype() == TensorType::F32),
+            "Attention expects F32 inputs"
+        );
+        if let Some(mask) = mask {
+            assert!(
+                mask.shape() == &[batch_size, &[*l, *s]].concat()[..],
+                "Attention expects a mask of shape {:?}, found {:?}",
+                [batch_size, &[*l, *s]].concat(),
+                mask.shape()
+            );
+        }
+
+        let output_view = TensorView::from_contiguous_shape(&[batch_size, &[*l, *ev]].concat());
+        Tensor::new(
+            output_view,
+            TensorInput::attention(
+                self.clone(),
+                key.clone(),
+                value.clone(),
+                mask.cloned(),
+                causal,
+                scale,
+            ),
+            TensorType::F32,
+        )
+    }
+}
@@ -1,4 +1,4 @@
-use crate::primitives::tensor::Tensor;
+use crate::primitives::tensor::{Tensor, TensorType};
 use crate::primitives::tensorview::TensorView;
 
 use super::{OperationSpec, TensorInput};
@@ -13,6 +13,12 @@ pub enum BinaryType {
     MOD,
     EQUAL,
     LESSTHAN,
+    BITWISEAND,
+    BITWISEOR,
+    BITWISEXOR,
+    SHIFTLEFT,
+    SHIFTRIGHT,
+    ATAN2,
 }
 
 #[derive(Clone, Debug)]
@@ -34,7 +40,31 @@ impl Tensor {
         let lhs = self.broadcast(&rhs).Cast(datatype);
         let rhs = rhs.broadcast(&self).Cast(datatype);
         let output_view = TensorView::from_contiguous_shape(&lhs.shape());
-        Tensor::new(output_view, TensorInput::binary(op, lhs, rhs), datatype)
+        Tensor::new(output_view, TensorInput::binary(op, lhs, rhs), datatype).wrap_narrow_integer()
+    }
+
+    // 8-bit integers are computed in 32 bits, so results are wrapped back into their range
+    pub(crate) fn wrap_narrow_integer(&self) -> Tensor {
+        let low_byte = || self.Cast(TensorType::I32).BitwiseAnd(&Tensor::scalar(0xff));
+        match self.datatype() {
+            TensorType::U8 => low_byte().Cast(TensorType::U8),
+            TensorType::I8 => low_byte()
+                .BitwiseXor(&Tensor::scalar(0x80))
+                .Sub(&Tensor::scalar(0x80))
+                .Cast(TensorType::I8),
+            _ => self.clone(),
+        }
+    }
+
+    fn integer_binary_op(&self, op: BinaryType, rhs: &Tensor) -> Tensor {
+        assert!(
+            self.datatype().is_integer() && self.datatype() == rhs.datatype(),
+            "{:?} requires matching integer inputs, found {:?} and {:?}",
+            op,
+            self.datatype(),
+            rhs.datatype()
+        );
+        self.binary_op(op, rhs)
     }
 
     pub fn Add(&self, rhs: &Tensor) -> Tensor {
@@ -68,4 +98,31 @@ impl Tensor {
     pub fn LessThan(&self, rhs: &Tensor) -> Tensor {
         self.binary_op(BinaryType::LESSTHAN, rhs)
     }
+
+    pub fn BitwiseAnd(&self, rhs: &Tensor) -> Tensor {
+        self.integer_binary_op(BinaryType::BITWISEAND, rhs)
+    }
+
+    pub fn BitwiseOr(&self, rhs: &Tensor) -> Tensor {
+        self.integer_binary_op(BinaryType::BITWISEOR, rhs)
+    }
+
+    pub fn BitwiseXor(&self, rhs: &Tensor) -> Tensor {
+        self.integer_binary_op(BinaryType::BITWISEXOR, rhs)
+    }
+
+    pub fn BitShiftLeft(&self, rhs: &Tensor) -> Tensor {
+        self.integer_binary_op(BinaryType::SHIFTLEFT, rhs)
+    }
+
+    // Signed integers are shifted arithmetically
+    pub fn BitShiftRight(&self, rhs: &Tensor) -> Tensor {
+        self.integer_binary_op(BinaryType::SHIFTRIGHT, rhs)
+    }
+
+    // Angle of the point (x, y) where self is y
+    pub fn Atan2(&self, x: &Tensor) -> Tensor {
+        self.Cast(TensorType::F32)
+            .binary_op(BinaryType::ATAN2, &x.Cast(TensorType::F32))
+    }
 }
@@ -0,0 +1,80 @@
+use crate::primitives::tensor::Tensor;
+use crate::primitives::tensorview::{TensorView, ViewType};
+
+use super::{OperationSpec, TensorInput};
+
+#[derive(Clone, Debug)]
+pub struct ConcatSpec {
+    pub inputs: Vec<Tensor>,
+    pub axis: ViewType,
+}
+
+impl ConcatSpec {
+    // Range [begin, end) along the axis each input occupies in the output
+    pub fn ranges(&self) -> Vec<(ViewType, ViewType)> {
+        self.inputs
+            .iter()
+            .scan(0, |offset, input| {
+                let begin = *offset;
+                *offset += input.shape()[self.axis as usize];
+                Some((begin, *offset))
+            })
+            .collect::<Vec<_>>()
+    }
+}
+
+impl TensorInput {
+    pub fn concat(inputs: Vec<Tensor>, axis: ViewType) -> TensorInput {
+        TensorInput::OperationResult(OperationSpec::ConcatOp(ConcatSpec { inputs, axis }))
+    }
+}
+
+impl Tensor {
+    pub fn Concat(inputs: &[Tensor], axis: ViewType) -> Tensor {
+        assert!(!inputs.is_empty(), "Concat expects at least one input");
+
+        let first = &inputs[0];
+        assert!(
+            axis >= 0 && axis < first.ndim(),
+            "Concat axis {} is out of bounds, 0 <= axis < {}",
+            axis,
+            first.ndim()
+        );
+
+        inputs.iter().for_each(|input| {
+            assert!(
+                input.ndim() == first.ndim()
+                    && input
+                        .shape()
+                        .iter()
+                        .zip(first.shape().iter())
+                        .enumerate()
+                        .all(|(dimension, (&lhs, &rhs))| dimension == axis as usize || lhs == rhs),
+                "Unable to concat `{:?}` <-> `{:?}` along axis {}",
+                input.shape(),
+                first.shape(),
+                axis
+            )
+        });
+
+        let datatype = inputs
+            .iter()
+            .skip(1)
+            .fold(first.datatype(), |datatype, input| {
+                datatype.agreeable_type(input.datatype())
+            });
+        let inputs = inputs
+            .iter()
+            .map(|input| input.Cast(datatype))
+            .collect::<Vec<_>>();
+
+        let mut output_shape = first.shape().to_vec();
+        output_shape[axis as usize] = inputs
+            .iter()
+            .map(|input| input.shape()[axis as usize])
+            .sum();
+        let output_view = TensorView::from_contiguous_shape(&output_shape[..]);
+
+        Tensor::new(output_view, TensorInput::concat(inputs, axis), datatype)
+    }
+}
@@ -3,3 +3,19 @@ pub use mlops::*;
 
 mod hlops;
 pub use hlops::*;
+
+mod masked;
+pub use masked::*;
+
+mod interpolate;
+pub use interpolate::*;
+
+mod recurrent;
+pub use recurrent::*;
+
+mod quantize;
+pub use quantize::*;
+
+mod spectral;
+
+mod einsum;
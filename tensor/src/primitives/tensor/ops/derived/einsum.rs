@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+
+use crate::primitives::tensor::Tensor;
+use crate::primitives::tensorview::{TensorView, ViewType};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum EinsumLabel {
+    // Axes covered by `...`, numbered from the leftmost broadcast axis
+    Ellipsis(usize),
+    Named(char),
+}
+
+// Labels of a single term with `...` left unexpanded as `None`
+fn parse_term(term: &str, equation: &str) -> Vec<Option<char>> {
+    assert!(
+        term.matches("...").count() <= 1,
+        "Einsum term `{}` in `{}` has more than one ellipsis",
+        term,
+        equation
+    );
+
+    let (before, after) = term.split_once("...").unwrap_or((term, ""));
+    let ellipsis = term.contains("...");
+
+    let label = |label: char| {
+        assert!(
+            label.is_ascii_alphabetic(),
+            "Unexpected label `{}` in einsum equation `{}`",
+            label,
+            equation
+        );
+        Some(label)
+    };
+
+    before
+        .chars()
+        .map(label)
+        .chain(ellipsis.then_some(None))
+        .chain(after.chars().map(label))
+        .collect::<Vec<_>>()
+}
+
+fn expand_term(
+    term: &[Option<char>],
+    ellipsis_rank: usize,
+    broadcast_rank: usize,
+) -> Vec<EinsumLabel> {
+    // Ellipsis axes are right aligned against those of the other operands
+    term.iter()
+        .flat_map(|label| match label {
+            Some(label) => vec![EinsumLabel::Named(*label)],
+            None => (broadcast_rank - ellipsis_rank..broadcast_rank)
+                .map(EinsumLabel::Ellipsis)
+                .collect::<Vec<_>>(),
+        })
+        .collect::<Vec<_>>()
+}
+
+impl Tensor {
+    pub fn Einsum(equation: &str, operands: &[Tensor]) -> Tensor {
+        let equation = equation.replace(char::is_whitespace, "");
+        let (inputs, output) = match equation.split_once("->") {
+            Some((inputs, output)) => (inputs, Some(output)),
+            None => (&equation[..], None),
+        };
+
+        let terms = inputs
+            .split(',')
+            .map(|term| parse_term(term, &equation))
+            .collect::<Vec<_>>();
+        assert!(
+            terms.len() == operands.len(),
+            "Einsum equation `{}` expects {} operands, got {}",
+            equation,
+            terms.len(),
+            operands.len()
+        );
+
+        let ellipsis_ranks = terms
+            .iter()
+            .zip(operands.iter())
+            .map(|(term, operand)| {
+                let named_labels = term.iter().filter(|label| label.is_some()).count();
+                let has_ellipsis = named_labels != term.len();
+                assert!(
+                    operand.ndim() as usize == named_labels
+                        || (has_ellipsis && operand.ndim() as usize > named_labels),
+                    "Einsum term `{}` does not match operand of shape {:?}",
+                    term.iter()
+                        .map(|label| label.unwrap_or('.'))
+                        .collect::<String>(),
+                    operand.shape()
+                );
+                operand.ndim() as usize - named_labels
+            })
+            .collect::<Vec<_>>();
+        let broadcast_rank = ellipsis_ranks.iter().copied().max().unwrap_or(0);
+
+        let operands = terms
+            .iter()
+            .zip(ellipsis_ranks.iter())
+            .zip(operands.iter())
+            .map(|((term, &ellipsis_rank), operand)| {
+                (
+                    operand.clone(),
+                    expand_term(term, ellipsis_rank, broadcast_rank),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // Named labels are summed out unless they appear exactly once, broadcast axes are kept
+        let output_labels = match output {
+            Some(output) => expand_term(
+                &parse_term(output, &equation),
+                broadcast_rank,
+                broadcast_rank,
+            ),
+            None => {
+                let mut counts = HashMap::new();
+                operands
+                    .iter()
+                    .flat_map(|(_, labels)| labels)
+                    .for_each(|label| {
+                        *counts.entry(*label).or_insert(0) += 1;
+                    });
+
+                let mut named_labels = counts
+                    .iter()
+                    .filter_map(|(label, &count)| match label {
+                        EinsumLabel::Named(label) if count == 1 => Some(*label),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                named_labels.sort();
+
+                (0..broadcast_rank)
+                    .map(EinsumLabel::Ellipsis)
+                    .chain(named_labels.into_iter().map(EinsumLabel::Named))
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        // Each named label has a single extent, broadcast axes may also be 1
+        let mut extents = HashMap::new();
+        operands.iter().for_each(|(operand, labels)| {
+            labels
+                .iter()
+                .zip(operand.shape().iter())
+                .for_each(|(label, &extent)| {
+                    let known_extent = extents.entry(*label).or_insert(extent);
+                    match label {
+                        EinsumLabel::Ellipsis(_) if *known_extent == 1 => *known_extent = extent,
+                        EinsumLabel::Ellipsis(_) if extent == 1 => {}
+                        _ => assert!(
+                            *known_extent == extent,
+                            "Einsum label {:?} has mismatching extents {} and {} in `{}`",
+                            label,
+                            known_extent,
+                            extent,
+                            equation
+                        ),
+                    }
+                })
+        });
+        output_labels.iter().enumerate().for_each(|(index, label)| {
+            assert!(
+                extents.contains_key(label) && !output_labels[..index].contains(label),
+                "Einsum output label {:?} in `{}` must appear once and in an input",
+                label,
+                equation
+            )
+        });
+
+        let mut operands = operands
+            .into_iter()
+            .map(|(operand, labels)| Tensor::einsum_diagonal(&operand, &labels))
+            .collect::<Vec<_>>();
+
+        // Contract operands pairwise from the left, keeping labels that are still needed
+        let (first_operand, first_labels) = operands.remove(0);
+        let (result, labels) =
+            (0..operands.len()).fold((first_operand, first_labels), |(lhs, lhs_labels), index| {
+                let (rhs, rhs_labels) = &operands[index];
+                let kept_labels = operands[index + 1..]
+                    .iter()
+                    .flat_map(|(_, labels)| labels.iter())
+                    .chain(output_labels.iter())
+                    .copied()
+                    .collect::<Vec<_>>();
+
+                Tensor::einsum_contract(
+                    (lhs, lhs_labels),
+                    (rhs.clone(), rhs_labels.clone()),
+                    &kept_labels,
+                )
+            });
+
+        let (result, labels) = Tensor::einsum_sum(&result, &labels, &output_labels);
+        let permutation = output_labels
+            .iter()
+            .map(|label| labels.iter().position(|other| other == label).unwrap() as ViewType)
+            .collect::<Vec<_>>();
+        result.transpose(&permutation)
+    }
+
+    // Repeated labels within an operand select its diagonal
+    fn einsum_diagonal(&self, labels: &[EinsumLabel]) -> (Tensor, Vec<EinsumLabel>) {
+        let contiguous_stride = TensorView::from_contiguous_shape(self.shape()).contiguous_stride();
+
+        let mut unique_labels: Vec<EinsumLabel> = vec![];
+        let mut shape = vec![];
+        let mut stride = vec![];
+        labels.iter().enumerate().for_each(|(axis, label)| {
+            match unique_labels.iter().position(|other| other == label) {
+                Some(position) => stride[position] += contiguous_stride[axis],
+                None => {
+                    unique_labels.push(*label);
+                    shape.push(self.shape()[axis]);
+                    stride.push(contiguous_stride[axis]);
+                }
+            }
+        });
+
+        if unique_labels.len() == labels.len() {
+            (self.clone(), unique_labels)
+        } else {
+            (self.as_strided(&shape[..], &stride[..]), unique_labels)
+        }
+    }
+
+    // Sum out every label which is not kept
+    fn einsum_sum(
+        &self,
+        labels: &[EinsumLabel],
+        kept_labels: &[EinsumLabel],
+    ) -> (Tensor, Vec<EinsumLabel>) {
+        let axes = labels
+            .iter()
+            .enumerate()
+            .filter(|(_, label)| !kept_labels.contains(label))
+            .map(|(axis, _)| axis as ViewType)
+            .collect::<Vec<_>>();
+        let labels = labels
+            .iter()
+            .filter(|label| kept_labels.contains(label))
+            .copied()
+            .collect::<Vec<_>>();
+
+        if axes.is_empty() {
+            (self.clone(), labels)
+        } else {
+            (self.Sum(&axes[..], false), labels)
+        }
+    }
+
+    // Lowers a pairwise contraction onto a batched MatMul of
+    // [batch..., lhs..., contracted...] @ [batch..., contracted..., rhs...]
+    fn einsum_contract(
+        (lhs, lhs_labels): (Tensor, Vec<EinsumLabel>),
+        (rhs, rhs_labels): (Tensor, Vec<EinsumLabel>),
+        kept_labels: &[EinsumLabel],
+    ) -> (Tensor, Vec<EinsumLabel>) {
+        // Labels only present in a single operand are summed out beforehand
+        let lhs_kept_labels = [kept_labels, &rhs_labels[..]].concat();
+        let (lhs, lhs_labels) = lhs.einsum_sum(&lhs_labels, &lhs_kept_labels);
+        let rhs_kept_labels = [kept_labels, &lhs_labels[..]].concat();
+        let (rhs, rhs_labels) = rhs.einsum_sum(&rhs_labels, &rhs_kept_labels);
+
+        let batch_labels = lhs_labels
+            .iter()
+            .filter(|label| rhs_labels.contains(label) && kept_labels.contains(label))
+            .copied()
+            .collect::<Vec<_>>();
+        let contracted_labels = lhs_labels
+            .iter()
+            .filter(|label| rhs_labels.contains(label) && !kept_labels.contains(label))
+            .copied()
+            .collect::<Vec<_>>();
+        let lhs_free_labels = lhs_labels
+            .iter()
+            .filter(|label| !rhs_labels.contains(label))
+            .copied()
+            .collect::<Vec<_>>();
+        let rhs_free_labels = rhs_labels
+            .iter()
+            .filter(|label| !lhs_labels.contains(label))
+            .copied()
+            .collect::<Vec<_>>();
+
+        let arrange = |tensor: &Tensor, labels: &[EinsumLabel], order: &[&[EinsumLabel]]| {
+            let permutation = order
+                .concat()
+                .iter()
+                .map(|label| labels.iter().position(|other| other == label).unwrap() as ViewType)
+                .collect::<Vec<_>>();
+            tensor.transpose(&permutation)
+        };
+        let extent = |tensor: &Tensor, labels: &[EinsumLabel], selected: &[EinsumLabel]| {
+            selected
+                .iter()
+                .map(|label| {
+                    tensor.shape()[labels.iter().position(|other| other == label).unwrap()]
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let lhs_batch_shape = extent(&lhs, &lhs_labels, &batch_labels);
+        let rhs_batch_shape = extent(&rhs, &rhs_labels, &batch_labels);
+        let lhs_free_shape = extent(&lhs, &lhs_labels, &lhs_free_labels);
+        let rhs_free_shape = extent(&rhs, &rhs_labels, &rhs_free_labels);
+        let contracted_length = extent(&lhs, &lhs_labels, &contracted_labels)
+            .iter()
+            .product::<ViewType>();
+
+        let lhs = arrange(
+            &lhs,
+            &lhs_labels,
+            &[&batch_labels, &lhs_free_labels, &contracted_labels],
+        )
+        .view_as(
+            &[
+                &lhs_batch_shape[..],
+                &[lhs_free_shape.iter().product(), contracted_length],
+            ]
+            .concat(),
+        );
+        let rhs = arrange(
+            &rhs,
+            &rhs_labels,
+            &[&batch_labels, &contracted_labels, &rhs_free_labels],
+        )
+        .view_as(
+            &[
+                &rhs_batch_shape[..],
+                &[contracted_length, rhs_free_shape.iter().product()],
+            ]
+            .concat(),
+        );
+
+        let result = lhs.MatMul(&rhs);
+        let batch_shape = &result.shape()[..batch_labels.len()];
+        let result =
+            result.view_as(&[batch_shape, &lhs_free_shape[..], &rhs_free_shape[..]].concat());
+
+        (
+            result,
+            [batch_labels, lhs_free_labels, rhs_free_labels].concat(),
+        )
+    }
+}
@@ -1,5 +1,5 @@
-use crate::primitives::tensor::Tensor;
-use crate::primitives::tensorview::{TensorView, ViewType};
+use crate::primitives::tensor::{Tensor, TensorType};
+use crate::primitives::tensorview::{PadMode, TensorView, ViewType};
 
 #[derive(Clone, Copy, Debug)]
 pub enum ConvPadding<'a> {
@@ -10,6 +10,56 @@ pub enum ConvPadding<'a> {
     Custom(&'a [(ViewType, ViewType)]),
 }
 
+impl<'a> ConvPadding<'a> {
+    fn axis_padding(
+        &self,
+        shape: &[ViewType],
+        filters: &[ViewType],
+        kernel_strides: &[ViewType],
+    ) -> Vec<(ViewType, ViewType)> {
+        // Only the trailing feature dimensions are padded
+        let (unpadded_dimensions, features) = shape.split_at(shape.len() - filters.len());
+
+        match self {
+            ConvPadding::Valid => shape.iter().map(|_| (0, 0)).collect::<Vec<_>>(),
+            ConvPadding::Same | ConvPadding::SameLower | ConvPadding::SameUpper => {
+                kernel_strides.iter().for_each(|&stride| {
+                    assert!(stride == 1, "Same padding expects strides of only 1")
+                });
+
+                // P = ((S-1)*W-S+F)/2, with F = filter size, S = stride, W = input size
+                // https://stats.stackexchange.com/questions/297678/how-to-calculate-optimal-zero-padding-for-convolutional-neural-networks
+                let padded_dimensions = features
+                    .iter()
+                    .zip(kernel_strides.iter().zip(filters.iter()))
+                    .map(|(&feature, (&stride, &filter))| {
+                        let padding_needed = (stride - 1) * feature - stride + filter;
+                        let padding_extra = (padding_needed / 2 + 1).min(padding_needed);
+                        let padding_leftover = padding_needed - padding_extra;
+
+                        match self {
+                            ConvPadding::SameLower => (padding_extra, padding_leftover),
+                            ConvPadding::Same | ConvPadding::SameUpper => {
+                                (padding_leftover, padding_extra)
+                            }
+                            _ => unreachable!(),
+                        }
+                    });
+
+                unpadded_dimensions
+                    .iter()
+                    .map(|_| (0, 0))
+                    .chain(padded_dimensions)
+                    .collect::<Vec<_>>()
+            }
+            ConvPadding::Custom(explicit_padding) => explicit_padding.to_vec(),
+        }
+    }
+}
+
+// Padding before and after every dimension
+type AxisPadding = Vec<(ViewType, ViewType)>;
+
 #[derive(Clone, Copy, Debug)]
 pub enum ScatterReduction {
     None,
@@ -20,54 +70,110 @@ pub enum ScatterReduction {
 }
 
 impl Tensor {
-    pub fn ArgMax(&self, axis: ViewType, keep_dims: bool, select_last_index: bool) -> Tensor {
-        let max_along_axis = self.Max(&[axis], true);
-        let mask = self.Equal(&max_along_axis);
-
-        let (_, shape_per_batch) = self.shape().split_at(axis as usize);
-        let axis_size = self.shape()[axis as usize];
-        let elements_per_batch = shape_per_batch[1..]
-            .iter()
-            .fold(1, |acc, &shape| acc * shape);
-        let indices = Tensor::arange(&self.shape())
-            .Divide(&Tensor::scalar(elements_per_batch))
-            .Mod(&Tensor::scalar(axis_size))
-            .Floor();
-
-        let matched_indices = mask.Multiply(&indices);
-        if select_last_index {
-            matched_indices.Max(&[axis], keep_dims)
-        } else {
-            let batch_normalizer = Tensor::scalar(axis_size);
-            let masked_normalization = mask.Multiply(&batch_normalizer);
-            let adjusted_indices = masked_normalization.Sub(&matched_indices);
-            batch_normalizer.Sub(&adjusted_indices.Max(&[axis], keep_dims))
-        }
-    }
-
-    pub fn ArgMin(&self, axis: ViewType, keep_dims: bool, select_last_index: bool) -> Tensor {
-        self.Neg().ArgMax(axis, keep_dims, select_last_index)
-    }
-
     pub fn AvgPool<'a>(
         &self,
         kernel_size: &[ViewType],
         kernel_strides: &[ViewType],
+        dilations: &[ViewType],
+        ceil_mode: bool,
+        count_include_pad: bool,
         padding: ConvPadding<'a>,
     ) -> Tensor {
-        let kernel = Tensor::scalar(1).reshape(&TensorView::from_contiguous_shape(kernel_size));
-        let n = Tensor::scalar(kernel.len());
-        self.Conv(&kernel, kernel_strides, padding).Divide(&n)
+        let (explicit_padding, axis_padding) =
+            self.pool_padding(kernel_size, kernel_strides, dilations, ceil_mode, padding);
+        let (windows, window_axes) =
+            self.pool_windows(kernel_size, kernel_strides, dilations, &axis_padding[..]);
+
+        // Padding added by ceil_mode is never counted
+        let counted_padding = match count_include_pad {
+            true => explicit_padding,
+            false => self.shape().iter().map(|_| (0, 0)).collect::<Vec<_>>(),
+        };
+        let counted = self.pool_mask(
+            kernel_size,
+            kernel_strides,
+            dilations,
+            &counted_padding[..],
+            &axis_padding[..],
+        );
+
+        windows
+            .Sum(&window_axes[..], false)
+            .Divide(&counted.Sum(&window_axes[..], false))
+    }
+
+    // Normalizes every channel of N x C x ... with the given statistics of shape [C]
+    pub fn BatchNormalization(&self, mean: &Tensor, variance: &Tensor, epsilon: &Tensor) -> Tensor {
+        let channel_shape = self.channel_shape();
+        let mean = mean.view_as(&channel_shape[..]);
+        let standard_deviation = variance.view_as(&channel_shape[..]).Add(epsilon).Sqrt();
+        self.Sub(&mean).Divide(&standard_deviation)
+    }
+
+    // Normalizes with the statistics of the batch, which are folded into the running statistics
+    pub fn BatchNormalizationTraining(
+        &self,
+        running_mean: &Tensor,
+        running_variance: &Tensor,
+        momentum: &Tensor,
+        epsilon: &Tensor,
+    ) -> (Tensor, Tensor, Tensor) {
+        let batch_axes = (0..self.ndim())
+            .filter(|&axis| axis != 1)
+            .collect::<Vec<_>>();
+        let (mean, variance) = self.moments(&batch_axes[..]);
+        let output = self.Sub(&mean).Divide(&variance.Add(epsilon).Sqrt());
+
+        let one = Tensor::scalar(1.0);
+        let batch_momentum = one.Sub(momentum);
+        let running_mean = running_mean
+            .Multiply(momentum)
+            .Add(&mean.view_as(&[-1]).Multiply(&batch_momentum));
+        let running_variance = running_variance
+            .Multiply(momentum)
+            .Add(&variance.view_as(&[-1]).Multiply(&batch_momentum));
+        (output, running_mean, running_variance)
     }
 
     pub fn Clip(&self, min: &Tensor, max: &Tensor) -> Tensor {
-        self.Minimum(min).Maximum(max)
+        let lower_bounded = self.LessThan(min).Where(min, self);
+        max.LessThan(&lower_bounded).Where(max, &lower_bounded)
+    }
+
+    // A = LL^T for symmetric positive definite A, where the LU factors without pivoting are
+    // U = DL^T so the columns of L are scaled by sqrt(D)
+    pub fn Cholesky(&self, upper: bool) -> Tensor {
+        let ndim = self.ndim();
+        let n = self.shape()[ndim as usize - 1];
+        let transposed = (0..ndim - 2)
+            .chain([ndim - 1, ndim - 2])
+            .collect::<Vec<_>>();
+
+        let (factors, _) = self.LU(false);
+        let rows = Tensor::arange(&[n, 1]);
+        let columns = Tensor::arange(&[1, n]);
+        let diagonal = rows.Equal(&columns);
+        let pivots = diagonal
+            .Where(&factors, &Tensor::scalar(0.0))
+            .Sum(&[ndim - 2], true)
+            .Sqrt();
+        let lower = columns
+            .LessThan(&rows)
+            .Where(&factors, &diagonal.Cast(TensorType::F32))
+            .Multiply(&pivots);
+
+        match upper {
+            true => lower.transpose(&transposed),
+            false => lower,
+        }
     }
 
     pub fn Convolve<'a>(
         &self,
         kernel: &Tensor,
         kernel_strides: &[ViewType],
+        dilations: &[ViewType],
+        group: ViewType,
         padding: ConvPadding<'a>,
         reduction: impl Fn(&Tensor, &[ViewType], bool) -> Tensor,
     ) -> Tensor {
@@ -77,8 +183,8 @@ impl Tensor {
         let [input_batch_size, c_in, features @ ..] = &input.shape() else {
             panic!("Conv expects an input signature of N x C x ...")
         };
-        let [_, in_channels, filters @ ..] = &kernel.shape() else {
-            panic!("Conv expects an kernel signature of C_out x C_in x ...")
+        let [c_out, in_channels, filters @ ..] = &kernel.shape() else {
+            panic!("Conv expects an kernel signature of C_out x C_in/group x ...")
         };
 
         assert!(
@@ -96,114 +202,80 @@ impl Tensor {
         );
 
         assert!(
-            c_in == in_channels,
-            "Input channels in ({}) should equate kernel input channels ({})",
-            c_in,
-            in_channels
+            dilations.len() == features.len(),
+            "A dilation should be defined ({} defined) for all dimensions of the kernel ({})",
+            dilations.len(),
+            features.len(),
         );
 
-        // Compute necessary padding for convolution
-        let axis_padding = match padding {
-            ConvPadding::Valid => self.shape().iter().map(|_| (0, 0)).collect::<Vec<_>>(),
-            ConvPadding::Same | ConvPadding::SameLower | ConvPadding::SameUpper => {
-                kernel_strides.iter().for_each(|&stride| {
-                    assert!(stride == 1, "Same padding expects strides of only 1")
-                });
-
-                let unpadded_dimensions = std::iter::once(input_batch_size)
-                    .chain(std::iter::once(c_in))
-                    .map(|_| (0, 0));
-
-                // P = ((S-1)*W-S+F)/2, with F = filter size, S = stride, W = input size
-                // https://stats.stackexchange.com/questions/297678/how-to-calculate-optimal-zero-padding-for-convolutional-neural-networks
-                let padded_dimensions = features
-                    .iter()
-                    .zip(kernel_strides.iter().zip(filters.iter()))
-                    .map(|(&feature, (&stride, &filter))| {
-                        let padding_needed = (stride - 1) * feature - stride + filter;
-                        let padding_extra = (padding_needed / 2 + 1).min(padding_needed);
-                        let padding_leftover = padding_needed - padding_extra;
-
-                        match padding {
-                            ConvPadding::SameLower => (padding_extra, padding_leftover),
-                            ConvPadding::Same | ConvPadding::SameUpper => {
-                                (padding_leftover, padding_extra)
-                            }
-                            _ => unreachable!(),
-                        }
-                    });
+        assert!(
+            group > 0 && c_in % group == 0 && c_out % group == 0,
+            "Input channels ({}) and output channels ({}) should be divisible by group ({})",
+            c_in,
+            c_out,
+            group
+        );
 
-                unpadded_dimensions
-                    .chain(padded_dimensions)
-                    .collect::<Vec<_>>()
-            }
-            ConvPadding::Custom(explicit_padding) => explicit_padding.to_vec(),
-        };
+        assert!(
+            c_in / group == *in_channels,
+            "Input channels in ({}) should equate kernel input channels ({}) times group ({})",
+            c_in,
+            in_channels,
+            group
+        );
 
         // Perform convolution after padding is done
-        let input = input.Pad(&axis_padding);
-
-        let [input_batch_size, c_in, features @ ..] = &input.shape() else {
-            panic!("Conv expects an input signature of N x C x ...")
-        };
-        let [batch_stride, c_in_stride, features_stride @ ..] = &input.view().stride[..] else {
-            panic!("Conv expects an input signature of N x C x ...")
-        };
-
-        // X_out = (X_in + 2*padding - dilation*(kernel_size-1) - 1 / stride).floor()
-        // h_in is padded here now
-        let output_dimensions = features
+        // A dilated kernel spans d*(k-1)+1 elements
+        let extents = filters
             .iter()
-            .zip(filters.iter().zip(kernel_strides.iter()))
-            .map(|(input, (filter, stride))| (input - 1 * (filter - 1) - 1) / stride + 1)
+            .zip(dilations.iter())
+            .map(|(&filter, &dilation)| dilation * (filter - 1) + 1)
             .collect::<Vec<_>>();
+        let axis_padding = padding.axis_padding(input.shape(), &extents[..], kernel_strides);
+        let input = input.Pad(&axis_padding);
+
+        // Split channels into their groups (n, group, c_in/group, features...)
+        let input = input.view_as(
+            &[*input_batch_size, group, c_in / group]
+                .into_iter()
+                .chain(input.shape()[2..].iter().copied())
+                .collect::<Vec<_>>()[..],
+        );
 
-        // (n, c_out, c_in, output_size..., kernel_size...)
+        // (n, group, c_out/group, c_in/group, output_size..., kernel_size...)
         // n batch size of
-        // c_out (1 broadcasted) output channels which are the
-        // sum of c_in input channels which are of size
+        // group independent convolutions of
+        // c_out/group (1 broadcasted) output channels which are the
+        // sum of c_in/group input channels which are of size
         // (output_size...) computed using
         // (kernel_size) convolved kernels
         //
-        // (n, 1, c_in, h_out, w_out, k_h, k_w)
-        // Simplier explanation of (h_out, w_out, k_h, k_w), since it is known
-        // the output size is (h_out, w_out), use some stride_tricks to get the
-        // (k_h, k_w) window for each element that is convolved with the kernel
-        let input_shape_trick = [*input_batch_size, 1, *c_in]
-            .iter()
-            .chain(output_dimensions.iter())
-            .chain(filters.iter())
-            .map(|&x| x)
-            .collect::<Vec<_>>();
-        let input_stride_trick = [*batch_stride, 0, *c_in_stride]
-            .into_iter()
-            .chain(
-                features_stride
-                    .iter()
-                    .zip(kernel_strides.iter())
-                    .map(|(&feature_stride, &filter_stride)| feature_stride * filter_stride),
-            )
-            .chain(features_stride.iter().map(|&x| x))
-            .collect::<Vec<_>>();
-        let input = input.reshape(&TensorView::new(
-            false,
-            input_shape_trick.into_boxed_slice(),
-            input_stride_trick.into_boxed_slice(),
-        ));
+        // (n, group, 1, c_in/group, h_out, w_out, k_h, k_w)
+        // Each (k_h, k_w) window is a view into the input, so nothing is copied
+        let windows = input.Unfold(filters, kernel_strides, dilations);
+        let input = windows.view_as(
+            &[*input_batch_size, group, 1]
+                .into_iter()
+                .chain(windows.shape()[2..].iter().copied())
+                .collect::<Vec<_>>()[..],
+        );
+        let output_size = windows.shape()[3..3 + features.len()].to_vec();
 
         // Expand kernel to expected shape
-        // [1, c_out, c_in, output_size_broadcastable..., kernel_size...]
-        let mut kernel = kernel.unsqueeze(0);
-        for _ in features {
-            kernel = kernel.unsqueeze(3);
-        }
-        let kernel = kernel;
+        // [1, group, c_out/group, c_in/group, output_size_broadcastable..., kernel_size...]
+        let kernel = kernel.view_as(
+            &[1, group, c_out / group, *in_channels]
+                .into_iter()
+                .chain(features.iter().map(|_| 1))
+                .chain(filters.iter().copied())
+                .collect::<Vec<_>>()[..],
+        );
 
         let convolver = input.Multiply(&kernel);
         let convolver_dimensions = convolver.ndim();
-        // 2nd dimension is [n, c_out, c_in, ...]
-        // Reduce the filter dimensions as well to make it [n, c_out, output_size]
-        let reduce_dimensions = [2]
+        // 3rd dimension is [n, group, c_out/group, c_in/group, ...]
+        // Reduce the filter dimensions as well to make it [n, group, c_out/group, output_size]
+        let reduce_dimensions = [3]
             .into_iter()
             .chain(
                 filters
@@ -213,16 +285,177 @@ impl Tensor {
             )
             .collect::<Vec<_>>();
 
-        reduction(&convolver, &reduce_dimensions[..], false)
+        reduction(&convolver, &reduce_dimensions[..], false).view_as(
+            &[*input_batch_size, *c_out]
+                .into_iter()
+                .chain(output_size)
+                .collect::<Vec<_>>()[..],
+        )
     }
 
     pub fn Conv<'a>(
         &self,
         kernel: &Tensor,
         kernel_strides: &[ViewType],
+        dilations: &[ViewType],
+        group: ViewType,
         padding: ConvPadding<'a>,
     ) -> Tensor {
-        self.Convolve(kernel, kernel_strides, padding, Tensor::Sum)
+        self.Convolve(
+            kernel,
+            kernel_strides,
+            dilations,
+            group,
+            padding,
+            Tensor::Sum,
+        )
+    }
+
+    // Kernel is C_in x C_out/group x ..., padding and output_padding are given per feature dimension
+    pub fn ConvTranspose(
+        &self,
+        kernel: &Tensor,
+        kernel_strides: &[ViewType],
+        dilations: &[ViewType],
+        group: ViewType,
+        padding: &[(ViewType, ViewType)],
+        output_padding: &[ViewType],
+    ) -> Tensor {
+        let [_, c_in, features @ ..] = &self.shape() else {
+            panic!("ConvTranspose expects an input signature of N x C x ...")
+        };
+        let [in_channels, out_channels, filters @ ..] = &kernel.shape() else {
+            panic!("ConvTranspose expects an kernel signature of C_in x C_out/group x ...")
+        };
+
+        assert!(
+            features.len() == filters.len()
+                && kernel_strides.len() == filters.len()
+                && dilations.len() == filters.len()
+                && padding.len() == filters.len()
+                && output_padding.len() == filters.len(),
+            "Strides, dilations, padding and output padding should be defined for all dimensions of the kernel ({})",
+            filters.len()
+        );
+
+        assert!(
+            group > 0 && c_in == in_channels && c_in % group == 0,
+            "Input channels ({}) should equate kernel input channels ({}) and be divisible by group ({})",
+            c_in,
+            in_channels,
+            group
+        );
+
+        // Insert stride-1 zeros between the input elements of every feature dimension
+        let mut input = self.contiguous();
+        for (axis, (&feature, &stride)) in features.iter().zip(kernel_strides.iter()).enumerate() {
+            if stride == 1 {
+                continue;
+            }
+
+            let axis = axis + 2;
+            let spaced = input.unsqueeze(axis as ViewType + 1);
+            let spacing = (0..spaced.ndim())
+                .map(|i| {
+                    if i as usize == axis + 1 {
+                        (0, stride - 1)
+                    } else {
+                        (0, 0)
+                    }
+                })
+                .collect::<Vec<_>>();
+            let spaced = spaced.Pad(&spacing[..]);
+
+            let mut shape = input.shape().to_vec();
+            shape[axis] = feature * stride;
+            input = spaced.view_as(&shape[..]);
+        }
+
+        // Fully pad the spaced input and crop it by the requested padding, the trailing
+        // stride-1 zeros are cropped as well unless kept by the output padding
+        let axis_padding = [(0, 0), (0, 0)]
+            .into_iter()
+            .chain(
+                filters
+                    .iter()
+                    .zip(dilations.iter())
+                    .zip(kernel_strides.iter())
+                    .zip(padding.iter().zip(output_padding.iter()))
+                    .map(
+                        |(((&filter, &dilation), &stride), (&(begin, end), &extra))| {
+                            let full = dilation * (filter - 1);
+                            (full - begin, full - end + extra - (stride - 1))
+                        },
+                    ),
+            )
+            .collect::<Vec<_>>();
+        let input = input.Pad(&axis_padding[..]);
+
+        // Swap the channels of every group and flip the kernel
+        // [group, c_in/group, c_out/group, ...] -> [c_out, c_in/group, ...]
+        let mut kernel = kernel
+            .view_as(
+                &[group, c_in / group, *out_channels]
+                    .into_iter()
+                    .chain(filters.iter().copied())
+                    .collect::<Vec<_>>()[..],
+            )
+            .transpose(
+                &[0, 2, 1]
+                    .into_iter()
+                    .chain(3..filters.len() as ViewType + 3)
+                    .collect::<Vec<_>>()[..],
+            )
+            .contiguous()
+            .view_as(
+                &[group * out_channels, c_in / group]
+                    .into_iter()
+                    .chain(filters.iter().copied())
+                    .collect::<Vec<_>>()[..],
+            );
+        for (axis, &filter) in filters.iter().enumerate() {
+            kernel = kernel.Gather(
+                axis as ViewType + 2,
+                &Tensor::range(&[filter], filter - 1, -1),
+            );
+        }
+
+        let unit_strides = filters.iter().map(|_| 1).collect::<Vec<_>>();
+        input.Conv(
+            &kernel,
+            &unit_strides[..],
+            dilations,
+            group,
+            ConvPadding::Valid,
+        )
+    }
+
+    // Product of the pivots, negated for an odd number of row exchanges
+    pub fn Det(&self) -> Tensor {
+        let ndim = self.ndim();
+        let n = self.shape()[ndim as usize - 1];
+
+        let (factors, permutation) = self.LU(true);
+        let rows = Tensor::arange(&[n, 1]);
+        let columns = Tensor::arange(&[1, n]);
+        let pivots = rows
+            .Equal(&columns)
+            .Where(&factors, &Tensor::scalar(1.0))
+            .Prod(&[ndim - 2, ndim - 1], false);
+
+        // Parity of the permutation is the parity of its inversions
+        let inversions = permutation
+            .unsqueeze(ndim - 1)
+            .Greater(&permutation.unsqueeze(ndim - 2))
+            .And(&rows.LessThan(&columns))
+            .Cast(TensorType::I32)
+            .Sum(&[ndim - 2, ndim - 1], false);
+        let sign = inversions
+            .Mod(&Tensor::scalar(2))
+            .Equal(&Tensor::scalar(0))
+            .Where(&Tensor::scalar(1.0), &Tensor::scalar(-1.0));
+
+        pivots.Multiply(&sign)
     }
 
     pub fn InstanceNormalization(&self, epsilon: &Tensor) -> Tensor {
@@ -242,6 +475,32 @@ impl Tensor {
         self.Normalization(&feature_axes[..], epsilon)
     }
 
+    pub fn Inverse(&self) -> Tensor {
+        let n = self.shape()[self.ndim() as usize - 1];
+        let identity = Tensor::arange(&[n, 1])
+            .Equal(&Tensor::arange(&[1, n]))
+            .Cast(TensorType::F32);
+
+        self.Solve(&identity)
+    }
+
+    pub fn GlobalAvgPool(&self) -> Tensor {
+        self.Mean(&self.feature_axes()[..], true)
+    }
+
+    pub fn GlobalLpPool(&self, p: i32) -> Tensor {
+        let p = Tensor::scalar(p as f32);
+        let one = Tensor::scalar(1.0);
+        self.Abs()
+            .Pow(&p)
+            .Sum(&self.feature_axes()[..], true)
+            .Pow(&one.Divide(&p))
+    }
+
+    pub fn GlobalMaxPool(&self) -> Tensor {
+        self.Max(&self.feature_axes()[..], true)
+    }
+
     pub fn Gather(&self, axis: ViewType, indices: &Tensor) -> Tensor {
         let indices_shape = indices.shape();
         let batch_shape = &self.shape()[..axis as usize];
@@ -317,61 +576,148 @@ impl Tensor {
             .reshape(indices.view())
     }
 
-    pub fn MatMul(&self, other: &Tensor) -> Tensor {
-        // m x k @ k x n
-        let input = match self.ndim() {
-            // m -> [1, m]
-            0 => self.broadcast_to(&TensorView::from_contiguous_shape(&[1, 1])),
-            // [m] -> [m, 1]
-            1 => self.unsqueeze(1),
-            // [..., m, k]
-            _ => self.clone(),
+    // Normalizes N x C x ... over groups of consecutive channels
+    pub fn GroupNormalization(&self, num_groups: ViewType, epsilon: &Tensor) -> Tensor {
+        let [batch_size, channels, ..] = self.shape() else {
+            panic!("GroupNormalization expects an input signature of N x C x ...")
         };
+        assert!(
+            channels % num_groups == 0,
+            "GroupNormalization expects the channels ({}) to be divisible by the groups ({})",
+            channels,
+            num_groups
+        );
 
-        let other = match other.ndim() {
-            // n -> [1, n]
-            0 => other.broadcast_to(&TensorView::from_contiguous_shape(&[1, 1])),
-            // [n] -> [1, n]
-            1 => other.unsqueeze(0),
-            // [..., k, n]
-            _ => other.clone(),
-        };
+        let grouped = self.view_as(&[*batch_size, num_groups, -1]);
+        let (mean, variance) = grouped.moments(&[2]);
+        grouped
+            .Sub(&mean)
+            .Divide(&variance.Add(epsilon).Sqrt())
+            .view_as(self.shape())
+    }
 
-        let input_dimension = input.ndim();
-        let other_dimension = other.ndim();
-        let [_batch_size @ .., _n, k] = &input.shape() else {
-            panic!("MatMul requires input to be at least 2D");
-        };
-        let [_batch_size @ .., other_k, _m] = &other.shape() else {
-            panic!("MatMul requires tensor to be at least 2D");
-        };
+    // Normalizes over all axes from axis onwards, also returning the mean and inverse standard
+    // deviation with the reduced axes kept
+    pub fn LayerNormalization(&self, axis: ViewType, epsilon: &Tensor) -> (Tensor, Tensor, Tensor) {
+        let axis = (axis + self.ndim()) % self.ndim();
+        let normalized_axes = (axis..self.ndim()).collect::<Vec<_>>();
+
+        let one = Tensor::scalar(1.0);
+        let (mean, variance) = self.moments(&normalized_axes[..]);
+        let inverse_standard_deviation = one.Divide(&variance.Add(epsilon).Sqrt());
+        let output = self.Sub(&mean).Multiply(&inverse_standard_deviation);
+        (output, mean, inverse_standard_deviation)
+    }
 
+    // Local response normalization across the channels of N x C x ...
+    pub fn LRN(&self, size: ViewType, alpha: f32, beta: f32, bias: f32) -> Tensor {
         assert!(
-            k == other_k,
-            "Failed to multiply matrices of shape {:?} @ {:?}",
-            self.view(),
-            other.view()
+            self.ndim() > 2,
+            "LRN expects an input signature of N x C x ..."
         );
 
-        // (..., n, k, 1)
-        let input = input.unsqueeze(input_dimension);
-        // (..., 1, k, m) - index `-3` which in this case will be axis `other_dimension + 1 - 3`
-        let other = other.unsqueeze(other_dimension - 2);
-        // (..., n, k, m)
-        let intermediate_result = input.Multiply(&other);
-        // (..., n, m) by summing along k
-        let reduce_dimension = intermediate_result.ndim() - 2;
-        intermediate_result.Sum(&[reduce_dimension], false)
+        // The window spans floor((size - 1) / 2) channels before and ceil((size - 1) / 2) after
+        let before = (size - 1) / 2;
+        let after = size - 1 - before;
+        let channel_padding = |begin: ViewType, end: ViewType| {
+            (0..self.ndim())
+                .map(|axis| if axis == 1 { (begin, end) } else { (0, 0) })
+                .collect::<Vec<_>>()
+        };
+
+        let squares = self.Multiply(self).Pad(&channel_padding(before, after)[..]);
+        let square_sum = (0..size)
+            .map(|offset| squares.Pad(&channel_padding(-offset, offset - size + 1)[..]))
+            .reduce(|square_sum, squares| square_sum.Add(&squares))
+            .unwrap();
+
+        let scale = square_sum
+            .Multiply(&Tensor::scalar(alpha / size as f32))
+            .Add(&Tensor::scalar(bias))
+            .Pow(&Tensor::scalar(beta));
+        self.Divide(&scale)
+    }
+
+    pub fn LpPool<'a>(
+        &self,
+        p: i32,
+        kernel_size: &[ViewType],
+        kernel_strides: &[ViewType],
+        dilations: &[ViewType],
+        ceil_mode: bool,
+        padding: ConvPadding<'a>,
+    ) -> Tensor {
+        // Zero padding never contributes to the norm
+        let (_, axis_padding) =
+            self.pool_padding(kernel_size, kernel_strides, dilations, ceil_mode, padding);
+        let (windows, window_axes) =
+            self.pool_windows(kernel_size, kernel_strides, dilations, &axis_padding[..]);
+
+        let p = Tensor::scalar(p as f32);
+        let one = Tensor::scalar(1.0);
+        windows
+            .Abs()
+            .Pow(&p)
+            .Sum(&window_axes[..], false)
+            .Pow(&one.Divide(&p))
     }
 
     pub fn MaxPool<'a>(
         &self,
         kernel_size: &[ViewType],
         kernel_strides: &[ViewType],
+        dilations: &[ViewType],
+        ceil_mode: bool,
         padding: ConvPadding<'a>,
     ) -> Tensor {
-        let kernel = Tensor::scalar(1).reshape(&TensorView::from_contiguous_shape(kernel_size));
-        self.Convolve(&kernel, kernel_strides, padding, Tensor::Max)
+        let (windows, _, window_axes) =
+            self.max_pool_windows(kernel_size, kernel_strides, dilations, ceil_mode, padding);
+        windows.Max(&window_axes[..], false)
+    }
+
+    // Also returns the flattened index of the (first) maximum of every window
+    pub fn MaxPoolWithIndices<'a>(
+        &self,
+        kernel_size: &[ViewType],
+        kernel_strides: &[ViewType],
+        dilations: &[ViewType],
+        ceil_mode: bool,
+        padding: ConvPadding<'a>,
+    ) -> (Tensor, Tensor) {
+        let (_, axis_padding) =
+            self.pool_padding(kernel_size, kernel_strides, dilations, ceil_mode, padding);
+        let (windows, valid, window_axes) = self.max_pool_windows(
+            kernel_size,
+            kernel_strides,
+            dilations,
+            false,
+            ConvPadding::Custom(&axis_padding[..]),
+        );
+        let output = windows.Max(&window_axes[..], false);
+
+        let (indices, _) = Tensor::arange(self.shape()).pool_windows(
+            kernel_size,
+            kernel_strides,
+            dilations,
+            &axis_padding[..],
+        );
+        let is_max = windows
+            .Equal(
+                &output.view_as(
+                    &output
+                        .shape()
+                        .iter()
+                        .copied()
+                        .chain(kernel_size.iter().map(|_| 1))
+                        .collect::<Vec<_>>()[..],
+                ),
+            )
+            .And(&valid);
+        let indices = is_max
+            .Where(&indices, &Tensor::scalar(self.len()))
+            .Min(&window_axes[..], false);
+
+        (output, indices)
     }
 
     pub fn Normalization(&self, feature_axes: &[ViewType], epsilon: &Tensor) -> Tensor {
@@ -402,45 +748,102 @@ impl Tensor {
     }
 
     pub fn Pad(&self, padding: &[(ViewType, ViewType)]) -> Tensor {
+        self.PadWith(padding, PadMode::Constant(0.0))
+    }
+
+    pub fn PadWith(&self, padding: &[(ViewType, ViewType)], mode: PadMode) -> Tensor {
         let dimension = self.ndim() as usize;
         assert!(
             dimension == padding.len(),
             "Padding must be specified for every dimension"
         );
 
-        padding.iter().enumerate().fold(
-            self.clone(),
-            |updates, (axis, &(padding_pre, padding_post))| {
-                let axis = axis as i32;
-                let piecewise_padding = (0..updates.ndim())
-                    .map(|i| {
-                        if i == axis {
-                            (padding_pre, padding_post)
-                        } else {
-                            (0, 0)
-                        }
-                    })
-                    .collect::<Vec<_>>();
+        if padding.iter().all(|&axis_padding| axis_padding == (0, 0)) {
+            return self.clone();
+        }
+
+        self.reshape(&self.view().pad(padding, mode))
+    }
 
-                let offset_tensor = Tensor::scalar(padding_pre);
-                let axis_shape_tensor = Tensor::scalar(updates.shape()[axis as usize]);
+    // Normalizes by the root mean square over all axes from axis onwards
+    pub fn RMSNormalization(&self, axis: ViewType, epsilon: &Tensor) -> Tensor {
+        let axis = (axis + self.ndim()) % self.ndim();
+        let normalized_axes = (axis..self.ndim()).collect::<Vec<_>>();
 
-                let batch_elements = updates.shape()[axis as usize + 1..]
-                    .iter()
-                    .fold(1, |acc, shape| acc * shape);
-                let batch_elements_tensor = Tensor::scalar(batch_elements);
+        let mean_square = self.Multiply(self).Mean(&normalized_axes[..], true);
+        self.Divide(&mean_square.Add(epsilon).Sqrt())
+    }
 
-                let padded_view = updates.view().pad(&piecewise_padding[..]);
-                let data = Tensor::zeros_like(&padded_view.shape[..]);
+    pub fn Repeat(&self, repeats: &[ViewType]) -> Tensor {
+        assert!(
+            repeats.len() >= self.ndim() as usize,
+            "Number of repeats ({}) cannot be less than the number of dimensions ({})",
+            repeats.len(),
+            self.ndim()
+        );
 
-                let indices = Tensor::arange(updates.shape())
-                    .Divide(&batch_elements_tensor)
-                    .Floor()
-                    .Mod(&axis_shape_tensor)
-                    .Add(&offset_tensor);
+        self.Tile(repeats)
+    }
 
-                data.Scatter(axis, &indices, &updates)
-            },
+    pub fn RepeatInterleave(&self, repeats: ViewType, axis: ViewType) -> Tensor {
+        let mut output_shape = self.shape().to_vec();
+        output_shape[axis as usize] *= repeats;
+
+        self.reshape(&self.view().repeat_interleave(repeats, axis))
+            .reshape(&TensorView::from_contiguous_shape(&output_shape[..]))
+    }
+
+    // Query [..., L, E] attends over key [..., S, E] and value [..., S, Ev] with broadcast batch
+    // dimensions. I32 masks keep nonzero positions while F32 masks are added to the scores
+    pub fn ScaledDotProductAttention(
+        query: &Tensor,
+        key: &Tensor,
+        value: &Tensor,
+        mask: Option<&Tensor>,
+        causal: bool,
+        scale: Option<f32>,
+    ) -> Tensor {
+        let [query_batch_size @ .., l, e] = query.shape() else {
+            panic!("ScaledDotProductAttention requires query to be at least 2D");
+        };
+        let [key_batch_size @ .., s, key_e] = key.shape() else {
+            panic!("ScaledDotProductAttention requires key to be at least 2D");
+        };
+        let [value_batch_size @ .., value_s, ev] = value.shape() else {
+            panic!("ScaledDotProductAttention requires value to be at least 2D");
+        };
+
+        let batch_size = [key_batch_size, value_batch_size]
+            .iter()
+            .fold(
+                TensorView::from_contiguous_shape(query_batch_size),
+                |batch_size, other| batch_size.broadcast(&TensorView::from_contiguous_shape(other)),
+            )
+            .shape;
+        let batched = |tensor: &Tensor, shape: &[ViewType]| {
+            tensor
+                .Cast(TensorType::F32)
+                .broadcast_to(&TensorView::from_contiguous_shape(
+                    &[&batch_size[..], shape].concat(),
+                ))
+        };
+
+        let mask = mask.map(|mask| match mask.datatype() {
+            TensorType::F32 => batched(mask, &[*l, *s]),
+            _ => batched(
+                &mask
+                    .Equal(&Tensor::scalar(0))
+                    .Where(&Tensor::scalar(f32::NEG_INFINITY), &Tensor::scalar(0.0)),
+                &[*l, *s],
+            ),
+        });
+
+        batched(query, &[*l, *e]).Attention(
+            &batched(key, &[*s, *key_e]),
+            &batched(value, &[*value_s, *ev]),
+            mask.as_ref(),
+            causal,
+            scale.unwrap_or(1.0 / (*e as f32).sqrt()),
         )
     }
 
@@ -498,16 +901,144 @@ impl Tensor {
             .into_iter()
             .fold(fixed_updates, |acc, _| acc.unsqueeze(acc.ndim()));
 
-        let value_updates = fixed_updates.Multiply(&mask).Sum(&[0], false);
+        let zero = Tensor::scalar(0);
+        let one = Tensor::scalar(1);
         match reduction {
+            // Duplicate indices are applied in order, so the last update wins
             ScatterReduction::None => {
-                let inverted_mask = mask.Max(&[0], false).Not();
-                value_updates.Add(&self.Multiply(&inverted_mask))
+                let order = Tensor::arange(&[updates.len()])
+                    .Add(&one)
+                    .reshape(fixed_updates.view());
+                let latest = mask.Multiply(&order).Max(&[0], true);
+                let value_updates = order
+                    .Equal(&latest)
+                    .Where(&fixed_updates, &zero)
+                    .Sum(&[0], false);
+                mask.Max(&[0], false).Where(&value_updates, self)
+            }
+            // Duplicate indices accumulate
+            ScatterReduction::Add => self.Add(&mask.Where(&fixed_updates, &zero).Sum(&[0], false)),
+            ScatterReduction::Mul => {
+                self.Multiply(&mask.Where(&fixed_updates, &one).Prod(&[0], false))
+            }
+            ScatterReduction::Max => {
+                self.Maximum(&mask.Where(&fixed_updates, self).Max(&[0], false))
+            }
+            ScatterReduction::Min => {
+                self.Minimum(&mask.Where(&fixed_updates, self).Min(&[0], false))
             }
-            _ => panic!("Only ScatterReduction::None is currently supported"),
         }
     }
 
+    // Row-major offset of every index tuple along the last axis of `indices`,
+    // addressing the axes of self starting at `first_axis`
+    fn nd_offsets(&self, indices: &Tensor, first_axis: usize) -> Tensor {
+        let depth = indices.shape()[indices.ndim() as usize - 1] as usize;
+        let addressed_shape = &self.shape()[first_axis..first_axis + depth];
+        let addressed_stride =
+            TensorView::from_contiguous_shape(addressed_shape).contiguous_stride();
+
+        let extents = Tensor::from_contiguous(addressed_shape, &[depth as ViewType]);
+        let strides = Tensor::from_contiguous(&addressed_stride[..], &[depth as ViewType]);
+
+        // Negative indices count from the end of their axis
+        let indices = indices
+            .LessThan(&Tensor::scalar(0))
+            .Multiply(&extents)
+            .Add(indices);
+        indices.Multiply(&strides).Sum(&[indices.ndim() - 1], false)
+    }
+
+    pub fn GatherND(&self, indices: &Tensor, batch_dims: ViewType) -> Tensor {
+        let [index_shape @ .., depth] = indices.shape() else {
+            panic!("GatherND expects indices to be at least 1D");
+        };
+        let (batch_dims, depth) = (batch_dims as usize, *depth as usize);
+        assert!(
+            batch_dims + depth <= self.ndim() as usize
+                && self.shape()[..batch_dims] == indices.shape()[..batch_dims],
+            "GatherND cannot index {:?} with indices {:?} and {} batch dimensions",
+            self.shape(),
+            indices.shape(),
+            batch_dims
+        );
+
+        let addressed_length = self.shape()[..batch_dims + depth]
+            .iter()
+            .product::<ViewType>();
+        let slice_shape = &self.shape()[batch_dims + depth..];
+        let slice_length = slice_shape.iter().product::<ViewType>();
+
+        // Every batch addresses its own block of the flattened leading axes
+        let offsets = self.nd_offsets(indices, batch_dims);
+        let offsets = if batch_dims > 0 {
+            let batch_shape = &self.shape()[..batch_dims];
+            let block_length = addressed_length / batch_shape.iter().product::<ViewType>();
+            let batch_offsets = Tensor::arange(batch_shape)
+                .Multiply(&Tensor::scalar(block_length))
+                .reshape(&TensorView::from_contiguous_shape(
+                    &[batch_shape, &vec![1; index_shape.len() - batch_dims]].concat(),
+                ));
+            offsets.Add(&batch_offsets)
+        } else {
+            offsets
+        };
+
+        let data = self.reshape(&TensorView::from_contiguous_shape(&[
+            addressed_length,
+            slice_length,
+        ]));
+        let offsets = offsets.reshape(&TensorView::from_contiguous_shape(&[offsets.len()]));
+        data.Gather(0, &offsets)
+            .reshape(&TensorView::from_contiguous_shape(
+                &[index_shape, slice_shape].concat(),
+            ))
+    }
+
+    pub fn ScatterND(
+        &self,
+        reduction: ScatterReduction,
+        indices: &Tensor,
+        updates: &Tensor,
+    ) -> Tensor {
+        let [index_shape @ .., depth] = indices.shape() else {
+            panic!("ScatterND expects indices to be at least 1D");
+        };
+        let depth = *depth as usize;
+        assert!(
+            depth <= self.ndim() as usize
+                && updates.shape() == &[index_shape, &self.shape()[depth..]].concat()[..],
+            "ScatterND cannot update {:?} with indices {:?} and updates {:?}",
+            self.shape(),
+            indices.shape(),
+            updates.shape()
+        );
+
+        // Every index tuple addresses a row of self flattened to [addressed, slice]
+        let addressed_length = self.shape()[..depth].iter().product::<ViewType>();
+        let slice_length = self.shape()[depth..].iter().product::<ViewType>();
+        let updates_length = index_shape.iter().product::<ViewType>();
+
+        let data = self.reshape(&TensorView::from_contiguous_shape(&[
+            addressed_length,
+            slice_length,
+        ]));
+        let offsets = self
+            .nd_offsets(indices, 0)
+            .reshape(&TensorView::from_contiguous_shape(&[updates_length, 1]))
+            .broadcast_to(&TensorView::from_contiguous_shape(&[
+                updates_length,
+                slice_length,
+            ]));
+        let updates = updates.reshape(&TensorView::from_contiguous_shape(&[
+            updates_length,
+            slice_length,
+        ]));
+
+        data.ScatterElements(0, reduction, &offsets, &updates)
+            .reshape(&TensorView::from_contiguous_shape(self.shape()))
+    }
+
     pub fn Slice(
         &self,
         starts: &[ViewType],
@@ -531,4 +1062,288 @@ impl Tensor {
                 acc.Gather(axis, &indices_tensor)
             })
     }
+
+    // Solves AX = B by forward and back substitution through the pivoted LU factors of A
+    pub fn Solve(&self, rhs: &Tensor) -> Tensor {
+        let [matrix_batch_size @ .., n, m] = self.shape() else {
+            panic!("Solve requires matrix to be at least 2D");
+        };
+        let [rhs_batch_size @ .., rhs_n, k] = rhs.shape() else {
+            panic!("Solve requires rhs to be at least 2D");
+        };
+        assert!(
+            n == m && n == rhs_n,
+            "Failed to solve matrices of shape {:?} \\ {:?}",
+            self.shape(),
+            rhs.shape()
+        );
+
+        let batch_size = TensorView::from_contiguous_shape(matrix_batch_size)
+            .broadcast(&TensorView::from_contiguous_shape(rhs_batch_size))
+            .shape;
+        let matrix = self.broadcast_to(&TensorView::from_contiguous_shape(
+            &[&batch_size[..], &[*n, *n]].concat(),
+        ));
+        let rhs = rhs
+            .Cast(TensorType::F32)
+            .broadcast_to(&TensorView::from_contiguous_shape(
+                &[&batch_size[..], &[*n, *k]].concat(),
+            ));
+
+        // Rows of B are exchanged like the rows of A
+        let ndim = matrix.ndim();
+        let (factors, permutation) = matrix.LU(true);
+        let permutation = permutation.unsqueeze(ndim - 1).broadcast_to(rhs.view());
+        let rhs = rhs.GatherElements(ndim - 2, &permutation);
+
+        factors.TriangularSolve(&factors.TriangularSolve(&rhs, false, true), true, false)
+    }
+
+    pub fn Split(&self, axis: ViewType, splits: &[ViewType]) -> Vec<Tensor> {
+        let axis_shape = self.shape()[axis as usize];
+        assert!(
+            splits.iter().sum::<ViewType>() == axis_shape,
+            "Splits {:?} must sum to the shape {} of axis {}",
+            splits,
+            axis_shape,
+            axis
+        );
+
+        splits
+            .iter()
+            .scan(0, |offset, &split| {
+                let begin = *offset;
+                *offset += split;
+                Some((begin, *offset))
+            })
+            .map(|(begin, end)| {
+                // Negative padding crops the axis, reads never fall outside so the mode is irrelevant
+                let padding = (0..self.ndim())
+                    .map(|dimension| {
+                        if dimension == axis {
+                            (-begin, end - axis_shape)
+                        } else {
+                            (0, 0)
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                self.PadWith(&padding[..], PadMode::Edge)
+            })
+            .collect::<Vec<_>>()
+    }
+
+    pub fn Stack(inputs: &[Tensor], axis: ViewType) -> Tensor {
+        let inputs = inputs
+            .iter()
+            .map(|input| {
+                assert!(
+                    axis <= input.ndim(),
+                    "Stack axis {} is out of bounds, 0 <= axis <= {}",
+                    axis,
+                    input.ndim()
+                );
+
+                let mut shape = input.shape().to_vec();
+                shape.insert(axis as usize, 1);
+                input.reshape(&TensorView::from_contiguous_shape(&shape[..]))
+            })
+            .collect::<Vec<_>>();
+
+        Tensor::Concat(&inputs[..], axis)
+    }
+
+    pub fn Tile(&self, repeats: &[ViewType]) -> Tensor {
+        // Missing leading dimensions are treated as 1, for either the input or repeats
+        let ndim = repeats.len().max(self.ndim() as usize);
+        let input_shape = std::iter::repeat_n(1, ndim - self.ndim() as usize)
+            .chain(self.shape().iter().copied())
+            .collect::<Vec<_>>();
+        let repeats = std::iter::repeat_n(1, ndim - repeats.len())
+            .chain(repeats.iter().copied())
+            .collect::<Vec<_>>();
+
+        let input = if input_shape.len() == self.ndim() as usize {
+            self.clone()
+        } else {
+            self.reshape(&TensorView::from_contiguous_shape(&input_shape[..]))
+        };
+
+        let output_shape = input_shape
+            .iter()
+            .zip(repeats.iter())
+            .map(|(&dimension, &repeat)| dimension * repeat)
+            .collect::<Vec<_>>();
+
+        input
+            .reshape(&input.view().tile(&repeats[..]))
+            .reshape(&TensorView::from_contiguous_shape(&output_shape[..]))
+    }
+
+    pub fn Unfold(
+        &self,
+        kernel_size: &[ViewType],
+        strides: &[ViewType],
+        dilations: &[ViewType],
+    ) -> Tensor {
+        self.reshape(&self.view().unfold(kernel_size, strides, dilations))
+    }
+
+    // [1, C, 1, ...] for parameters shared by every element of a channel
+    fn channel_shape(&self) -> Vec<ViewType> {
+        assert!(
+            self.ndim() >= 2,
+            "Channel parameters expect an input signature of N x C x ..."
+        );
+        (0..self.ndim())
+            .map(|axis| if axis == 1 { -1 } else { 1 })
+            .collect::<Vec<_>>()
+    }
+
+    fn feature_axes(&self) -> Vec<ViewType> {
+        assert!(
+            self.ndim() > 2,
+            "Pooling expects an input signature of N x C x ..."
+        );
+        (2..self.ndim()).collect::<Vec<_>>()
+    }
+
+    fn max_pool_windows<'a>(
+        &self,
+        kernel_size: &[ViewType],
+        kernel_strides: &[ViewType],
+        dilations: &[ViewType],
+        ceil_mode: bool,
+        padding: ConvPadding<'a>,
+    ) -> (Tensor, Tensor, Vec<ViewType>) {
+        let (_, axis_padding) =
+            self.pool_padding(kernel_size, kernel_strides, dilations, ceil_mode, padding);
+        let (windows, window_axes) =
+            self.pool_windows(kernel_size, kernel_strides, dilations, &axis_padding[..]);
+        let no_padding = self.shape().iter().map(|_| (0, 0)).collect::<Vec<_>>();
+        let valid = self.pool_mask(
+            kernel_size,
+            kernel_strides,
+            dilations,
+            &no_padding[..],
+            &axis_padding[..],
+        );
+
+        // The smallest element never exceeds the maximum of the window it replaces padding in
+        let all_axes = (0..self.ndim()).collect::<Vec<_>>();
+        let lowest = self.Min(&all_axes[..], false);
+        (valid.Where(&windows, &lowest), valid, window_axes)
+    }
+
+    // Mean and biased variance, with the reduced axes kept
+    fn moments(&self, axes: &[ViewType]) -> (Tensor, Tensor) {
+        let mean = self.Mean(axes, true);
+        let centered = self.Sub(&mean);
+        let variance = centered.Multiply(&centered).Mean(axes, true);
+        (mean, variance)
+    }
+
+    // Returns the requested padding of every dimension and the padding extended by ceil_mode
+    fn pool_padding<'a>(
+        &self,
+        kernel_size: &[ViewType],
+        kernel_strides: &[ViewType],
+        dilations: &[ViewType],
+        ceil_mode: bool,
+        padding: ConvPadding<'a>,
+    ) -> (AxisPadding, AxisPadding) {
+        assert!(
+            kernel_strides.len() == kernel_size.len() && dilations.len() == kernel_size.len(),
+            "A stride ({} defined) and dilation ({} defined) should be defined for all dimensions of the kernel ({})",
+            kernel_strides.len(),
+            dilations.len(),
+            kernel_size.len(),
+        );
+
+        let extents = kernel_size
+            .iter()
+            .zip(dilations.iter())
+            .map(|(&kernel, &dilation)| dilation * (kernel - 1) + 1)
+            .collect::<Vec<_>>();
+        let explicit_padding = padding.axis_padding(self.shape(), &extents[..], kernel_strides);
+        if !ceil_mode {
+            return (explicit_padding.clone(), explicit_padding);
+        }
+
+        // Extend the end so that a partial last window is kept, unless it would start in the padding
+        let leading_axes = self.ndim() as usize - kernel_size.len();
+        let axis_padding = explicit_padding
+            .iter()
+            .enumerate()
+            .map(|(axis, &(begin, end))| {
+                if axis < leading_axes {
+                    return (begin, end);
+                }
+
+                let feature = self.shape()[axis];
+                let extent = extents[axis - leading_axes];
+                let stride = kernel_strides[axis - leading_axes];
+
+                let padded = feature + begin + end;
+                let mut windows = (padded - extent + stride - 1) / stride + 1;
+                if (windows - 1) * stride >= feature + begin {
+                    windows -= 1;
+                }
+                let extension = ((windows - 1) * stride + extent - padded).max(0);
+                (begin, end + extension)
+            })
+            .collect::<Vec<_>>();
+        (explicit_padding, axis_padding)
+    }
+
+    // Windows marking the elements of the padded input which are counted, with `counted_padding`
+    // being the part of `axis_padding` that is counted
+    fn pool_mask(
+        &self,
+        kernel_size: &[ViewType],
+        kernel_strides: &[ViewType],
+        dilations: &[ViewType],
+        counted_padding: &[(ViewType, ViewType)],
+        axis_padding: &[(ViewType, ViewType)],
+    ) -> Tensor {
+        let counted_shape = self
+            .shape()
+            .iter()
+            .zip(counted_padding.iter())
+            .map(|(&dimension, &(begin, end))| dimension + begin + end)
+            .collect::<Vec<_>>();
+        let remaining_padding = counted_padding
+            .iter()
+            .zip(axis_padding.iter())
+            .map(|(&(counted_begin, counted_end), &(begin, end))| {
+                (begin - counted_begin, end - counted_end)
+            })
+            .collect::<Vec<_>>();
+
+        let (mask, _) = Tensor::scalar(1)
+            .broadcast_to(&TensorView::from_contiguous_shape(&counted_shape[..]))
+            .pool_windows(
+                kernel_size,
+                kernel_strides,
+                dilations,
+                &remaining_padding[..],
+            );
+        mask
+    }
+
+    fn pool_windows(
+        &self,
+        kernel_size: &[ViewType],
+        kernel_strides: &[ViewType],
+        dilations: &[ViewType],
+        axis_padding: &[(ViewType, ViewType)],
+    ) -> (Tensor, Vec<ViewType>) {
+        let windows = self
+            .Pad(axis_padding)
+            .Unfold(kernel_size, kernel_strides, dilations);
+
+        // Windows are the trailing dimensions
+        let window_axes =
+            (windows.ndim() - kernel_size.len() as ViewType..windows.ndim()).collect::<Vec<_>>();
+        (windows, window_axes)
+    }
 }
@@ -0,0 +1,446 @@
+use crate::primitives::tensor::{Tensor, TensorType};
+use crate::primitives::tensorview::{TensorView, ViewType};
+
+#[derive(Clone, Copy, Debug)]
+pub enum NearestMode {
+    RoundPreferFloor,
+    RoundPreferCeil,
+    Floor,
+    Ceil,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ResizeMode {
+    Nearest(NearestMode),
+    Linear,
+    // Cubic convolution with coefficient a
+    Cubic(f32),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum CoordinateTransformation<'a> {
+    HalfPixel,
+    HalfPixelSymmetric,
+    PytorchHalfPixel,
+    AlignCorners,
+    Asymmetric,
+    // Region of interest (start, end) of every axis, and the value outside of it
+    TfCropAndResize(&'a [(f32, f32)], f32),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum GridSampleMode {
+    Bilinear,
+    Nearest,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum GridSamplePadding {
+    Zeros,
+    Border,
+    Reflection,
+}
+
+impl Tensor {
+    pub fn Resize<'a>(
+        &self,
+        sizes: &[ViewType],
+        scales: &[f32],
+        mode: ResizeMode,
+        transformation: CoordinateTransformation<'a>,
+        exclude_outside: bool,
+    ) -> Tensor {
+        assert!(
+            sizes.len() == self.ndim() as usize && scales.len() == self.ndim() as usize,
+            "Resize expects a size ({} defined) and scale ({} defined) for every dimension ({})",
+            sizes.len(),
+            scales.len(),
+            self.ndim()
+        );
+
+        // Interpolation is separable, the innermost axes are resized first
+        let datatype = self.datatype();
+        (0..self.ndim())
+            .rev()
+            .fold(self.Cast(TensorType::F32), |input, axis| {
+                let (size, scale) = (sizes[axis as usize], scales[axis as usize]);
+                let identity = size == self.shape()[axis as usize]
+                    && scale == 1.0
+                    && !matches!(
+                        transformation,
+                        CoordinateTransformation::TfCropAndResize(..)
+                    );
+                if identity {
+                    return input;
+                }
+
+                input.resize_axis(axis, size, scale, mode, transformation, exclude_outside)
+            })
+            .Cast(datatype)
+    }
+
+    // Samples [N, C, D...] at the normalized coordinates of grid [N, O..., ndim], which are
+    // ordered from the innermost dimension outwards
+    pub fn GridSample(
+        &self,
+        grid: &Tensor,
+        mode: GridSampleMode,
+        padding: GridSamplePadding,
+        align_corners: bool,
+    ) -> Tensor {
+        let [batch_size, channels, features @ ..] = self.shape() else {
+            panic!("GridSample expects an input signature of N x C x ...")
+        };
+        let [grid_batch_size, output_size @ .., coordinates] = grid.shape() else {
+            panic!("GridSample expects a grid signature of N x ... x ndim")
+        };
+
+        assert!(
+            batch_size == grid_batch_size
+                && *coordinates as usize == features.len()
+                && output_size.len() == features.len(),
+            "GridSample expects a grid of shape [{}, ..., {}] with {} output dimensions, got {:?}",
+            batch_size,
+            features.len(),
+            features.len(),
+            grid.shape()
+        );
+
+        let zero = Tensor::scalar(0.0);
+        let one = Tensor::scalar(1.0);
+
+        // Unnormalized coordinate along every feature dimension, [N, O...]
+        let last_axis = grid.ndim() - 1;
+        let components = grid
+            .Cast(TensorType::F32)
+            .Split(last_axis, &vec![1; features.len()][..]);
+        let positions = features
+            .iter()
+            .enumerate()
+            .map(|(dimension, &length)| {
+                let coordinate = components[features.len() - 1 - dimension].squeeze(last_axis);
+                let length_f32 = length as f32;
+
+                // Corners are either the centers or the edges of the outermost elements
+                let position = match align_corners {
+                    true => coordinate
+                        .Add(&one)
+                        .Multiply(&Tensor::scalar((length_f32 - 1.0) / 2.0)),
+                    false => coordinate
+                        .Add(&one)
+                        .Multiply(&Tensor::scalar(length_f32))
+                        .Sub(&one)
+                        .Divide(&Tensor::scalar(2.0)),
+                };
+                let position = match mode {
+                    GridSampleMode::Nearest => position.round_half_to_even(),
+                    GridSampleMode::Bilinear => position,
+                };
+
+                match padding {
+                    GridSamplePadding::Zeros => position,
+                    GridSamplePadding::Border => {
+                        position.Clip(&zero, &Tensor::scalar(length_f32 - 1.0))
+                    }
+                    GridSamplePadding::Reflection => position.reflect(length, align_corners),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Flattened offset of every (n, c) plane, [N, C, 1...]
+        let plane = features.iter().product::<ViewType>();
+        let spatial_ones = features.iter().map(|_| 1).collect::<Vec<_>>();
+        let plane_offset = Tensor::arange(&[&[*batch_size, *channels], &spatial_ones[..]].concat())
+            .Multiply(&Tensor::scalar(plane));
+
+        // Reads the input at integral coordinates
+        let read = |coordinates: &[Tensor]| {
+            let coordinates = coordinates
+                .iter()
+                .zip(features.iter())
+                .map(|(coordinate, &length)| match padding {
+                    GridSamplePadding::Reflection => coordinate.reflect(length, align_corners),
+                    _ => coordinate.clone(),
+                })
+                .collect::<Vec<_>>();
+            let clamped = coordinates
+                .iter()
+                .zip(features.iter())
+                .map(|(coordinate, &length)| {
+                    coordinate.Clip(&zero, &Tensor::scalar(length as f32 - 1.0))
+                })
+                .collect::<Vec<_>>();
+
+            let stride = TensorView::from_contiguous_shape(features).contiguous_stride();
+            let offset = clamped
+                .iter()
+                .zip(stride.iter())
+                .map(|(coordinate, &stride)| {
+                    coordinate
+                        .Cast(TensorType::I32)
+                        .Multiply(&Tensor::scalar(stride))
+                })
+                .reduce(|offset, dimension_offset| offset.Add(&dimension_offset))
+                .unwrap();
+
+            // [N, O...] -> [N, 1, O...]
+            let indices = plane_offset.Add(&offset.unsqueeze(1));
+            let values = self.Take(&indices).Cast(TensorType::F32);
+            match padding {
+                // Reads outside of the input are zero
+                GridSamplePadding::Zeros => {
+                    let inside = coordinates
+                        .iter()
+                        .zip(clamped.iter())
+                        .map(|(coordinate, clamped)| clamped.Equal(coordinate))
+                        .reduce(|inside, dimension_inside| inside.And(&dimension_inside))
+                        .unwrap();
+                    values.Multiply(&inside.unsqueeze(1))
+                }
+                _ => values,
+            }
+        };
+
+        let output = match mode {
+            GridSampleMode::Nearest => read(&positions[..]),
+            GridSampleMode::Bilinear => {
+                let lower = positions
+                    .iter()
+                    .map(|position| position.Floor())
+                    .collect::<Vec<_>>();
+
+                // Every corner of the enclosing cell is weighed by the opposite fraction
+                (0..1 << features.len())
+                    .map(|corner| {
+                        let (coordinates, weight) =
+                            positions.iter().zip(lower.iter()).enumerate().fold(
+                                (vec![], Tensor::scalar(1.0)),
+                                |(mut coordinates, weight), (dimension, (position, lower))| {
+                                    let fraction = position.Sub(lower);
+                                    let weight = match (corner >> dimension) & 1 {
+                                        0 => {
+                                            coordinates.push(lower.clone());
+                                            weight.Multiply(&one.Sub(&fraction))
+                                        }
+                                        _ => {
+                                            coordinates.push(lower.Add(&one));
+                                            weight.Multiply(&fraction)
+                                        }
+                                    };
+                                    (coordinates, weight)
+                                },
+                            );
+                        read(&coordinates[..]).Multiply(&weight.unsqueeze(1))
+                    })
+                    .reduce(|output, corner| output.Add(&corner))
+                    .unwrap()
+            }
+        };
+
+        output.Cast(self.datatype())
+    }
+
+    fn resize_axis<'a>(
+        &self,
+        axis: ViewType,
+        size: ViewType,
+        scale: f32,
+        mode: ResizeMode,
+        transformation: CoordinateTransformation<'a>,
+        exclude_outside: bool,
+    ) -> Tensor {
+        let length = self.shape()[axis as usize];
+        let length_f32 = length as f32;
+        let zero = Tensor::scalar(0.0);
+        let half = Tensor::scalar(0.5);
+        let one = Tensor::scalar(1.0);
+        let last = Tensor::scalar(length_f32 - 1.0);
+
+        // Coordinate in the input of every output element along the axis
+        let resized = Tensor::arange(&[size]).Cast(TensorType::F32);
+        let constant = |value: f32| {
+            Tensor::scalar(value).broadcast_to(&TensorView::from_contiguous_shape(&[size]))
+        };
+        let half_pixel = resized.Add(&half).Divide(&Tensor::scalar(scale)).Sub(&half);
+        let original = match transformation {
+            CoordinateTransformation::HalfPixel => half_pixel,
+            CoordinateTransformation::HalfPixelSymmetric => {
+                let adjustment = size as f32 / (scale * length_f32);
+                let offset = length_f32 / 2.0 * (1.0 - adjustment);
+                half_pixel.Add(&Tensor::scalar(offset))
+            }
+            CoordinateTransformation::PytorchHalfPixel if size > 1 => half_pixel,
+            CoordinateTransformation::PytorchHalfPixel => constant(0.0),
+            CoordinateTransformation::AlignCorners if size > 1 => {
+                resized.Multiply(&Tensor::scalar((length_f32 - 1.0) / (size as f32 - 1.0)))
+            }
+            CoordinateTransformation::AlignCorners => constant(0.0),
+            CoordinateTransformation::Asymmetric => resized.Divide(&Tensor::scalar(scale)),
+            CoordinateTransformation::TfCropAndResize(roi, _) => {
+                let (start, end) = roi[axis as usize];
+                match size > 1 {
+                    true => resized
+                        .Multiply(&Tensor::scalar(
+                            (end - start) * (length_f32 - 1.0) / (size as f32 - 1.0),
+                        ))
+                        .Add(&Tensor::scalar(start * (length_f32 - 1.0))),
+                    false => constant(0.5 * (start + end) * (length_f32 - 1.0)),
+                }
+            }
+        };
+
+        // Per-element factors along the axis broadcast over the other dimensions
+        let along_axis = |tensor: &Tensor| {
+            let shape = (0..self.ndim())
+                .map(|dimension| if dimension == axis { size } else { 1 })
+                .collect::<Vec<_>>();
+            tensor.view_as(&shape[..])
+        };
+        // Neighbours beyond the input repeat its edge
+        let read = |coordinate: &Tensor| {
+            self.take_along(axis, &coordinate.Clip(&zero, &last).Cast(TensorType::I32))
+        };
+
+        let output = match mode {
+            ResizeMode::Nearest(nearest) => {
+                let coordinate = match nearest {
+                    NearestMode::RoundPreferFloor => original.Sub(&half).Ceil(),
+                    NearestMode::RoundPreferCeil => original.Add(&half).Floor(),
+                    NearestMode::Floor => original.Floor(),
+                    NearestMode::Ceil => original.Ceil(),
+                };
+                read(&coordinate)
+            }
+            ResizeMode::Linear => {
+                let lower = original.Floor();
+                let fraction = original.Sub(&lower);
+                read(&lower)
+                    .Multiply(&along_axis(&one.Sub(&fraction)))
+                    .Add(&read(&lower.Add(&one)).Multiply(&along_axis(&fraction)))
+            }
+            ResizeMode::Cubic(a) => {
+                let lower = original.Floor();
+                let t = original.Sub(&lower);
+                let a = Tensor::scalar(a);
+
+                // Cubic convolution weights of the neighbours at offsets -1, 0, 1 and 2
+                // W(x) = (a+2)|x|^3 - (a+3)|x|^2 + 1 for |x| <= 1
+                // W(x) = a|x|^3 - 5a|x|^2 + 8a|x| - 4a for 1 < |x| < 2
+                let near = |x: &Tensor| {
+                    a.Add(&Tensor::scalar(2.0))
+                        .Multiply(x)
+                        .Sub(&a.Add(&Tensor::scalar(3.0)))
+                        .Multiply(x)
+                        .Multiply(x)
+                        .Add(&one)
+                };
+                let far = |x: &Tensor| {
+                    a.Multiply(x)
+                        .Sub(&a.Multiply(&Tensor::scalar(5.0)))
+                        .Multiply(x)
+                        .Add(&a.Multiply(&Tensor::scalar(8.0)))
+                        .Multiply(x)
+                        .Sub(&a.Multiply(&Tensor::scalar(4.0)))
+                };
+                let neighbours = [
+                    (-1.0, far(&t.Add(&one))),
+                    (0.0, near(&t)),
+                    (1.0, near(&one.Sub(&t))),
+                    (2.0, far(&Tensor::scalar(2.0).Sub(&t))),
+                ]
+                .into_iter()
+                .map(|(offset, weight)| {
+                    let coordinate = lower.Add(&Tensor::scalar(offset));
+                    // Neighbours outside of the input may be excluded from the convolution
+                    let weight = match exclude_outside {
+                        true => weight.Multiply(&coordinate.Clip(&zero, &last).Equal(&coordinate)),
+                        false => weight,
+                    };
+                    (coordinate, weight)
+                })
+                .collect::<Vec<_>>();
+
+                let total = neighbours
+                    .iter()
+                    .fold(Tensor::scalar(0.0), |total, (_, weight)| total.Add(weight));
+                neighbours
+                    .iter()
+                    .map(|(coordinate, weight)| {
+                        let weight = match exclude_outside {
+                            true => weight.Divide(&total),
+                            false => weight.clone(),
+                        };
+                        read(coordinate).Multiply(&along_axis(&weight))
+                    })
+                    .reduce(|output, neighbour| output.Add(&neighbour))
+                    .unwrap()
+            }
+        };
+
+        match transformation {
+            CoordinateTransformation::TfCropAndResize(_, extrapolation) => {
+                let outside = original.LessThan(&zero).Or(&last.LessThan(&original));
+                along_axis(&outside).Where(&Tensor::scalar(extrapolation), &output)
+            }
+            _ => output,
+        }
+    }
+
+    // Reads the axis at every index of a 1D tensor, with all other dimensions kept
+    fn take_along(&self, axis: ViewType, indices: &Tensor) -> Tensor {
+        let shape = self.shape().to_vec();
+        let outer = shape[..axis as usize].iter().product::<ViewType>();
+        let length = shape[axis as usize];
+        let inner = shape[axis as usize + 1..].iter().product::<ViewType>();
+        let size = indices.view().len();
+
+        // Index (o, i, j) of [outer, size, inner] reads (o, indices[i], j) of [outer, length, inner]
+        let flattened_indices = Tensor::arange(&[outer, 1, 1])
+            .Multiply(&Tensor::scalar(length * inner))
+            .Add(
+                &indices
+                    .view_as(&[1, size, 1])
+                    .Multiply(&Tensor::scalar(inner)),
+            )
+            .Add(&Tensor::arange(&[1, 1, inner]));
+
+        let mut output_shape = shape;
+        output_shape[axis as usize] = size;
+        self.Take(&flattened_indices).view_as(&output_shape[..])
+    }
+
+    // Rounds halfway cases towards the even neighbour
+    fn round_half_to_even(&self) -> Tensor {
+        let half = Tensor::scalar(0.5);
+        let rounded = self.Add(&half).Floor();
+        let halfway = rounded.Sub(self).Equal(&half);
+        let odd = rounded
+            .Mod(&Tensor::scalar(2.0))
+            .Equal(&Tensor::scalar(0.0))
+            .Not();
+        rounded.Sub(&halfway.And(&odd).Cast(TensorType::F32))
+    }
+
+    // Folds coordinates back into the borders of an axis of the given length, which are the
+    // edges of the outermost elements unless corners are aligned to their centers
+    fn reflect(&self, length: ViewType, align_corners: bool) -> Tensor {
+        let (low, high) = match align_corners {
+            true => (0.0, length as f32 - 1.0),
+            false => (-0.5, length as f32 - 0.5),
+        };
+        let range = high - low;
+        if range <= 0.0 {
+            return Tensor::scalar(low).broadcast_to(self.view());
+        }
+
+        // low + range - |(|x - low| % 2range) - range|
+        let range_tensor = Tensor::scalar(range);
+        let folded = self
+            .Sub(&Tensor::scalar(low))
+            .Abs()
+            .Mod(&Tensor::scalar(2.0 * range));
+        range_tensor
+            .Sub(&folded.Sub(&range_tensor).Abs())
+            .Add(&Tensor::scalar(low))
+    }
+}
@@ -0,0 +1,121 @@
+use std::rc::Rc;
+
+use crate::primitives::tensor::{ScatterReduction, Tensor, TensorType};
+use crate::primitives::tensorview::{TensorView, ViewType};
+
+// A Tensor whose shape depends on data, evaluated in two passes:
+// `count` is evaluated first and its value sizes the fill pass
+#[derive(Clone)]
+pub struct DataDependent {
+    pub count: Tensor,
+    fill: Rc<dyn Fn(ViewType) -> Tensor>,
+}
+
+impl DataDependent {
+    pub fn new(count: Tensor, fill: impl Fn(ViewType) -> Tensor + 'static) -> DataDependent {
+        DataDependent {
+            count,
+            fill: Rc::new(fill),
+        }
+    }
+
+    pub fn resolve(&self, count: ViewType) -> Tensor {
+        (self.fill)(count)
+    }
+}
+
+impl Tensor {
+    fn flatten(&self) -> Tensor {
+        self.reshape(&TensorView::from_contiguous_shape(&[self.len()]))
+    }
+
+    // Flattened 0/1 mask of the nonzero elements
+    fn nonzero_mask(&self) -> Tensor {
+        self.flatten()
+            .Equal(&Tensor::scalar(0))
+            .Not()
+            .Cast(TensorType::I32)
+    }
+
+    // Built directly, as broadcasting would widen an empty axis to one element
+    fn empty(shape: &[ViewType], datatype: TensorType) -> Tensor {
+        Tensor::from_raw_bytes(&[], TensorView::from_contiguous_shape(shape), datatype)
+    }
+
+    // Positions of the first `count` ones of a flattened mask in ascending order
+    fn selected_indices(mask: &Tensor, count: ViewType) -> Tensor {
+        assert!(count > 0, "Empty selections are resolved without indices");
+
+        // Every selected position is scattered to the slot counting the selections before it,
+        // the other positions go to a spare slot past the end which is split off
+        let slots = mask.Where(&mask.CumSum(0, true, false), &Tensor::scalar(count));
+        let positions = Tensor::arange(mask.shape());
+        Tensor::zeros_like(&[count + 1])
+            .ScatterElements(0, ScatterReduction::None, &slots, &positions)
+            .Split(0, &[count, 1])[0]
+            .clone()
+    }
+
+    // Coordinates of the nonzero elements as [ndim, count]
+    pub fn NonZero(&self) -> DataDependent {
+        assert!(self.ndim() > 0, "NonZero expects at least a 1D Tensor");
+
+        let mask = self.nonzero_mask();
+        let count = mask.Sum(&[0], false);
+        let shape = self.shape().to_vec();
+
+        DataDependent::new(count, move |count| {
+            if count == 0 {
+                return Tensor::empty(&[shape.len() as ViewType, 0], TensorType::I32);
+            }
+
+            let indices = Tensor::selected_indices(&mask, count);
+            let stride = TensorView::from_contiguous_shape(&shape).contiguous_stride();
+
+            let coordinates = shape
+                .iter()
+                .zip(stride.iter())
+                .map(|(&extent, &stride)| {
+                    indices
+                        .Divide(&Tensor::scalar(stride))
+                        .Mod(&Tensor::scalar(extent))
+                        .unsqueeze(0)
+                })
+                .collect::<Vec<_>>();
+            Tensor::Concat(&coordinates[..], 0)
+        })
+    }
+
+    // Selects the slices along `axis` whose condition is nonzero, or elements when flattened
+    pub fn Compress(&self, condition: &Tensor, axis: Option<ViewType>) -> DataDependent {
+        let input = match axis {
+            Some(_) => self.clone(),
+            None => self.flatten(),
+        };
+        let axis = axis.unwrap_or(0);
+        assert!(
+            condition.ndim() == 1 && condition.len() <= input.shape()[axis as usize],
+            "Compress expects a 1D condition no longer than {}, got {:?}",
+            input.shape()[axis as usize],
+            condition.shape()
+        );
+
+        let mask = condition.nonzero_mask();
+        let count = mask.Sum(&[0], false);
+
+        DataDependent::new(count, move |count| {
+            if count == 0 {
+                let mut shape = input.shape().to_vec();
+                shape[axis as usize] = 0;
+                return Tensor::empty(&shape, input.datatype());
+            }
+
+            input.Gather(axis, &Tensor::selected_indices(&mask, count))
+        })
+    }
+
+    // Elements where the broadcasted mask is nonzero, flattened
+    pub fn masked_select(&self, mask: &Tensor) -> DataDependent {
+        self.Compress(&mask.broadcast_to(self.view()).flatten(), None)
+    }
+}
@@ -22,7 +22,7 @@ impl Tensor {
     }
 
     pub fn Minimum(&self, other: &Tensor) -> Tensor {
-        self.Neg().Maximum(&other.Neg()).Neg()
+        self.LessThan(other).Where(self, other)
     }
 
     pub fn Or(&self, other: &Tensor) -> Tensor {
@@ -78,12 +78,10 @@ impl Tensor {
         let alpha = Tensor::scalar(alpha);
 
         let leak = alpha.Multiply(&self.Exp().Sub(&one));
-        let mask = self.LessOrEqual(&zero);
-        let masked_leak = leak.Multiply(&mask);
 
         // z if z > 0
         // alpha * (exp(z) - 1) if z <= 0
-        self.Relu().Add(&masked_leak)
+        zero.LessThan(self).Where(self, &leak)
     }
 
     pub fn Erf(&self) -> Tensor {
@@ -165,12 +163,10 @@ impl Tensor {
         let alpha = Tensor::scalar(alpha);
 
         let leak = alpha.Multiply(self);
-        let mask = self.LessOrEqual(&zero);
-        let masked_leak = leak.Multiply(&mask);
 
         // z if z > 0
         // alpha * z if z <= 0
-        self.Relu().Add(&masked_leak)
+        zero.LessThan(self).Where(self, &leak)
     }
 
     pub fn Log(&self) -> Tensor {
@@ -201,7 +197,7 @@ impl Tensor {
         let alpha = Tensor::scalar(alpha);
 
         // PReLU(x) = max(0,x) + a∗min(0,x)
-        self.Relu().Add(&alpha.Multiply(&zero.Minimum(self)))
+        zero.LessThan(self).Where(self, &alpha.Multiply(self))
     }
 
     pub fn Recip(&self) -> Tensor {
@@ -211,7 +207,7 @@ impl Tensor {
 
     pub fn Relu(&self) -> Tensor {
         let zero = Tensor::scalar(0);
-        self.Maximum(&zero)
+        zero.LessThan(self).Where(self, &zero)
     }
 
     pub fn Sec(&self) -> Tensor {
@@ -287,8 +283,24 @@ impl Tensor {
         self.Sum(axes, keep_dims).Divide(&n)
     }
 
-    pub fn Min(&self, axes: &[ViewType], keep_dims: bool) -> Tensor {
-        self.Neg().Max(axes, keep_dims).Neg()
+    // NaN elements are skipped, lines which are entirely NaN yield NaN except for NanSum
+    pub fn NanSum(&self, axes: &[ViewType], keep_dims: bool) -> Tensor {
+        let zero = Tensor::scalar(0);
+        self.IsNaN().Where(&zero, self).Sum(axes, keep_dims)
+    }
+
+    pub fn NanMean(&self, axes: &[ViewType], keep_dims: bool) -> Tensor {
+        let n = self.IsNaN().Not().Sum(axes, keep_dims);
+        self.NanSum(axes, keep_dims).Divide(&n)
+    }
+
+    pub fn NanMax(&self, axes: &[ViewType], keep_dims: bool) -> Tensor {
+        let nan = Tensor::scalar(f32::NAN);
+        let neg_inf = Tensor::scalar(f32::NEG_INFINITY);
+
+        let n = self.IsNaN().Not().Sum(axes, keep_dims);
+        let max = self.IsNaN().Where(&neg_inf, self).Max(axes, keep_dims);
+        n.Where(&max, &nan)
     }
 
     pub fn L1(&self, axes: &[ViewType], keep_dims: bool) -> Tensor {
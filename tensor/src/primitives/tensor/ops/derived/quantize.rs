@@ -0,0 +1,209 @@
+use crate::primitives::tensor::{ConvPadding, Tensor, TensorType};
+use crate::primitives::tensorview::{TensorView, ViewType};
+
+// Real values are (quantized - zero_point) * scale, where the parameters are scalars for the whole
+// tensor or vectors along one of its axes
+#[derive(Clone, Copy, Debug)]
+pub struct Quantization<'a> {
+    pub scale: &'a Tensor,
+    pub zero_point: Option<&'a Tensor>,
+}
+
+impl<'a> Quantization<'a> {
+    // Quantized values take the type of the zero point, which defaults to U8
+    pub fn datatype(&self) -> TensorType {
+        self.zero_point
+            .map(|zero_point| zero_point.datatype())
+            .unwrap_or(TensorType::U8)
+    }
+}
+
+// Window of the integer convolutions, passed through to Conv
+#[derive(Clone, Copy, Debug)]
+pub struct ConvConfig<'a> {
+    pub kernel_strides: &'a [ViewType],
+    pub dilations: &'a [ViewType],
+    pub group: ViewType,
+    pub padding: ConvPadding<'a>,
+}
+
+impl Tensor {
+    // saturate(round(x / scale) + zero_point), with halfway cases rounded to even
+    pub fn QuantizeLinear(
+        &self,
+        quantization: Quantization,
+        axis: ViewType,
+        datatype: TensorType,
+    ) -> Tensor {
+        let (min, max) = match datatype {
+            TensorType::I8 => (-128.0, 127.0),
+            TensorType::U8 => (0.0, 255.0),
+            _ => panic!(
+                "QuantizeLinear expects an 8-bit integer type, found {:?}",
+                datatype
+            ),
+        };
+
+        let ndim = self.ndim();
+        let scale = along(&quantization.scale.Cast(TensorType::F32), axis, ndim);
+        let quantized = round_half_to_even(&self.Cast(TensorType::F32).Divide(&scale));
+        let quantized = match quantization.zero_point {
+            Some(zero_point) => {
+                quantized.Add(&along(&zero_point.Cast(TensorType::F32), axis, ndim))
+            }
+            None => quantized,
+        };
+
+        quantized
+            .Clip(&Tensor::scalar(min), &Tensor::scalar(max))
+            .Cast(datatype)
+    }
+
+    pub fn DequantizeLinear(&self, quantization: Quantization, axis: ViewType) -> Tensor {
+        let scale = along(&quantization.scale.Cast(TensorType::F32), axis, self.ndim());
+
+        centered(self, quantization.zero_point, axis)
+            .Cast(TensorType::F32)
+            .Multiply(&scale)
+    }
+
+    // Products are accumulated as I32, zero points are scalars or vectors over the rows of self
+    // and the columns of rhs
+    pub fn MatMulInteger(
+        &self,
+        rhs: &Tensor,
+        zero_point: Option<&Tensor>,
+        rhs_zero_point: Option<&Tensor>,
+    ) -> Tensor {
+        let lhs = centered(self, zero_point, self.ndim().max(2) - 2);
+        let rhs = centered(rhs, rhs_zero_point, rhs.ndim() - 1);
+
+        lhs.MatMul(&rhs)
+    }
+
+    // Products are accumulated as I32, the kernel zero point is a scalar or a vector over the
+    // output channels. Zeros padded after centering stand in for the input zero point
+    pub fn ConvInteger(
+        &self,
+        kernel: &Tensor,
+        zero_point: Option<&Tensor>,
+        kernel_zero_point: Option<&Tensor>,
+        config: ConvConfig,
+    ) -> Tensor {
+        centered(self, zero_point, 1).Conv(
+            &centered(kernel, kernel_zero_point, 0),
+            config.kernel_strides,
+            config.dilations,
+            config.group,
+            config.padding,
+        )
+    }
+
+    // The integer product is rescaled by both input scales and quantized to the output parameters
+    pub fn QLinearMatMul(
+        &self,
+        quantization: Quantization,
+        rhs: &Tensor,
+        rhs_quantization: Quantization,
+        output_quantization: Quantization,
+    ) -> Tensor {
+        let product = self.MatMulInteger(rhs, quantization.zero_point, rhs_quantization.zero_point);
+
+        let ndim = product.ndim();
+        let scale = along(
+            &quantization.scale.Cast(TensorType::F32),
+            ndim.max(2) - 2,
+            ndim,
+        )
+        .Multiply(&along(
+            &rhs_quantization.scale.Cast(TensorType::F32),
+            ndim - 1,
+            ndim,
+        ));
+
+        product
+            .Cast(TensorType::F32)
+            .Multiply(&scale)
+            .QuantizeLinear(
+                output_quantization,
+                ndim - 1,
+                output_quantization.datatype(),
+            )
+    }
+
+    // The I32 bias is added to the integer accumulator, which shares the scale of the product
+    pub fn QLinearConv(
+        &self,
+        quantization: Quantization,
+        kernel: &Tensor,
+        kernel_quantization: Quantization,
+        output_quantization: Quantization,
+        bias: Option<&Tensor>,
+        config: ConvConfig,
+    ) -> Tensor {
+        let accumulator = self.ConvInteger(
+            kernel,
+            quantization.zero_point,
+            kernel_quantization.zero_point,
+            config,
+        );
+
+        let ndim = accumulator.ndim();
+        let accumulator = match bias {
+            Some(bias) => accumulator.Add(&along(&bias.Cast(TensorType::I32), 1, ndim)),
+            None => accumulator,
+        };
+        let scale = quantization.scale.Cast(TensorType::F32).Multiply(&along(
+            &kernel_quantization.scale.Cast(TensorType::F32),
+            1,
+            ndim,
+        ));
+
+        accumulator
+            .Cast(TensorType::F32)
+            .Multiply(&scale)
+            .QuantizeLinear(output_quantization, 1, output_quantization.datatype())
+    }
+}
+
+// Scalars apply to every element, vectors to every index along the axis
+fn along(tensor: &Tensor, axis: ViewType, ndim: ViewType) -> Tensor {
+    if tensor.len() == 1 {
+        return tensor.clone();
+    }
+
+    let shape = (0..ndim)
+        .map(|dimension| match dimension == axis {
+            true => tensor.len(),
+            false => 1,
+        })
+        .collect::<Vec<_>>();
+    tensor.reshape(&TensorView::from_contiguous_shape(&shape[..]))
+}
+
+// Integers relative to their zero point, widened so products do not overflow
+fn centered(tensor: &Tensor, zero_point: Option<&Tensor>, axis: ViewType) -> Tensor {
+    let tensor = tensor.Cast(TensorType::I32);
+    match zero_point {
+        Some(zero_point) => tensor.Sub(&along(
+            &zero_point.Cast(TensorType::I32),
+            axis,
+            tensor.ndim(),
+        )),
+        None => tensor,
+    }
+}
+
+// x + 0.5 rounded down is off by one when x was halfway below an odd integer
+fn round_half_to_even(tensor: &Tensor) -> Tensor {
+    let rounded = tensor.Add(&Tensor::scalar(0.5)).Floor();
+    let halfway = rounded.Sub(tensor).Equal(&Tensor::scalar(0.5));
+    let odd = rounded
+        .Mod(&Tensor::scalar(2.0))
+        .Equal(&Tensor::scalar(0.0))
+        .Not();
+
+    halfway
+        .And(&odd)
+        .Where(&rounded.Sub(&Tensor::scalar(1.0)), &rounded)
+}
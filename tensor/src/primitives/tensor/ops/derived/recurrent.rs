@@ -0,0 +1,378 @@
+use crate::primitives::tensor::{Tensor, TensorType};
+use crate::primitives::tensorview::{TensorView, ViewType};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RecurrentDirection {
+    Forward,
+    Reverse,
+    Bidirectional,
+}
+
+impl RecurrentDirection {
+    fn directions(&self) -> usize {
+        match self {
+            RecurrentDirection::Forward | RecurrentDirection::Reverse => 1,
+            RecurrentDirection::Bidirectional => 2,
+        }
+    }
+
+    fn is_reverse(&self, direction: usize) -> bool {
+        match self {
+            RecurrentDirection::Forward => false,
+            RecurrentDirection::Reverse => true,
+            RecurrentDirection::Bidirectional => direction == 1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum RecurrentActivation {
+    Affine(f32, f32),
+    Elu(f32),
+    HardSigmoid(f32, f32),
+    LeakyRelu(f32),
+    Relu,
+    ScaledTanh(f32, f32),
+    Sigmoid,
+    Softplus,
+    Softsign,
+    Tanh,
+    ThresholdedRelu(f32),
+}
+
+impl RecurrentActivation {
+    fn apply(&self, input: &Tensor) -> Tensor {
+        let zero = Tensor::scalar(0.0);
+        let one = Tensor::scalar(1.0);
+
+        match *self {
+            RecurrentActivation::Affine(alpha, beta) => input
+                .Multiply(&Tensor::scalar(alpha))
+                .Add(&Tensor::scalar(beta)),
+            RecurrentActivation::Elu(alpha) => input.Elu(alpha),
+            RecurrentActivation::HardSigmoid(alpha, beta) => input
+                .Multiply(&Tensor::scalar(alpha))
+                .Add(&Tensor::scalar(beta))
+                .Clip(&zero, &one),
+            RecurrentActivation::LeakyRelu(alpha) => input.LeakyRelu(alpha),
+            RecurrentActivation::Relu => input.Relu(),
+            RecurrentActivation::ScaledTanh(alpha, beta) => input
+                .Multiply(&Tensor::scalar(beta))
+                .Tanh()
+                .Multiply(&Tensor::scalar(alpha)),
+            RecurrentActivation::Sigmoid => input.Sigmoid(),
+            RecurrentActivation::Softplus => input.Exp().Add(&one).Log(),
+            RecurrentActivation::Softsign => input.Divide(&input.Abs().Add(&one)),
+            RecurrentActivation::Tanh => input.Tanh(),
+            RecurrentActivation::ThresholdedRelu(alpha) => {
+                Tensor::scalar(alpha).LessThan(input).Where(input, &zero)
+            }
+        }
+    }
+}
+
+// Parameters shared by the recurrent layers, following ONNX with weights W of shape
+// [num_directions, gates * hidden_size, input_size], R of shape [num_directions,
+// gates * hidden_size, hidden_size] and biases B of shape [num_directions, 2 * gates * hidden_size]
+#[derive(Clone, Copy, Debug)]
+pub struct RecurrentConfig<'a> {
+    pub input_weights: &'a Tensor,
+    pub recurrent_weights: &'a Tensor,
+    pub bias: Option<&'a Tensor>,
+    pub sequence_lens: Option<&'a Tensor>,
+    pub direction: RecurrentDirection,
+    pub activations: &'a [RecurrentActivation],
+    pub clip: Option<f32>,
+}
+
+impl Tensor {
+    // Inputs are [seq_length, batch_size, input_size]
+    pub fn RNN(&self, config: RecurrentConfig, initial_h: Option<&Tensor>) -> (Tensor, Tensor) {
+        let activations = recurrent_activations::<1>("RNN", config.activations, config.direction);
+        let clip = config.clip;
+
+        let (Y, mut states) = self.recurrent(
+            &config,
+            &[initial_h],
+            1,
+            |direction, projection, states, R, R_bias| {
+                let [f] = activations[direction];
+
+                // Ht = f(Xt*(Wi^T) + Ht-1*(Ri^T) + Wbi + Rbi)
+                let input_gate = projection.Add(&states[0].MatMul(R)).Add(R_bias);
+                vec![f.apply(&clip_gate(&input_gate, clip))]
+            },
+        );
+
+        (Y, states.remove(0))
+    }
+
+    // Gates of W, R and B are ordered as input, output, forget and cell, peepholes P of shape
+    // [num_directions, 3 * hidden_size] as input, output and forget
+    pub fn LSTM(
+        &self,
+        config: RecurrentConfig,
+        initial_h: Option<&Tensor>,
+        initial_c: Option<&Tensor>,
+        P: Option<&Tensor>,
+        input_forget: bool,
+    ) -> (Tensor, Tensor, Tensor) {
+        let activations = recurrent_activations::<3>("LSTM", config.activations, config.direction);
+        let clip = config.clip;
+        let hidden_size = config.recurrent_weights.shape()[2];
+        let peepholes = (0..config.direction.directions())
+            .map(|direction| match P {
+                Some(P) => direction_slice(P, direction)
+                    .Split(0, &[hidden_size; 3])
+                    .into_iter()
+                    .map(|peephole| peephole.view_as(&[1, hidden_size]))
+                    .collect::<Vec<_>>(),
+                None => vec![Tensor::scalar(0.0); 3],
+            })
+            .collect::<Vec<_>>();
+        let one = Tensor::scalar(1.0);
+
+        let (Y, mut states) = self.recurrent(
+            &config,
+            &[initial_h, initial_c],
+            4,
+            |direction, projection, states, R, R_bias| {
+                let [f, g, h] = activations[direction];
+                let [P_i, P_o, P_f] = &peepholes[direction][..] else {
+                    unreachable!()
+                };
+                let (H, C) = (&states[0], &states[1]);
+
+                let gates = projection.Add(&H.MatMul(R)).Add(R_bias);
+                let gates = gates.Split(1, &[hidden_size; 4]);
+                let [input_gate, output_gate, forget_gate, cell_gate] = &gates[..] else {
+                    unreachable!()
+                };
+
+                // it = f(Xt*(Wi^T) + Ht-1*(Ri^T) + Pi (.) Ct-1 + Wbi + Rbi)
+                // ft = f(Xt*(Wf^T) + Ht-1*(Rf^T) + Pf (.) Ct-1 + Wbf + Rbf)
+                // ct = g(Xt*(Wc^T) + Ht-1*(Rc^T) + Wbc + Rbc)
+                let i = f.apply(&clip_gate(&input_gate.Add(&P_i.Multiply(C)), clip));
+                let forget = match input_forget {
+                    true => one.Sub(&i),
+                    false => f.apply(&clip_gate(&forget_gate.Add(&P_f.Multiply(C)), clip)),
+                };
+                let c = g.apply(&clip_gate(cell_gate, clip));
+
+                // Ct = ft (.) Ct-1 + it (.) ct
+                // ot = f(Xt*(Wo^T) + Ht-1*(Ro^T) + Po (.) Ct + Wbo + Rbo)
+                // Ht = ot (.) h(Ct)
+                let C = forget.Multiply(C).Add(&i.Multiply(&c));
+                let o = f.apply(&clip_gate(&output_gate.Add(&P_o.Multiply(&C)), clip));
+                vec![o.Multiply(&h.apply(&C)), C]
+            },
+        );
+
+        let Y_c = states.remove(1);
+        (Y, states.remove(0), Y_c)
+    }
+
+    // Gates of W, R and B are ordered as update, reset and hidden
+    pub fn GRU(
+        &self,
+        config: RecurrentConfig,
+        initial_h: Option<&Tensor>,
+        linear_before_reset: bool,
+    ) -> (Tensor, Tensor) {
+        let activations = recurrent_activations::<2>("GRU", config.activations, config.direction);
+        let clip = config.clip;
+        let hidden_size = config.recurrent_weights.shape()[2];
+        let one = Tensor::scalar(1.0);
+
+        let (Y, mut states) = self.recurrent(
+            &config,
+            &[initial_h],
+            3,
+            |direction, projection, states, R, R_bias| {
+                let [f, g] = activations[direction];
+                let H = &states[0];
+
+                let projections = projection.Split(1, &[hidden_size; 3]);
+                let Rs = R.Split(1, &[hidden_size; 3]);
+                let R_biases = R_bias.Split(1, &[hidden_size; 3]);
+
+                // zt = f(Xt*(Wz^T) + Ht-1*(Rz^T) + Wbz + Rbz)
+                // rt = f(Xt*(Wr^T) + Ht-1*(Rr^T) + Wbr + Rbr)
+                let z = f.apply(&clip_gate(
+                    &projections[0].Add(&H.MatMul(&Rs[0])).Add(&R_biases[0]),
+                    clip,
+                ));
+                let r = f.apply(&clip_gate(
+                    &projections[1].Add(&H.MatMul(&Rs[1])).Add(&R_biases[1]),
+                    clip,
+                ));
+
+                // ht = g(Xt*(Wh^T) + (rt (.) Ht-1)*(Rh^T) + Rbh + Wbh)
+                // ht = g(Xt*(Wh^T) + (rt (.) (Ht-1*(Rh^T) + Rbh)) + Wbh) if linear_before_reset
+                let hidden_gate = match linear_before_reset {
+                    true => r.Multiply(&H.MatMul(&Rs[2]).Add(&R_biases[2])),
+                    false => r.Multiply(H).MatMul(&Rs[2]).Add(&R_biases[2]),
+                };
+                let h = g.apply(&clip_gate(&projections[2].Add(&hidden_gate), clip));
+
+                // Ht = (1 - zt) (.) ht + zt (.) Ht-1
+                vec![one.Sub(&z).Multiply(&h).Add(&z.Multiply(H))]
+            },
+        );
+
+        (Y, states.remove(0))
+    }
+
+    // Unrolls a cell over every step of every direction, returning the hidden states of all
+    // steps [seq_length, num_directions, batch_size, hidden_size] and the final states
+    // [num_directions, batch_size, hidden_size], of which the hidden state is the first
+    fn recurrent<F>(
+        &self,
+        config: &RecurrentConfig,
+        initial_states: &[Option<&Tensor>],
+        gates: ViewType,
+        cell: F,
+    ) -> (Tensor, Vec<Tensor>)
+    where
+        F: Fn(usize, &Tensor, &[Tensor], &Tensor, &Tensor) -> Vec<Tensor>,
+    {
+        let (W, R, B) = (config.input_weights, config.recurrent_weights, config.bias);
+        let (sequence_lens, direction) = (config.sequence_lens, config.direction);
+        let [seq_length, batch_size, _] = *self.shape() else {
+            panic!("Recurrent layers expect an input signature of seq_length x batch_size x input_size")
+        };
+        let [num_directions, _, hidden_size] = *R.shape() else {
+            panic!("Recurrent layers expect R of shape num_directions x gates * hidden_size x hidden_size")
+        };
+        assert!(
+            num_directions as usize == direction.directions(),
+            "{:?} recurrent layers expect {} directions, got {}",
+            direction,
+            direction.directions(),
+            num_directions
+        );
+
+        let zero = Tensor::scalar(0.0);
+        let state_view = TensorView::from_contiguous_shape(&[batch_size, hidden_size]);
+        let input = self.Cast(TensorType::F32);
+
+        // Steps beyond the length of a sequence keep their state and output zeros
+        let step_masks = (0..seq_length)
+            .map(|step| {
+                sequence_lens.map(|sequence_lens| {
+                    Tensor::scalar(step)
+                        .LessThan(sequence_lens)
+                        .view_as(&[batch_size, 1])
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let (outputs, states): (Vec<_>, Vec<_>) = (0..direction.directions())
+            .map(|d| {
+                // Input projections of all steps are computed at once, [seq_length, batch_size, gates * hidden_size]
+                let W = direction_slice(W, d).transpose(&[1, 0]);
+                let R = direction_slice(R, d).transpose(&[1, 0]);
+                let (W_bias, R_bias) = match B {
+                    Some(B) => {
+                        let biases = direction_slice(B, d).Split(0, &[gates * hidden_size; 2]);
+                        (biases[0].view_as(&[1, -1]), biases[1].view_as(&[1, -1]))
+                    }
+                    None => {
+                        let bias_view =
+                            TensorView::from_contiguous_shape(&[1, gates * hidden_size]);
+                        (zero.broadcast_to(&bias_view), zero.broadcast_to(&bias_view))
+                    }
+                };
+                let projections = input
+                    .MatMul(&W)
+                    .Add(&W_bias)
+                    .Split(0, &vec![1; seq_length as usize][..])
+                    .into_iter()
+                    .map(|projection| projection.squeeze(0))
+                    .collect::<Vec<_>>();
+
+                let initial_states = initial_states
+                    .iter()
+                    .map(|initial_state| match initial_state {
+                        Some(initial_state) => direction_slice(initial_state, d),
+                        None => zero.broadcast_to(&state_view),
+                    })
+                    .collect::<Vec<_>>();
+
+                let steps = match direction.is_reverse(d) {
+                    true => (0..seq_length as usize).rev().collect::<Vec<_>>(),
+                    false => (0..seq_length as usize).collect::<Vec<_>>(),
+                };
+                let mut outputs = vec![None; seq_length as usize];
+                let states = steps.into_iter().fold(initial_states, |states, step| {
+                    let updated_states = cell(d, &projections[step], &states[..], &R, &R_bias);
+                    let (states, output) = match &step_masks[step] {
+                        Some(mask) => (
+                            updated_states
+                                .iter()
+                                .zip(states.iter())
+                                .map(|(updated_state, state)| mask.Where(updated_state, state))
+                                .collect::<Vec<_>>(),
+                            mask.Where(&updated_states[0], &zero),
+                        ),
+                        None => (updated_states.clone(), updated_states[0].clone()),
+                    };
+                    outputs[step] = Some(output);
+                    states
+                });
+
+                let outputs = outputs.into_iter().flatten().collect::<Vec<_>>();
+                (Tensor::Stack(&outputs[..], 0), states)
+            })
+            .unzip();
+
+        let states = (0..initial_states.len())
+            .map(|state| {
+                let direction_states = states
+                    .iter()
+                    .map(|direction_states| direction_states[state].clone())
+                    .collect::<Vec<_>>();
+                Tensor::Stack(&direction_states[..], 0)
+            })
+            .collect::<Vec<_>>();
+        (Tensor::Stack(&outputs[..], 1), states)
+    }
+}
+
+// Activations are either shared by all directions or given for every direction
+fn recurrent_activations<const N: usize>(
+    layer: &str,
+    activations: &[RecurrentActivation],
+    direction: RecurrentDirection,
+) -> Vec<[RecurrentActivation; N]> {
+    let directions = direction.directions();
+    assert!(
+        activations.len() == N || activations.len() == N * directions,
+        "{} expects {} or {} activations, got {}",
+        layer,
+        N,
+        N * directions,
+        activations.len()
+    );
+
+    (0..directions)
+        .map(|direction| {
+            let offset = (direction * N) % activations.len();
+            activations[offset..offset + N].try_into().unwrap()
+        })
+        .collect::<Vec<_>>()
+}
+
+// Pre-activations are optionally clipped to [-clip, clip]
+fn clip_gate(gate: &Tensor, clip: Option<f32>) -> Tensor {
+    match clip {
+        Some(clip) => gate.Clip(&Tensor::scalar(-clip), &Tensor::scalar(clip)),
+        None => gate.clone(),
+    }
+}
+
+// Parameters of a single direction, with the leading num_directions axis removed
+fn direction_slice(parameter: &Tensor, direction: usize) -> Tensor {
+    let num_directions = parameter.shape()[0];
+    parameter.Split(0, &vec![1; num_directions as usize][..])[direction].squeeze(0)
+}
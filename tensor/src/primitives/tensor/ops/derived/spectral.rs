@@ -0,0 +1,292 @@
+use std::f64::consts::PI;
+
+use crate::primitives::tensor::{Tensor, TensorType};
+use crate::primitives::tensorview::{TensorView, ViewType};
+
+impl Tensor {
+    pub fn BlackmanWindow(size: ViewType, periodic: bool) -> Tensor {
+        cosine_window(size, periodic, &[0.42, 0.5, 0.08])
+    }
+
+    pub fn HammingWindow(size: ViewType, periodic: bool) -> Tensor {
+        cosine_window(size, periodic, &[25.0 / 46.0, 21.0 / 46.0])
+    }
+
+    pub fn HannWindow(size: ViewType, periodic: bool) -> Tensor {
+        cosine_window(size, periodic, &[0.5, 0.5])
+    }
+
+    // Input is [..., 1] for real or [..., 2] for complex signals, the output is always complex.
+    // The signal is zero padded or truncated to length along the axis before the transform
+    pub fn DFT(
+        &self,
+        axis: ViewType,
+        length: Option<ViewType>,
+        inverse: bool,
+        onesided: bool,
+    ) -> Tensor {
+        let ndim = self.ndim();
+        let signal_ndim = ndim - 1;
+        let [.., components] = self.shape() else {
+            panic!("DFT requires input to be at least 1D");
+        };
+        assert!(
+            *components == 1 || *components == 2,
+            "DFT expects a last dimension of 1 (real) or 2 (complex), found {:?}",
+            self.shape()
+        );
+        let axis = (axis + ndim) % ndim;
+        assert!(
+            axis < signal_ndim,
+            "DFT axis {} is out of bounds, 0 <= axis < {}",
+            axis,
+            signal_ndim
+        );
+
+        let parts = self
+            .Cast(TensorType::F32)
+            .Split(signal_ndim, &vec![1; *components as usize][..])
+            .iter()
+            .map(|part| part.squeeze(signal_ndim))
+            .collect::<Vec<_>>();
+        let real = parts[0].clone();
+        let imaginary = match parts.get(1) {
+            Some(imaginary) => imaginary.clone(),
+            None => Tensor::scalar(0.0).broadcast_to(real.view()),
+        };
+
+        // The transformed axis is moved last
+        let permutation = (0..signal_ndim)
+            .filter(|&other| other != axis)
+            .chain([axis])
+            .collect::<Vec<_>>();
+        let mut inverse_permutation = vec![0; permutation.len()];
+        permutation
+            .iter()
+            .enumerate()
+            .for_each(|(position, &other)| {
+                inverse_permutation[other as usize] = position as ViewType
+            });
+
+        let n = self.shape()[axis as usize];
+        let length = length.unwrap_or(n);
+        let resize = |tensor: &Tensor| {
+            let tensor = tensor.transpose(&permutation);
+            if length < n {
+                tensor.Split(signal_ndim - 1, &[length, n - length])[0].clone()
+            } else if length > n {
+                let mut padding_shape = tensor.shape().to_vec();
+                padding_shape[signal_ndim as usize - 1] = length - n;
+                let padding = Tensor::scalar(0.0)
+                    .broadcast_to(&TensorView::from_contiguous_shape(&padding_shape[..]));
+                Tensor::Concat(&[tensor, padding], signal_ndim - 1)
+            } else {
+                tensor
+            }
+        };
+
+        let (real, imaginary) = fft(&resize(&real), &resize(&imaginary), inverse);
+        let (real, imaginary) = match inverse {
+            true => {
+                let scale = Tensor::scalar(1.0 / length as f32);
+                (real.Multiply(&scale), imaginary.Multiply(&scale))
+            }
+            false => (real, imaginary),
+        };
+
+        // Only the non-negative frequencies of the conjugate symmetric spectrum are kept
+        let bins = match onesided {
+            true => length / 2 + 1,
+            false => length,
+        };
+        let truncate = |tensor: Tensor| match bins < length {
+            true => tensor.Split(signal_ndim - 1, &[bins, length - bins])[0].clone(),
+            false => tensor,
+        };
+
+        Tensor::Stack(
+            &[
+                truncate(real).transpose(&inverse_permutation),
+                truncate(imaginary).transpose(&inverse_permutation),
+            ],
+            signal_ndim,
+        )
+    }
+
+    // Signal [batch, signal_length, 1 | 2] is split into frames of frame_length every
+    // frame_step samples, yielding the spectrum [batch, frames, bins, 2] of each windowed frame
+    pub fn STFT(
+        &self,
+        frame_step: ViewType,
+        window: Option<&Tensor>,
+        frame_length: Option<ViewType>,
+        onesided: bool,
+    ) -> Tensor {
+        assert!(
+            self.ndim() == 3,
+            "STFT expects a signal of shape [batch, signal_length, 1 | 2], found {:?}",
+            self.shape()
+        );
+
+        let frame_length = match (window, frame_length) {
+            (Some(window), Some(frame_length)) => {
+                assert!(
+                    window.shape() == [frame_length],
+                    "STFT window {:?} does not match the frame length {}",
+                    window.shape(),
+                    frame_length
+                );
+                frame_length
+            }
+            (Some(window), None) => window.shape()[0],
+            (None, Some(frame_length)) => frame_length,
+            (None, None) => panic!("STFT expects either a window or a frame length"),
+        };
+
+        // Frames slide over the signal axis, which is unfolded as the trailing axis
+        let frames = self
+            .transpose(&[0, 2, 1])
+            .Unfold(&[frame_length], &[frame_step], &[1])
+            .transpose(&[0, 2, 3, 1]);
+        let frames = match window {
+            Some(window) => frames.Multiply(&window.unsqueeze(1)),
+            None => frames,
+        };
+
+        frames.DFT(2, None, false, onesided)
+    }
+}
+
+// Generalized cosine window, sum of (-1)^k a_k cos(2 pi k n / N) where a periodic window of
+// size N is the symmetric window of size N + 1 without its last sample
+fn cosine_window(size: ViewType, periodic: bool, coefficients: &[f64]) -> Tensor {
+    let period = match periodic {
+        true => size,
+        false => size - 1,
+    }
+    .max(1);
+
+    let data = (0..size)
+        .map(|n| {
+            coefficients
+                .iter()
+                .enumerate()
+                .map(|(k, a)| {
+                    let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+                    sign * a * (2.0 * PI * (k as f64) * (n as f64) / (period as f64)).cos()
+                })
+                .sum::<f64>() as f32
+        })
+        .collect::<Vec<_>>();
+    Tensor::from_contiguous(&data[..], &[size])
+}
+
+// exp(-2 pi i jk / n) in row j and column k, conjugated for the inverse
+fn roots_of_unity(
+    rows: ViewType,
+    columns: ViewType,
+    n: ViewType,
+    inverse: bool,
+) -> (Tensor, Tensor) {
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let (real, imaginary): (Vec<_>, Vec<_>) = (0..rows)
+        .flat_map(|j| (0..columns).map(move |k| (j * k) % n))
+        .map(|jk| {
+            let angle = 2.0 * PI * (jk as f64) / (n as f64);
+            (angle.cos() as f32, (sign * angle.sin()) as f32)
+        })
+        .unzip();
+
+    (
+        Tensor::from_contiguous(&real[..], &[rows, columns]),
+        Tensor::from_contiguous(&imaginary[..], &[rows, columns]),
+    )
+}
+
+// Transforms the last axis, splitting it by its smallest prime factor (decimation in time)
+// until the remaining length is prime
+fn fft(real: &Tensor, imaginary: &Tensor, inverse: bool) -> (Tensor, Tensor) {
+    let ndim = real.ndim();
+    let n = real.shape()[ndim as usize - 1];
+    let Some(radix) = (2..=n).find(|radix| n % radix == 0) else {
+        return (real.clone(), imaginary.clone());
+    };
+
+    // Prime lengths, including odd lengths without smaller factors, fall back to the matrix DFT
+    if radix == n {
+        let (matrix_real, matrix_imaginary) = roots_of_unity(n, n, n, inverse);
+        let (real, imaginary) = complex_matmul(
+            (&matrix_real, &matrix_imaginary),
+            (&real.unsqueeze(ndim), &imaginary.unsqueeze(ndim)),
+        );
+        return (real.squeeze(ndim), imaginary.squeeze(ndim));
+    }
+
+    // x[j1 * radix + j2] is transformed over j1 into y[j2, k1] for every j2
+    let m = n / radix;
+    let batch = vec![0; ndim as usize - 1];
+    let decimated = |tensor: &Tensor| {
+        let permutation = (0..ndim - 1).chain([ndim, ndim - 1]).collect::<Vec<_>>();
+        tensor
+            .view_as(&[&batch[..], &[m, radix]].concat())
+            .transpose(&permutation)
+    };
+    let (real, imaginary) = fft(&decimated(real), &decimated(imaginary), inverse);
+
+    // Twiddle factors w_n^(j2 k1) before the transform of length radix over j2
+    let (twiddle_real, twiddle_imaginary) = roots_of_unity(radix, m, n, inverse);
+    let (real, imaginary) =
+        complex_multiply((&real, &imaginary), (&twiddle_real, &twiddle_imaginary));
+
+    // X[k2 * m + k1] for the output frequencies k2 of the radix transform
+    let (real, imaginary) = match radix {
+        // Butterfly of y[0, k1] +- y[1, k1]
+        2 => {
+            let butterfly = |tensor: &Tensor| {
+                let halves = tensor.Split(ndim - 1, &[1, 1]);
+                Tensor::Concat(
+                    &[halves[0].Add(&halves[1]), halves[0].Sub(&halves[1])],
+                    ndim - 1,
+                )
+            };
+            (butterfly(&real), butterfly(&imaginary))
+        }
+        _ => {
+            let (matrix_real, matrix_imaginary) = roots_of_unity(radix, radix, radix, inverse);
+            complex_matmul((&matrix_real, &matrix_imaginary), (&real, &imaginary))
+        }
+    };
+
+    (
+        real.view_as(&[&batch[..], &[n]].concat()),
+        imaginary.view_as(&[&batch[..], &[n]].concat()),
+    )
+}
+
+fn complex_multiply(
+    (lhs_real, lhs_imaginary): (&Tensor, &Tensor),
+    (rhs_real, rhs_imaginary): (&Tensor, &Tensor),
+) -> (Tensor, Tensor) {
+    (
+        lhs_real
+            .Multiply(rhs_real)
+            .Sub(&lhs_imaginary.Multiply(rhs_imaginary)),
+        lhs_real
+            .Multiply(rhs_imaginary)
+            .Add(&lhs_imaginary.Multiply(rhs_real)),
+    )
+}
+
+fn complex_matmul(
+    (lhs_real, lhs_imaginary): (&Tensor, &Tensor),
+    (rhs_real, rhs_imaginary): (&Tensor, &Tensor),
+) -> (Tensor, Tensor) {
+    (
+        lhs_real
+            .MatMul(rhs_real)
+            .Sub(&lhs_imaginary.MatMul(rhs_imaginary)),
+        lhs_real
+            .MatMul(rhs_imaginary)
+            .Add(&lhs_imaginary.MatMul(rhs_real)),
+    )
+}
@@ -0,0 +1,49 @@
+use crate::primitives::tensor::{Tensor, TensorType};
+use crate::primitives::tensorview::TensorView;
+
+use super::{OperationSpec, TensorInput};
+
+#[derive(Clone, Debug)]
+pub struct LUSpec {
+    // [..., n, n]
+    pub input: Tensor,
+    // Rows are exchanged so the largest remaining element of each column is the pivot
+    pub pivoting: bool,
+}
+
+impl TensorInput {
+    pub fn lu(input: Tensor, pivoting: bool) -> TensorInput {
+        TensorInput::OperationResult(OperationSpec::LUOp(LUSpec { input, pivoting }))
+    }
+}
+
+impl Tensor {
+    // PA = LU, where the unit lower triangular L and upper triangular U are packed into the
+    // factors [..., n, n] and row i of PA is row permutation[i] of A
+    pub fn LU(&self, pivoting: bool) -> (Tensor, Tensor) {
+        let [batch_size @ .., n, m] = self.shape() else {
+            panic!("LU requires input to be at least 2D");
+        };
+        assert!(
+            n == m,
+            "LU expects square matrices, found {:?}",
+            self.shape()
+        );
+
+        // The original row of every factor row is carried as an extra column through the
+        // row exchanges
+        let output_view = TensorView::from_contiguous_shape(&[batch_size, &[*n, n + 1]].concat());
+        let augmented = Tensor::new(
+            output_view,
+            TensorInput::lu(self.Cast(TensorType::F32), pivoting),
+            TensorType::F32,
+        );
+
+        let ndim = self.ndim();
+        let parts = augmented.Split(ndim - 1, &[*n, 1]);
+        (
+            parts[0].clone(),
+            parts[1].squeeze(ndim - 1).Cast(TensorType::I32),
+        )
+    }
+}
@@ -0,0 +1,85 @@
+use crate::primitives::tensor::Tensor;
+use crate::primitives::tensorview::{TensorView, ViewType};
+
+use super::{OperationSpec, TensorInput};
+
+#[derive(Clone, Debug)]
+pub struct MatMulSpec {
+    // [..., n, k]
+    pub lhs: Tensor,
+    // [..., k, m]
+    pub rhs: Tensor,
+}
+
+impl TensorInput {
+    pub fn matmul(lhs: Tensor, rhs: Tensor) -> TensorInput {
+        TensorInput::OperationResult(OperationSpec::MatMulOp(MatMulSpec { lhs, rhs }))
+    }
+}
+
+impl Tensor {
+    pub fn MatMul(&self, other: &Tensor) -> Tensor {
+        let (lhs_dimension, rhs_dimension) = (self.ndim(), other.ndim());
+
+        // Vectors are promoted to matrices and the promoted axis is removed afterwards
+        let input = match self.ndim() {
+            // m -> [1, 1]
+            0 => self.broadcast_to(&TensorView::from_contiguous_shape(&[1, 1])),
+            // [k] -> [1, k]
+            1 => self.unsqueeze(0),
+            // [..., n, k]
+            _ => self.clone(),
+        };
+
+        let other = match other.ndim() {
+            // m -> [1, 1]
+            0 => other.broadcast_to(&TensorView::from_contiguous_shape(&[1, 1])),
+            // [k] -> [k, 1]
+            1 => other.unsqueeze(1),
+            // [..., k, m]
+            _ => other.clone(),
+        };
+
+        let [input_batch_size @ .., n, k] = input.shape() else {
+            panic!("MatMul requires input to be at least 2D");
+        };
+        let [other_batch_size @ .., other_k, m] = other.shape() else {
+            panic!("MatMul requires tensor to be at least 2D");
+        };
+
+        assert!(
+            k == other_k,
+            "Failed to multiply matrices of shape {:?} @ {:?}",
+            self.view(),
+            other.view()
+        );
+
+        // Batch dimensions broadcast against each other
+        let batch_size = TensorView::from_contiguous_shape(input_batch_size)
+            .broadcast(&TensorView::from_contiguous_shape(other_batch_size))
+            .shape;
+        let datatype = input.datatype().agreeable_type(other.datatype());
+        let lhs = input
+            .broadcast_to(&TensorView::from_contiguous_shape(
+                &[&batch_size[..], &[*n, *k]].concat(),
+            ))
+            .Cast(datatype);
+        let rhs = other
+            .broadcast_to(&TensorView::from_contiguous_shape(
+                &[&batch_size[..], &[*k, *m]].concat(),
+            ))
+            .Cast(datatype);
+
+        let output_view = TensorView::from_contiguous_shape(&[&batch_size[..], &[*n, *m]].concat());
+        let output = Tensor::new(output_view, TensorInput::matmul(lhs, rhs), datatype);
+
+        let output = match rhs_dimension {
+            1 => output.squeeze(output.ndim() - 1),
+            _ => output,
+        };
+        match lhs_dimension {
+            1 => output.squeeze(output.ndim() - 1 - (rhs_dimension != 1) as ViewType),
+            _ => output,
+        }
+    }
+}
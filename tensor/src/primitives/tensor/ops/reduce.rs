@@ -6,7 +6,13 @@ use super::{OperationSpec, TensorInput};
 #[derive(Clone, Copy, Debug)]
 pub enum ReduceType {
     SUM,
+    PROD,
     MAX,
+    MIN,
+    ANY,
+    ALL,
+    ARGMAX,
+    ARGMIN,
 }
 
 #[derive(Clone, Debug)]
@@ -80,7 +86,49 @@ impl Tensor {
         self.reduce_op(ReduceType::SUM, axes, keep_dims)
     }
 
+    pub fn Prod(&self, axes: &[ViewType], keep_dims: bool) -> Tensor {
+        self.reduce_op(ReduceType::PROD, axes, keep_dims)
+    }
+
     pub fn Max(&self, axes: &[ViewType], keep_dims: bool) -> Tensor {
         self.reduce_op(ReduceType::MAX, axes, keep_dims)
     }
+
+    pub fn Min(&self, axes: &[ViewType], keep_dims: bool) -> Tensor {
+        self.reduce_op(ReduceType::MIN, axes, keep_dims)
+    }
+
+    pub fn Any(&self, axes: &[ViewType], keep_dims: bool) -> Tensor {
+        self.reduce_op(ReduceType::ANY, axes, keep_dims)
+    }
+
+    pub fn All(&self, axes: &[ViewType], keep_dims: bool) -> Tensor {
+        self.reduce_op(ReduceType::ALL, axes, keep_dims)
+    }
+
+    pub fn ArgMax(&self, axis: ViewType, keep_dims: bool, select_last_index: bool) -> Tensor {
+        self.arg_reduce_op(ReduceType::ARGMAX, axis, keep_dims, select_last_index)
+    }
+
+    pub fn ArgMin(&self, axis: ViewType, keep_dims: bool, select_last_index: bool) -> Tensor {
+        self.arg_reduce_op(ReduceType::ARGMIN, axis, keep_dims, select_last_index)
+    }
+
+    fn arg_reduce_op(
+        &self,
+        op: ReduceType,
+        axis: ViewType,
+        keep_dims: bool,
+        select_last_index: bool,
+    ) -> Tensor {
+        if !select_last_index {
+            return self.reduce_op(op, &[axis], keep_dims);
+        }
+
+        // The first index of the flipped axis is the last index of the original axis
+        let last_index = Tensor::scalar(self.shape()[axis as usize] - 1);
+        let flipped_indices = last_index.Sub(&Tensor::arange(&[self.shape()[axis as usize]]));
+        let flipped = self.Gather(axis, &flipped_indices);
+        last_index.Sub(&flipped.reduce_op(op, &[axis], keep_dims))
+    }
 }
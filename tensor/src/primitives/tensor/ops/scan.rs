@@ -0,0 +1,69 @@
+use crate::primitives::tensor::Tensor;
+use crate::primitives::tensorview::{TensorView, ViewType};
+
+use super::{OperationSpec, TensorInput};
+
+#[derive(Clone, Copy, Debug)]
+pub enum ScanType {
+    SUM,
+    PROD,
+    MAX,
+}
+
+#[derive(Clone, Debug)]
+pub struct ScanSpec {
+    pub op: ScanType,
+    pub input: Tensor,
+    pub axis: ViewType,
+    pub exclusive: bool,
+    pub reverse: bool,
+}
+
+impl TensorInput {
+    pub fn scan(
+        op: ScanType,
+        input: Tensor,
+        axis: ViewType,
+        exclusive: bool,
+        reverse: bool,
+    ) -> TensorInput {
+        TensorInput::OperationResult(OperationSpec::ScanOp(ScanSpec {
+            op,
+            input,
+            axis,
+            exclusive,
+            reverse,
+        }))
+    }
+}
+
+impl Tensor {
+    fn scan_op(&self, op: ScanType, axis: ViewType, exclusive: bool, reverse: bool) -> Tensor {
+        assert!(
+            axis >= 0 && axis < self.ndim(),
+            "Scan axis {} is out of bounds, 0 <= axis < {}",
+            axis,
+            self.ndim()
+        );
+
+        let output_view = TensorView::from_contiguous_shape(self.shape());
+        Tensor::new(
+            output_view,
+            TensorInput::scan(op, self.clone(), axis, exclusive, reverse),
+            self.datatype(),
+        )
+    }
+
+    pub fn CumSum(&self, axis: ViewType, exclusive: bool, reverse: bool) -> Tensor {
+        self.scan_op(ScanType::SUM, axis, exclusive, reverse)
+    }
+
+    pub fn CumProd(&self, axis: ViewType, exclusive: bool, reverse: bool) -> Tensor {
+        self.scan_op(ScanType::PROD, axis, exclusive, reverse)
+    }
+
+    pub fn CumMax(&self, axis: ViewType, reverse: bool) -> Tensor {
+        // Maximum has no identity to start an exclusive scan from
+        self.scan_op(ScanType::MAX, axis, false, reverse)
+    }
+}
@@ -0,0 +1,187 @@
+use crate::primitives::tensor::{Tensor, TensorDataElement, TensorElementOrder, TensorType};
+use crate::primitives::tensorview::{TensorView, ViewType};
+
+use super::{OperationSpec, TensorInput};
+
+#[derive(Clone, Copy, Debug)]
+pub enum SortType {
+    VALUES,
+    INDICES,
+}
+
+#[derive(Clone, Debug)]
+pub struct SortSpec {
+    pub op: SortType,
+    pub input: Tensor,
+    pub axis: ViewType,
+    pub descending: bool,
+}
+
+impl TensorInput {
+    pub fn sort(op: SortType, input: Tensor, axis: ViewType, descending: bool) -> TensorInput {
+        TensorInput::OperationResult(OperationSpec::SortOp(SortSpec {
+            op,
+            input,
+            axis,
+            descending,
+        }))
+    }
+}
+
+impl Tensor {
+    fn sort_op(&self, op: SortType, axis: ViewType, descending: bool) -> Tensor {
+        assert!(
+            axis >= 0 && axis < self.ndim(),
+            "Sort axis {} is out of bounds, 0 <= axis < {}",
+            axis,
+            self.ndim()
+        );
+
+        let datatype = match op {
+            SortType::VALUES => self.datatype(),
+            SortType::INDICES => TensorType::I32,
+        };
+        let output_view = TensorView::from_contiguous_shape(self.shape());
+        Tensor::new(
+            output_view,
+            TensorInput::sort(op, self.clone(), axis, descending),
+            datatype,
+        )
+    }
+
+    // Equal elements keep their original order
+    pub fn Sort(&self, axis: ViewType, descending: bool) -> Tensor {
+        self.sort_op(SortType::VALUES, axis, descending)
+    }
+
+    pub fn ArgSort(&self, axis: ViewType, descending: bool) -> Tensor {
+        self.sort_op(SortType::INDICES, axis, descending)
+    }
+
+    pub fn TopK(
+        &self,
+        k: ViewType,
+        axis: ViewType,
+        largest: bool,
+        _sorted: bool,
+    ) -> (Tensor, Tensor) {
+        assert!(
+            k >= 0 && k <= self.shape()[axis as usize],
+            "TopK expects 0 <= k <= {}, got {}",
+            self.shape()[axis as usize],
+            k
+        );
+
+        // Elements are always returned sorted, the leading `k` along the axis are a view
+        let mut shape = self.shape().to_vec();
+        shape[axis as usize] = k;
+        let stride = TensorView::from_contiguous_shape(self.shape()).contiguous_stride();
+
+        let values = self.Sort(axis, largest).as_strided(&shape[..], &stride[..]);
+        let indices = self
+            .ArgSort(axis, largest)
+            .as_strided(&shape[..], &stride[..]);
+        (values, indices)
+    }
+
+    // Yields the unique values, the indices of their first occurrence, the index of
+    // every input into the unique values and the number of occurrences
+    pub fn Unique(&self, axis: Option<ViewType>, sorted: bool) -> (Tensor, Tensor, Tensor, Tensor) {
+        // The output shape depends on the data, so it is computed on the host
+        assert!(
+            self.has_data(),
+            "Unique requires an evaluated Tensor, found {:?}",
+            self.data()
+        );
+
+        match self.datatype() {
+            TensorType::F32 => self.host_unique::<f32>(axis, sorted),
+            TensorType::I32 => self.host_unique::<i32>(axis, sorted),
+            TensorType::U32 => self.host_unique::<u32>(axis, sorted),
+            datatype => panic!("Unique does not support {:?}", datatype),
+        }
+    }
+
+    fn host_unique<T: TensorDataElement + TensorElementOrder>(
+        &self,
+        axis: Option<ViewType>,
+        sorted: bool,
+    ) -> (Tensor, Tensor, Tensor, Tensor) {
+        let data = self.load::<T>();
+        let (shape, axis) = match axis {
+            Some(axis) => (self.shape().to_vec(), axis as usize),
+            None => (vec![data.len() as ViewType], 0),
+        };
+
+        // Every index along the axis selects a slice of [..axis] x [axis+1..]
+        let outer = shape[..axis].iter().product::<ViewType>() as usize;
+        let extent = shape[axis] as usize;
+        let inner = shape[axis + 1..].iter().product::<ViewType>() as usize;
+        let slices = (0..extent)
+            .map(|index| {
+                (0..outer)
+                    .flat_map(|batch| {
+                        let offset = (batch * extent + index) * inner;
+                        data[offset..offset + inner].iter().copied()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // Stable sort so the first of every group of equal slices is its first occurrence
+        let compare = |lhs: usize, rhs: usize| {
+            slices[lhs]
+                .iter()
+                .zip(slices[rhs].iter())
+                .map(|(lhs, rhs)| lhs.total_order(rhs))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        };
+        let mut order = (0..extent).collect::<Vec<_>>();
+        order.sort_by(|&lhs, &rhs| compare(lhs, rhs));
+        let mut groups: Vec<Vec<usize>> = vec![];
+        order.into_iter().for_each(|index| match groups.last_mut() {
+            Some(group) if compare(group[0], index).is_eq() => group.push(index),
+            _ => groups.push(vec![index]),
+        });
+        if !sorted {
+            groups.sort_by_key(|group| group[0]);
+        }
+
+        let mut inverse_indices = vec![0; extent];
+        groups.iter().enumerate().for_each(|(unique_index, group)| {
+            group
+                .iter()
+                .for_each(|&index| inverse_indices[index] = unique_index as i32)
+        });
+
+        let slices = &slices;
+        let values = (0..outer)
+            .flat_map(|batch| {
+                groups.iter().flat_map(move |group| {
+                    slices[group[0]][batch * inner..(batch + 1) * inner]
+                        .iter()
+                        .copied()
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut values_shape = shape.clone();
+        values_shape[axis] = groups.len() as ViewType;
+
+        let indices = groups
+            .iter()
+            .map(|group| group[0] as i32)
+            .collect::<Vec<_>>();
+        let counts = groups
+            .iter()
+            .map(|group| group.len() as i32)
+            .collect::<Vec<_>>();
+
+        (
+            Tensor::from_contiguous(&values[..], &values_shape[..]),
+            Tensor::from_contiguous(&indices[..], &[indices.len() as ViewType]),
+            Tensor::from_contiguous(&inverse_indices[..], &[extent as ViewType]),
+            Tensor::from_contiguous(&counts[..], &[counts.len() as ViewType]),
+        )
+    }
+}
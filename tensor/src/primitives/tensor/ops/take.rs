@@ -0,0 +1,35 @@
+use crate::primitives::tensor::{Tensor, TensorType};
+use crate::primitives::tensorview::TensorView;
+
+use super::{OperationSpec, TensorInput};
+
+#[derive(Clone, Debug)]
+pub struct TakeSpec {
+    pub input: Tensor,
+    // Indices into the flattened input
+    pub indices: Tensor,
+}
+
+impl TensorInput {
+    pub fn take(input: Tensor, indices: Tensor) -> TensorInput {
+        TensorInput::OperationResult(OperationSpec::TakeOp(TakeSpec { input, indices }))
+    }
+}
+
+impl Tensor {
+    // Reads the flattened input at every index, in the shape of the indices
+    pub fn Take(&self, indices: &Tensor) -> Tensor {
+        assert!(
+            indices.datatype() == TensorType::I32,
+            "Take expects I32 indices, found {:?}",
+            indices.datatype()
+        );
+
+        let output_view = TensorView::from_contiguous_shape(indices.shape());
+        Tensor::new(
+            output_view,
+            TensorInput::take(self.clone(), indices.clone()),
+            self.datatype(),
+        )
+    }
+}
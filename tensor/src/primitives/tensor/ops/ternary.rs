@@ -0,0 +1,61 @@
+use crate::primitives::tensor::Tensor;
+use crate::primitives::tensorview::TensorView;
+
+use super::{OperationSpec, TensorInput};
+
+#[derive(Clone, Copy, Debug)]
+pub enum TernaryType {
+    SELECT,
+}
+
+#[derive(Clone, Debug)]
+pub struct TernarySpec {
+    pub op: TernaryType,
+    pub condition: Tensor,
+    pub lhs: Tensor,
+    pub rhs: Tensor,
+}
+
+impl TensorInput {
+    pub fn ternary(op: TernaryType, condition: Tensor, lhs: Tensor, rhs: Tensor) -> TensorInput {
+        TensorInput::OperationResult(OperationSpec::TernaryOp(TernarySpec {
+            op,
+            condition,
+            lhs,
+            rhs,
+        }))
+    }
+}
+
+impl Tensor {
+    fn ternary_op(&self, op: TernaryType, lhs: &Tensor, rhs: &Tensor) -> Tensor {
+        let datatype = lhs.datatype().agreeable_type(rhs.datatype());
+        let output_view = TensorView::from_contiguous_shape(
+            &self
+                .view()
+                .broadcast(lhs.view())
+                .broadcast(rhs.view())
+                .shape,
+        );
+
+        // Casting a fractional condition to integer branches would truncate it, so it becomes 0 or 1 first
+        let condition = if !self.datatype().is_integer() && datatype.is_integer() {
+            self.Equal(&Tensor::scalar(0.0)).Not()
+        } else {
+            self.clone()
+        };
+        let condition = condition.broadcast_to(&output_view).Cast(datatype);
+        let lhs = lhs.broadcast_to(&output_view).Cast(datatype);
+        let rhs = rhs.broadcast_to(&output_view).Cast(datatype);
+        Tensor::new(
+            output_view,
+            TensorInput::ternary(op, condition, lhs, rhs),
+            datatype,
+        )
+    }
+
+    pub fn Where(&self, lhs: &Tensor, rhs: &Tensor) -> Tensor {
+        // When True (nonzero), yield lhs, otherwise yield rhs
+        self.ternary_op(TernaryType::SELECT, lhs, rhs)
+    }
+}
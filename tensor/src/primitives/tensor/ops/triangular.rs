@@ -0,0 +1,72 @@
+use crate::primitives::tensor::{Tensor, TensorType};
+use crate::primitives::tensorview::TensorView;
+
+use super::{OperationSpec, TensorInput};
+
+#[derive(Clone, Debug)]
+pub struct TriangularSolveSpec {
+    // [..., n, n]
+    pub matrix: Tensor,
+    // [..., n, k]
+    pub rhs: Tensor,
+    // Only the upper or lower triangle of the matrix is read
+    pub upper: bool,
+    // The diagonal is read as ones
+    pub unit_diagonal: bool,
+}
+
+impl TensorInput {
+    pub fn triangular_solve(
+        matrix: Tensor,
+        rhs: Tensor,
+        upper: bool,
+        unit_diagonal: bool,
+    ) -> TensorInput {
+        TensorInput::OperationResult(OperationSpec::TriangularSolveOp(TriangularSolveSpec {
+            matrix,
+            rhs,
+            upper,
+            unit_diagonal,
+        }))
+    }
+}
+
+impl Tensor {
+    // Solves AX = B for X by substitution, where A is triangular
+    pub fn TriangularSolve(&self, rhs: &Tensor, upper: bool, unit_diagonal: bool) -> Tensor {
+        let [matrix_batch_size @ .., n, m] = self.shape() else {
+            panic!("TriangularSolve requires matrix to be at least 2D");
+        };
+        let [rhs_batch_size @ .., rhs_n, k] = rhs.shape() else {
+            panic!("TriangularSolve requires rhs to be at least 2D");
+        };
+        assert!(
+            n == m && n == rhs_n,
+            "Failed to solve matrices of shape {:?} \\ {:?}",
+            self.shape(),
+            rhs.shape()
+        );
+
+        // Batch dimensions broadcast against each other
+        let batch_size = TensorView::from_contiguous_shape(matrix_batch_size)
+            .broadcast(&TensorView::from_contiguous_shape(rhs_batch_size))
+            .shape;
+        let matrix = self
+            .Cast(TensorType::F32)
+            .broadcast_to(&TensorView::from_contiguous_shape(
+                &[&batch_size[..], &[*n, *n]].concat(),
+            ));
+        let rhs = rhs
+            .Cast(TensorType::F32)
+            .broadcast_to(&TensorView::from_contiguous_shape(
+                &[&batch_size[..], &[*n, *k]].concat(),
+            ));
+
+        let output_view = TensorView::from_contiguous_shape(rhs.shape());
+        Tensor::new(
+            output_view,
+            TensorInput::triangular_solve(matrix, rhs, upper, unit_diagonal),
+            TensorType::F32,
+        )
+    }
+}
@@ -14,6 +14,15 @@ pub enum UnaryType {
     ABS,
     FLOOR,
     CEIL,
+    BITWISENOT,
+    ASIN,
+    ACOS,
+    ATAN,
+    ASINH,
+    ACOSH,
+    ATANH,
+    ISNAN,
+    ISINF,
 }
 
 #[derive(Clone, Debug)]
@@ -87,6 +96,7 @@ impl Tensor {
 
     pub fn Abs(&self) -> Tensor {
         self.unary_op(UnaryType::ABS, self.datatype())
+            .wrap_narrow_integer()
     }
 
     pub fn Floor(&self) -> Tensor {
@@ -104,4 +114,53 @@ impl Tensor {
             &[TensorType::I32, TensorType::U32],
         )
     }
+
+    pub fn Asin(&self) -> Tensor {
+        self.upgradable_unary_op(UnaryType::ASIN, TensorType::F32)
+    }
+
+    pub fn Acos(&self) -> Tensor {
+        self.upgradable_unary_op(UnaryType::ACOS, TensorType::F32)
+    }
+
+    pub fn Atan(&self) -> Tensor {
+        self.upgradable_unary_op(UnaryType::ATAN, TensorType::F32)
+    }
+
+    pub fn Asinh(&self) -> Tensor {
+        self.upgradable_unary_op(UnaryType::ASINH, TensorType::F32)
+    }
+
+    pub fn Acosh(&self) -> Tensor {
+        self.upgradable_unary_op(UnaryType::ACOSH, TensorType::F32)
+    }
+
+    pub fn Atanh(&self) -> Tensor {
+        self.upgradable_unary_op(UnaryType::ATANH, TensorType::F32)
+    }
+
+    pub fn BitwiseNot(&self) -> Tensor {
+        assert!(
+            self.datatype().is_integer(),
+            "BitwiseNot requires an integer input, found {:?}",
+            self.datatype()
+        );
+        self.unary_op(UnaryType::BITWISENOT, self.datatype())
+            .wrap_narrow_integer()
+    }
+
+    pub fn IsNaN(&self) -> Tensor {
+        self.upgradable_unary_op(UnaryType::ISNAN, TensorType::F32)
+    }
+
+    pub fn IsInf(&self, detect_positive: bool, detect_negative: bool) -> Tensor {
+        let infinite = self.upgradable_unary_op(UnaryType::ISINF, TensorType::F32);
+        let zero = Tensor::scalar(0);
+        match (detect_positive, detect_negative) {
+            (true, true) => infinite,
+            (true, false) => infinite.Multiply(&zero.LessThan(self)),
+            (false, true) => infinite.Multiply(&self.LessThan(&zero)),
+            (false, false) => infinite.Multiply(&zero),
+        }
+    }
 }
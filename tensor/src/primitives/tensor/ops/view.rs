@@ -18,6 +18,16 @@ impl Tensor {
         )
     }
 
+    // Transparent views are derived from the public view, so a strided view is kept
+    // as its own level instead of being swapped out
+    fn logical_view(&self) -> Tensor {
+        if self.viewtracker().current_view().contiguous {
+            self.clone()
+        } else {
+            self.contiguous()
+        }
+    }
+
     pub fn contiguous(&self) -> Tensor {
         let view = TensorView::from_contiguous_shape(&self.shape());
         self.reshape(&view)
@@ -32,18 +42,132 @@ impl Tensor {
     }
 
     pub fn squeeze(&self, axis: ViewType) -> Tensor {
-        self.transparent_view_op(&self.view().squeeze(axis))
+        let tensor = self.logical_view();
+        tensor.transparent_view_op(&tensor.view().squeeze(axis))
     }
 
     pub fn transpose(&self, axes: &[ViewType]) -> Tensor {
-        self.transparent_view_op(&self.view().transpose(axes))
+        let tensor = self.logical_view();
+        tensor.transparent_view_op(&tensor.view().transpose(axes))
     }
 
     pub fn unsqueeze(&self, axis: ViewType) -> Tensor {
-        self.transparent_view_op(&self.view().unsqueeze(axis))
+        let tensor = self.logical_view();
+        tensor.transparent_view_op(&tensor.view().unsqueeze(axis))
+    }
+
+    pub fn as_strided(&self, shape: &[ViewType], stride: &[ViewType]) -> Tensor {
+        assert!(
+            shape.len() == stride.len(),
+            "Shape {:?} and stride {:?} must have the same dimensions",
+            shape,
+            stride
+        );
+
+        // Strides index into the contiguous layout of the current shape
+        let furthest_index = shape
+            .iter()
+            .zip(stride.iter())
+            .map(|(&dimension, &stride)| {
+                assert!(stride >= 0, "Unexpected negative stride {}", stride);
+                (dimension - 1) * stride
+            })
+            .sum::<ViewType>();
+        assert!(
+            furthest_index < self.len(),
+            "Strided view {:?} {:?} reads beyond {:?}",
+            shape,
+            stride,
+            self.shape()
+        );
+
+        self.reshape(&TensorView::new(
+            false,
+            shape.to_vec().into_boxed_slice(),
+            stride.to_vec().into_boxed_slice(),
+        ))
     }
 
     pub fn reshape(&self, view: &TensorView) -> Tensor {
         self.view_op(view)
     }
+
+    pub fn view_as(&self, shape: &[ViewType]) -> Tensor {
+        // `0` copies the dimension of the same axis
+        let shape = shape
+            .iter()
+            .enumerate()
+            .map(|(axis, &dimension)| {
+                assert!(
+                    dimension >= -1,
+                    "Unexpected dimension {} in {:?}",
+                    dimension,
+                    shape
+                );
+
+                if dimension == 0 {
+                    assert!(
+                        axis < self.ndim() as usize,
+                        "Unable to copy axis {} from {:?}",
+                        axis,
+                        self.shape()
+                    );
+                    self.shape()[axis]
+                } else {
+                    dimension
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // `-1` is inferred from the remaining elements
+        let inferred_axes = shape.iter().filter(|&&dimension| dimension == -1).count();
+        assert!(
+            inferred_axes <= 1,
+            "Only a single dimension can be inferred, got {:?}",
+            shape
+        );
+
+        let known_elements = shape
+            .iter()
+            .filter(|&&dimension| dimension != -1)
+            .product::<ViewType>();
+        let shape = if inferred_axes == 1 {
+            assert!(
+                known_elements != 0 && self.len() % known_elements == 0,
+                "Unable to infer the dimension to view {:?} as {:?}",
+                self.shape(),
+                shape
+            );
+
+            shape
+                .iter()
+                .map(|&dimension| {
+                    if dimension == -1 {
+                        self.len() / known_elements
+                    } else {
+                        dimension
+                    }
+                })
+                .collect::<Vec<_>>()
+        } else {
+            shape
+        };
+
+        assert!(
+            shape.iter().product::<ViewType>() == self.len(),
+            "Unable to view {:?} as {:?}",
+            self.shape(),
+            shape
+        );
+
+        match self
+            .viewtracker()
+            .current_view()
+            .attempt_reshape(&shape[..])
+        {
+            Some(view) => self.transparent_view_op(&view),
+            // Strides cannot be merged, so the reshape goes through a contiguous view
+            None => self.contiguous().view_as(&shape[..]),
+        }
+    }
 }
@@ -185,9 +185,53 @@ impl Drop for Tensor {
                         dest.push(op.rhs.clone());
                     }
 
+                    if let OperationSpec::TernaryOp(ref mut op) = result {
+                        dest.push(op.condition.clone());
+                        dest.push(op.lhs.clone());
+                        dest.push(op.rhs.clone());
+                    }
+
                     if let OperationSpec::ReduceOp(ref mut op) = result {
                         dest.push(op.input.clone());
                     }
+
+                    if let OperationSpec::ScanOp(ref mut op) = result {
+                        dest.push(op.input.clone());
+                    }
+
+                    if let OperationSpec::SortOp(ref mut op) = result {
+                        dest.push(op.input.clone());
+                    }
+
+                    if let OperationSpec::MatMulOp(ref mut op) = result {
+                        dest.push(op.lhs.clone());
+                        dest.push(op.rhs.clone());
+                    }
+
+                    if let OperationSpec::ConcatOp(ref mut op) = result {
+                        dest.append(&mut op.inputs);
+                    }
+
+                    if let OperationSpec::TakeOp(ref mut op) = result {
+                        dest.push(op.input.clone());
+                        dest.push(op.indices.clone());
+                    }
+
+                    if let OperationSpec::AttentionOp(ref mut op) = result {
+                        dest.push(op.query.clone());
+                        dest.push(op.key.clone());
+                        dest.push(op.value.clone());
+                        dest.extend(op.mask.take());
+                    }
+
+                    if let OperationSpec::LUOp(ref mut op) = result {
+                        dest.push(op.input.clone());
+                    }
+
+                    if let OperationSpec::TriangularSolveOp(ref mut op) = result {
+                        dest.push(op.matrix.clone());
+                        dest.push(op.rhs.clone());
+                    }
                 }
 
                 let _ = tensor.data.replace(TensorInput::Invalidated);
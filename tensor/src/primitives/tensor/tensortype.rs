@@ -1,12 +1,26 @@
 pub trait TensorDataElement: Into<TensorType> + bytemuck::Pod + Copy + ToString {}
 impl<T> TensorDataElement for T where T: Into<TensorType> + bytemuck::Pod + Copy + ToString {}
 
+// Total order over element values which places NaN after every number, so host sorts never panic
+pub trait TensorElementOrder: PartialOrd {
+    // Only NaN is unequal to itself
+    #[allow(clippy::eq_op)]
+    fn total_order(&self, other: &Self) -> std::cmp::Ordering {
+        self.partial_cmp(other)
+            .unwrap_or_else(|| (self != self).cmp(&(other != other)))
+    }
+}
+impl<T> TensorElementOrder for T where T: PartialOrd {}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum TensorType {
     I32,
     U32,
     F32,
     F16,
+    // 8-bit integers are widened to 32 bits in memory, as WGSL has no 8-bit storage types
+    I8,
+    U8,
 }
 
 impl TensorType {
@@ -16,9 +30,18 @@ impl TensorType {
             TensorType::U32 => 4,
             TensorType::F32 => 4,
             TensorType::F16 => 2,
+            TensorType::I8 => 4,
+            TensorType::U8 => 4,
         }
     }
 
+    pub const fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            TensorType::I32 | TensorType::U32 | TensorType::I8 | TensorType::U8
+        )
+    }
+
     pub fn agreeable_type(self, other: TensorType) -> TensorType {
         if self == other {
             self
@@ -32,6 +32,10 @@ impl TensorViewTracker {
         }
     }
 
+    pub fn current_view(&self) -> &TensorView {
+        &self.current
+    }
+
     pub fn root_view(&self) -> &TensorView {
         self.history.first().unwrap_or(&self.current)
     }
@@ -4,11 +4,38 @@ use itertools::{EitherOrBoth::*, Itertools};
 
 pub type ViewType = i32;
 
+#[derive(Clone, Copy, Debug)]
+pub enum PadMode {
+    Constant(f32),
+    Reflect,
+    Edge,
+    Wrap,
+}
+
+// Pad values compare by their bits so every mode, NaN included, equals itself
+impl PartialEq for PadMode {
+    fn eq(&self, other: &PadMode) -> bool {
+        match (self, other) {
+            (PadMode::Constant(lhs), PadMode::Constant(rhs)) => lhs.to_bits() == rhs.to_bits(),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for PadMode {}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ViewPadding {
+    pub padding: Box<[(ViewType, ViewType)]>,
+    pub mode: PadMode,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TensorView {
     pub contiguous: bool,
     pub shape: Box<[ViewType]>,
     pub stride: Box<[ViewType]>,
+    pub padding: Option<ViewPadding>,
 }
 
 impl TensorView {
@@ -21,6 +48,7 @@ impl TensorView {
             contiguous,
             shape,
             stride,
+            padding: None,
         }
     }
 
@@ -72,7 +100,7 @@ impl TensorView {
         TensorView::compute_contiguous_stride(&self.shape[..])
     }
 
-    pub fn pad(&self, padding: &[(ViewType, ViewType)]) -> TensorView {
+    pub fn pad(&self, padding: &[(ViewType, ViewType)], mode: PadMode) -> TensorView {
         assert!(
             (self.ndim() as usize) == padding.len(),
             "Padding must be specified for all dimensions"
@@ -85,7 +113,87 @@ impl TensorView {
             .map(|(rank, (padding_pre, padding_post))| padding_pre + rank + padding_post)
             .collect_vec();
 
-        TensorView::from_contiguous_shape(&expanded_shape)
+        expanded_shape.iter().for_each(|&dimension| {
+            assert!(
+                dimension >= 0,
+                "Padding {:?} removes more than the shape {:?}",
+                padding,
+                self.shape
+            )
+        });
+
+        // Reads are mapped back into the original shape, out-of-range reads
+        // resolve according to the padding mode
+        TensorView {
+            contiguous: false,
+            shape: expanded_shape.into_boxed_slice(),
+            stride: self.stride.clone(),
+            padding: Some(ViewPadding {
+                padding: padding.to_vec().into_boxed_slice(),
+                mode,
+            }),
+        }
+    }
+
+    pub fn attempt_reshape(&self, shape: &[ViewType]) -> Option<TensorView> {
+        if self.padding.is_some() {
+            return None;
+        }
+
+        // Any strides are valid when there are no elements
+        if self.len() == 0 || shape.contains(&0) {
+            return Some(TensorView::from_contiguous_shape(shape));
+        }
+
+        // https://github.com/numpy/numpy/blob/main/numpy/_core/src/multiarray/shape.c
+        // Groups of old dimensions are matched to groups of new dimensions with the
+        // same number of elements, which is only possible if each old group is contiguous.
+        // Dimensions of length 1 have arbitrary strides, so they are ignored
+        let (old_shape, old_stride): (Vec<ViewType>, Vec<ViewType>) = self
+            .shape
+            .iter()
+            .zip(self.stride.iter())
+            .filter(|(&dimension, _)| dimension != 1)
+            .unzip();
+
+        let mut stride = vec![0; shape.len()];
+        let (mut old_begin, mut old_end) = (0, 1);
+        let (mut new_begin, mut new_end) = (0, 1);
+        while new_begin < shape.len() && old_begin < old_shape.len() {
+            let mut new_elements = shape[new_begin];
+            let mut old_elements = old_shape[old_begin];
+            while new_elements != old_elements {
+                if new_elements < old_elements {
+                    new_elements *= shape[new_end];
+                    new_end += 1;
+                } else {
+                    old_elements *= old_shape[old_end];
+                    old_end += 1;
+                }
+            }
+
+            if (old_begin..old_end - 1)
+                .any(|axis| old_stride[axis] != old_shape[axis + 1] * old_stride[axis + 1])
+            {
+                return None;
+            }
+
+            stride[new_end - 1] = old_stride[old_end - 1];
+            for axis in (new_begin + 1..new_end).rev() {
+                stride[axis - 1] = stride[axis] * shape[axis];
+            }
+
+            new_begin = new_end;
+            new_end += 1;
+            old_begin = old_end;
+            old_end += 1;
+        }
+
+        Some(TensorView::new(
+            self.contiguous,
+            shape.to_vec().into_boxed_slice(),
+            stride.into_boxed_slice(),
+        ))
     }
 
     fn _split_and_join<T>(
@@ -149,6 +257,46 @@ impl TensorView {
         )
     }
 
+    pub fn tile(&self, repeats: &[ViewType]) -> TensorView {
+        assert!(
+            (self.ndim() as usize) == repeats.len(),
+            "Repeats must be specified for all dimensions"
+        );
+
+        // Every axis `n` is expanded into `(repeat, n)`, where the repeated axis
+        // has no stride so each block reads the same data
+        let shape = repeats
+            .iter()
+            .zip(self.shape.iter())
+            .flat_map(|(&repeat, &dimension)| [repeat, dimension])
+            .collect_vec();
+        let stride = self
+            .stride
+            .iter()
+            .flat_map(|&stride| [0, stride])
+            .collect_vec();
+
+        TensorView::new(false, shape.into_boxed_slice(), stride.into_boxed_slice())
+    }
+
+    pub fn repeat_interleave(&self, repeats: ViewType, axis: ViewType) -> TensorView {
+        assert!(
+            axis < self.ndim(),
+            "Repeat axis {} is out of bounds, 0 <= axis < {}",
+            axis,
+            self.ndim()
+        );
+
+        // Axis `n` is expanded into `(n, repeat)` so consecutive elements read the same data
+        let axis = axis as usize + 1;
+        let shape =
+            TensorView::_split_and_join(&self.shape, axis, repeats, TensorView::_join_unsqueeze);
+        let stride =
+            TensorView::_split_and_join(&self.stride, axis, 0, TensorView::_join_unsqueeze);
+
+        TensorView::new(false, shape.into_boxed_slice(), stride.into_boxed_slice())
+    }
+
     pub fn transpose(&self, axes: &[ViewType]) -> TensorView {
         let axis = if axes.len() == self.ndim() as usize {
             axes.iter().map(|&x| x as usize).collect::<Vec<_>>()
@@ -176,6 +324,74 @@ impl TensorView {
         TensorView::new(false, shape.into_boxed_slice(), stride.into_boxed_slice())
     }
 
+    pub fn unfold(
+        &self,
+        kernel_size: &[ViewType],
+        strides: &[ViewType],
+        dilations: &[ViewType],
+    ) -> TensorView {
+        assert!(
+            kernel_size.len() <= self.ndim() as usize,
+            "Kernel {:?} cannot exceed the dimensions of {:?}",
+            kernel_size,
+            self.shape
+        );
+        assert!(
+            strides.len() == kernel_size.len() && dilations.len() == kernel_size.len(),
+            "Strides {:?} and dilations {:?} must be defined for every dimension of the kernel {:?}",
+            strides,
+            dilations,
+            kernel_size
+        );
+
+        // Windows slide over the trailing dimensions, which are expanded into
+        // `(output_size..., kernel_size...)`
+        let leading_axes = self.ndim() as usize - kernel_size.len();
+        let (leading_shape, window_shape) = self.shape.split_at(leading_axes);
+        let (leading_stride, window_stride) = self.stride.split_at(leading_axes);
+
+        let output_shape = window_shape
+            .iter()
+            .zip(kernel_size.iter().zip(strides.iter().zip(dilations.iter())))
+            .map(|(&dimension, (&kernel, (&stride, &dilation)))| {
+                let extent = dilation * (kernel - 1) + 1;
+                assert!(
+                    dimension >= extent,
+                    "Kernel {:?} with dilations {:?} does not fit in {:?}",
+                    kernel_size,
+                    dilations,
+                    self.shape
+                );
+                (dimension - extent) / stride + 1
+            })
+            .collect_vec();
+
+        let shape = leading_shape
+            .iter()
+            .chain(output_shape.iter())
+            .chain(kernel_size.iter())
+            .copied()
+            .collect_vec();
+        let stride = leading_stride
+            .iter()
+            .copied()
+            .chain(
+                window_stride
+                    .iter()
+                    .zip(strides.iter())
+                    .map(|(&window_stride, &stride)| window_stride * stride),
+            )
+            .chain(
+                window_stride
+                    .iter()
+                    .zip(dilations.iter())
+                    .map(|(&window_stride, &dilation)| window_stride * dilation),
+            )
+            .collect_vec();
+
+        TensorView::new(false, shape.into_boxed_slice(), stride.into_boxed_slice())
+    }
+
     pub fn unsqueeze(&self, axis: ViewType) -> TensorView {
         assert!(
             axis <= self.ndim(),
@@ -1,3 +1,4 @@
+use dtensor::autodiff::backprop::BackPropagation;
 use dtensor::primitives::tensor::Tensor;
 use dtensor::primitives::tensorview::TensorView;
 use dtensor::runtime::webgpu::WebGPUEvaluation;
@@ -154,7 +155,7 @@ async fn conv() {
     let input = Tensor::arange(&[1, 1, 4, 4]);
     let kernel = Tensor::arange(&[1, 1, 2, 2]);
     let result = input
-        .Conv(&kernel, &[1, 1], ConvPadding::Valid)
+        .Conv(&kernel, &[1, 1], &[1, 1], 1, ConvPadding::Valid)
         .evaluate_webgpu(&wgpu_device);
     let output = result.load();
     assert_eq!(
@@ -166,13 +167,71 @@ async fn conv() {
     let input = Tensor::arange(&[1, 1, 4, 4]);
     let kernel = Tensor::arange(&[1, 1, 2, 2]);
     let result = input
-        .Conv(&kernel, &[1, 1], ConvPadding::Same)
+        .Conv(&kernel, &[1, 1], &[1, 1], 1, ConvPadding::Same)
         .evaluate_webgpu(&wgpu_device);
     let output = result.load();
     assert_eq!(
         bytemuck::cast_slice::<u8, i32>(&output[..]),
         &[24, 30, 36, 14, 48, 54, 60, 22, 72, 78, 84, 30, 13, 14, 15, 0]
     );
+
+    // Grouped
+    let input = Tensor::arange(&[1, 4, 3, 3]);
+    let kernel = Tensor::arange(&[2, 2, 2, 2]);
+    let result = input
+        .Conv(&kernel, &[1, 1], &[1, 1], 2, ConvPadding::Valid)
+        .evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[1, 2, 2, 2]);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[268, 296, 352, 380, 2340, 2432, 2616, 2708]
+    );
+
+    // Dilated
+    let input = Tensor::arange(&[1, 1, 4, 4]);
+    let kernel = Tensor::arange(&[1, 1, 2, 2]);
+    let result = input
+        .Conv(&kernel, &[1, 1], &[2, 2], 1, ConvPadding::Valid)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[48, 54, 72, 78]
+    );
+}
+
+#[tokio::test]
+async fn conv_transpose() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    // Strided
+    let input = Tensor::arange(&[1, 1, 2, 2]);
+    let kernel = Tensor::arange(&[1, 1, 2, 2]);
+    let result = input
+        .ConvTranspose(&kernel, &[2, 2], &[1, 1], 1, &[(0, 0), (0, 0)], &[0, 0])
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 0, 0, 1, 0, 0, 2, 3, 0, 2, 0, 3, 4, 6, 6, 9]
+    );
+
+    // Grouped with padding and output padding
+    let input = Tensor::arange(&[1, 2, 2, 2]);
+    let kernel = Tensor::arange(&[2, 1, 3, 3]);
+    let result = input
+        .ConvTranspose(&kernel, &[2, 2], &[1, 1], 2, &[(1, 1), (1, 1)], &[1, 1])
+        .evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[1, 2, 4, 4]);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[
+            0, 3, 4, 5, 2, 10, 10, 14, 8, 19, 12, 15, 14, 34, 21, 24, 52, 116, 65, 70, 124, 272,
+            150, 162, 78, 168, 91, 98, 96, 207, 112, 119
+        ]
+    );
 }
 
 #[tokio::test]
@@ -189,6 +248,361 @@ async fn matmul() {
     );
 }
 
+#[tokio::test]
+async fn matmul_batched() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    // [k] @ [k, m] -> [m]
+    let a = Tensor::from_contiguous(&[1.0, 2.0, 3.0], &[3]);
+    let b = Tensor::from_contiguous(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[3, 2]);
+    let result = a.MatMul(&b).evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[2]);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, f32>(&output[..]), &[22.0, 28.0]);
+
+    // [2, 1, 1, 2] @ [3, 2, 1] -> [2, 3, 1, 1]
+    let a = Tensor::arange(&[2, 1, 1, 2]);
+    let b = Tensor::arange(&[3, 2, 1]);
+    let result = a.MatMul(&b).evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[2, 3, 1, 1]);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[1, 3, 5, 3, 13, 23]
+    );
+
+    // Matrices spanning several tiles
+    let ones = Tensor::scalar(1).broadcast_to(&TensorView::from_contiguous_shape(&[17, 19]));
+    let result = Tensor::arange(&[20, 17])
+        .MatMul(&ones)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &(0..20)
+            .flat_map(|row| std::iter::repeat(289 * row + 136).take(19))
+            .collect::<Vec<_>>()[..]
+    );
+}
+
+#[tokio::test]
+async fn einsum() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let a = Tensor::arange(&[2, 3]);
+    let b = Tensor::arange(&[3, 2]);
+    let result = Tensor::Einsum("ij,jk->ik", &[a.clone(), b.clone()]).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[10, 13, 28, 40]
+    );
+
+    // Transposed output of an outer product
+    let result = Tensor::Einsum("ij,kl->lj", &[a, b]).evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[2, 3]);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[18, 30, 42, 27, 45, 63]
+    );
+
+    // Diagonal and trace
+    let square = Tensor::arange(&[3, 3]);
+    let result = Tensor::Einsum("ii->i", &[square.clone()]).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, i32>(&output[..]), &[0, 4, 8]);
+
+    let result = Tensor::Einsum("ii", &[square]).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, i32>(&output[..]), &[12]);
+
+    // Broadcast batch axes
+    let batched = Tensor::arange(&[2, 1, 2]);
+    let vector = Tensor::arange(&[2, 1]);
+    let result = Tensor::Einsum("...ij,...jk", &[batched, vector]).evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[2, 1, 1]);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, i32>(&output[..]), &[1, 3]);
+
+    let vector = Tensor::arange(&[3]);
+    let result = Tensor::Einsum("i,i,i->", &[vector.clone(), vector.clone(), vector])
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, i32>(&output[..]), &[9]);
+}
+
+#[tokio::test]
+async fn sort() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let input = Tensor::from_contiguous(&[3.0, 1.0, 2.0, 1.0, 5.0, 4.0, 4.0, 0.0], &[2, 4]);
+    let result = input.Sort(1, false).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[1.0, 1.0, 2.0, 3.0, 0.0, 4.0, 4.0, 5.0]
+    );
+
+    // Equal elements keep their original order
+    let result = input.ArgSort(1, false).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[1, 3, 2, 0, 3, 1, 2, 0]
+    );
+
+    let result = input.ArgSort(1, true).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 2, 1, 3, 0, 1, 2, 3]
+    );
+
+    let (values, indices) = input.TopK(2, 1, true, true);
+    let output = values.evaluate_webgpu(&wgpu_device).load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[3.0, 2.0, 5.0, 4.0]
+    );
+    let output = indices.evaluate_webgpu(&wgpu_device).load();
+    assert_eq!(bytemuck::cast_slice::<u8, i32>(&output[..]), &[0, 2, 0, 1]);
+
+    // Lines which are not a power of two
+    let result = Tensor::arange(&[2, 100])
+        .Sort(1, true)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &(0..2)
+            .flat_map(|line| (0..100).rev().map(move |element| line * 100 + element))
+            .collect::<Vec<_>>()[..]
+    );
+
+    // Lines longer than workgroup memory are sorted on the host
+    let result = Tensor::arange(&[2500, 2])
+        .Sort(0, true)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &(0..2500)
+            .rev()
+            .flat_map(|element| [element * 2, element * 2 + 1])
+            .collect::<Vec<_>>()[..]
+    );
+
+    let result = Tensor::from_contiguous(&vec![1; 2500][..], &[2500])
+        .ArgSort(0, false)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &(0..2500).collect::<Vec<_>>()[..]
+    );
+
+    let input = Tensor::from_contiguous(&[2.0, 1.0, 2.0, 3.0, 1.0], &[5]);
+    let (values, indices, inverse_indices, counts) = input.Unique(None, true);
+    assert_eq!(values.load::<f32>(), &[1.0, 2.0, 3.0]);
+    assert_eq!(indices.load::<i32>(), &[1, 0, 3]);
+    assert_eq!(inverse_indices.load::<i32>(), &[1, 0, 1, 2, 0]);
+    assert_eq!(counts.load::<i32>(), &[2, 2, 1]);
+
+    let (values, indices, _, _) = input.Unique(None, false);
+    assert_eq!(values.load::<f32>(), &[2.0, 1.0, 3.0]);
+    assert_eq!(indices.load::<i32>(), &[0, 1, 3]);
+
+    // NaN is unique once and sorts after every number
+    let input = Tensor::from_contiguous(&[f32::NAN, 1.0, f32::NAN], &[3]);
+    let (values, _, inverse_indices, counts) = input.Unique(None, true);
+    let values = values.load::<f32>();
+    assert!(values[0] == 1.0 && values[1].is_nan() && values.len() == 2);
+    assert_eq!(inverse_indices.load::<i32>(), &[1, 0, 1]);
+    assert_eq!(counts.load::<i32>(), &[1, 2]);
+}
+
+#[tokio::test]
+async fn bitwise() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let lhs = Tensor::from_contiguous(&[12, 10, -8, 7], &[4]);
+    let rhs = Tensor::from_contiguous(&[10, 6, 3, 1], &[4]);
+
+    let result = lhs.BitwiseAnd(&rhs).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, i32>(&output[..]), &[8, 2, 0, 1]);
+
+    let result = lhs.BitwiseOr(&rhs).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[14, 14, -5, 7]
+    );
+
+    let result = lhs.BitwiseXor(&rhs).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[6, 12, -5, 6]
+    );
+
+    let result = lhs.BitwiseNot().evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[-13, -11, 7, -8]
+    );
+
+    let result = lhs.BitShiftLeft(&rhs).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[12288, 640, -64, 14]
+    );
+
+    // Signed integers keep their sign
+    let result = lhs.BitShiftRight(&rhs).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, i32>(&output[..]), &[0, 0, -1, 3]);
+}
+
+#[tokio::test]
+async fn inverse_trigonometry() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let input = Tensor::from_contiguous(&[-0.5, 0.0, 0.5], &[3]);
+    let expect = |output: &[u8], expected: &[f32]| {
+        bytemuck::cast_slice::<u8, f32>(output)
+            .iter()
+            .zip(expected.iter())
+            .for_each(|(output, expected)| {
+                assert!(
+                    (output - expected).abs() < 1e-3,
+                    "{} != {}",
+                    output,
+                    expected
+                )
+            });
+    };
+
+    let output = input.Asin().evaluate_webgpu(&wgpu_device).load();
+    expect(&output[..], &[-0.5f32, 0.0, 0.5].map(f32::asin));
+    let output = input.Acos().evaluate_webgpu(&wgpu_device).load();
+    expect(&output[..], &[-0.5f32, 0.0, 0.5].map(f32::acos));
+    let output = input.Atan().evaluate_webgpu(&wgpu_device).load();
+    expect(&output[..], &[-0.5f32, 0.0, 0.5].map(f32::atan));
+    let output = input.Asinh().evaluate_webgpu(&wgpu_device).load();
+    expect(&output[..], &[-0.5f32, 0.0, 0.5].map(f32::asinh));
+    let output = input.Atanh().evaluate_webgpu(&wgpu_device).load();
+    expect(&output[..], &[-0.5f32, 0.0, 0.5].map(f32::atanh));
+
+    let input = Tensor::from_contiguous(&[1.0, 2.0, 3.0], &[3]);
+    let output = input.Acosh().evaluate_webgpu(&wgpu_device).load();
+    expect(&output[..], &[1.0f32, 2.0, 3.0].map(f32::acosh));
+
+    // Every quadrant of (x, y)
+    let y = Tensor::from_contiguous(&[1.0, 1.0, -1.0, -1.0], &[4]);
+    let x = Tensor::from_contiguous(&[1.0, -1.0, -1.0, 1.0], &[4]);
+    let output = y.Atan2(&x).evaluate_webgpu(&wgpu_device).load();
+    expect(
+        &output[..],
+        &[(1.0f32, 1.0f32), (1.0, -1.0), (-1.0, -1.0), (-1.0, 1.0)].map(|(y, x)| y.atan2(x)),
+    );
+}
+
+#[tokio::test]
+async fn non_finite() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let input = Tensor::from_contiguous(&[1.0, f32::NAN, f32::INFINITY, f32::NEG_INFINITY], &[4]);
+    let result = input.IsNaN().evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[0.0, 1.0, 0.0, 0.0]
+    );
+
+    let result = input.IsInf(true, true).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[0.0, 0.0, 1.0, 1.0]
+    );
+
+    let result = input.IsInf(false, true).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[0.0, 0.0, 0.0, 1.0]
+    );
+
+    let input =
+        Tensor::from_contiguous(&[1.0, f32::NAN, 2.0, f32::NAN, f32::NAN, f32::NAN], &[2, 3]);
+    let result = input.NanSum(&[1], false).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, f32>(&output[..]), &[3.0, 0.0]);
+
+    // Lines which are entirely NaN have no mean or maximum
+    let result = input.NanMean(&[1], false).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    let output = bytemuck::cast_slice::<u8, f32>(&output[..]);
+    assert!(output[0] == 1.5 && output[1].is_nan());
+
+    let result = input.NanMax(&[1], false).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    let output = bytemuck::cast_slice::<u8, f32>(&output[..]);
+    assert!(output[0] == 2.0 && output[1].is_nan());
+}
+
+#[tokio::test]
+async fn data_dependent() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let input = Tensor::from_contiguous(&[0.0, 3.0, 0.0, 4.0, 5.0, 0.0], &[2, 3]);
+    let result = input.NonZero().evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[2, 3]);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 1, 1, 1, 0, 1]
+    );
+
+    let condition = Tensor::from_contiguous(&[0, 1, 1], &[3]);
+    let result = input
+        .Compress(&condition, Some(1))
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[3.0, 0.0, 5.0, 0.0]
+    );
+
+    let mask = Tensor::from_contiguous(&[1.0, 0.0, 1.0], &[3]);
+    let result = input.masked_select(&mask).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[0.0, 0.0, 4.0, 0.0]
+    );
+
+    // Nothing selected yields empty results
+    let input = Tensor::from_contiguous(&[0.0, 0.0, 0.0, 0.0], &[2, 2]);
+    let result = input.NonZero().evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[2, 0]);
+    assert!(result.load::<i32>().is_empty());
+
+    let condition = Tensor::from_contiguous(&[0, 0], &[2]);
+    let result = input
+        .Compress(&condition, Some(0))
+        .evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[0, 2]);
+
+    let result = input.masked_select(&input).evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[0]);
+    assert!(result.load::<f32>().is_empty());
+}
+
 #[tokio::test]
 async fn softmax() {
     let wgpu_device = common::wgpu_setup().await.unwrap();
@@ -236,6 +650,37 @@ async fn scatter() {
         bytemuck::cast_slice::<u8, f32>(&output[..]),
         &[1.0, 1.1, 3.0, 2.1, 5.0]
     );
+
+    // Duplicate indices apply in order or accumulate through the reduction
+    input = Tensor::from_contiguous(&[1.0, 2.0, 3.0, 4.0], &[4]);
+    indices = Tensor::from_contiguous(&[1, 1, 3], &[3]);
+    updates = Tensor::from_contiguous(&[5.0, 0.5, 6.0], &[3]);
+    let expected: [(ScatterReduction, [f32; 4]); 5] = [
+        (ScatterReduction::None, [1.0, 0.5, 3.0, 6.0]),
+        (ScatterReduction::Add, [1.0, 7.5, 3.0, 10.0]),
+        (ScatterReduction::Mul, [1.0, 5.0, 3.0, 24.0]),
+        (ScatterReduction::Max, [1.0, 5.0, 3.0, 6.0]),
+        (ScatterReduction::Min, [1.0, 0.5, 3.0, 4.0]),
+    ];
+    for (reduction, expected) in expected {
+        result = input
+            .ScatterElements(0, reduction, &indices, &updates)
+            .evaluate_webgpu(&wgpu_device);
+        output = result.load();
+        assert_eq!(bytemuck::cast_slice::<u8, f32>(&output[..]), &expected);
+    }
+
+    input = Tensor::arange(&[2, 2, 3]);
+    indices = Tensor::from_contiguous(&[1, 0, 0, -1], &[2, 2]);
+    updates = Tensor::from_contiguous(&[-1, -2, -3, -4, -5, -6], &[2, 3]);
+    result = input
+        .ScatterND(ScatterReduction::Add, &indices, &updates)
+        .evaluate_webgpu(&wgpu_device);
+    output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 1, 2, -1, -1, -1, 5, 5, 5, 9, 10, 11]
+    );
 }
 
 #[tokio::test]
@@ -296,6 +741,22 @@ async fn reduce() {
     result = b.evaluate_webgpu(&wgpu_device);
     output = result.load();
     assert_eq!(bytemuck::cast_slice::<u8, f32>(&output[..]), &[6128.0]);
+
+    let transposed =
+        Tensor::from_contiguous(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]).transpose(&[1, 0]);
+
+    b = transposed.Sum(&[1], false);
+    result = b.evaluate_webgpu(&wgpu_device);
+    output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[5.0, 7.0, 9.0]
+    );
+
+    b = transposed.Max(&[0], false);
+    result = b.evaluate_webgpu(&wgpu_device);
+    output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, f32>(&output[..]), &[3.0, 6.0]);
 }
 
 #[tokio::test]
@@ -350,6 +811,25 @@ async fn gather() {
         bytemuck::cast_slice::<u8, f32>(&output[..]),
         &[4.0, 8.0, 3.0, 7.0, 2.0, 3.0]
     );
+
+    data = Tensor::arange(&[2, 2, 3]);
+    indices = Tensor::from_contiguous(&[1, 0, 0, -1], &[2, 2]);
+    result = data.GatherND(&indices, 0).evaluate_webgpu(&wgpu_device);
+    output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[6, 7, 8, 3, 4, 5]
+    );
+
+    // Every batch selects from its own slice of data
+    indices = Tensor::from_contiguous(&[1, 0], &[2, 1]);
+    result = data.GatherND(&indices, 1).evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[2, 3]);
+    output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[3, 4, 5, 6, 7, 8]
+    );
 }
 
 #[tokio::test]
@@ -376,3 +856,1301 @@ async fn erf() {
         &[-0.15729931, -0.11979495, -0.08968594, -0.06599197]
     );
 }
+
+#[tokio::test]
+async fn tile() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let input = Tensor::arange(&[2, 2]);
+    let result = input.Tile(&[2, 2]).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 1, 0, 1, 2, 3, 2, 3, 0, 1, 0, 1, 2, 3, 2, 3]
+    );
+
+    let result = input.Repeat(&[2, 1, 1]).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 1, 2, 3, 0, 1, 2, 3]
+    );
+
+    let result = input
+        .transpose(&[1, 0])
+        .RepeatInterleave(2, 1)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 0, 2, 2, 1, 1, 3, 3]
+    );
+}
+
+#[tokio::test]
+async fn pad_modes() {
+    use dtensor::primitives::tensorview::PadMode;
+
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let input = Tensor::arange(&[4]);
+    let result = input
+        .PadWith(&[(2, 2)], PadMode::Reflect)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[2, 1, 0, 1, 2, 3, 2, 1]
+    );
+
+    let result = input
+        .PadWith(&[(1, 2)], PadMode::Edge)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 0, 1, 2, 3, 3, 3]
+    );
+
+    let result = input
+        .PadWith(&[(2, 1)], PadMode::Wrap)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[2, 3, 0, 1, 2, 3, 0]
+    );
+
+    let result = input.Pad(&[(-1, 1)]).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, i32>(&output[..]), &[1, 2, 3, 0]);
+
+    let input = Tensor::arange(&[2, 2]);
+    let result = input
+        .PadWith(&[(1, 0), (0, 1)], PadMode::Constant(7.0))
+        .Pad(&[(0, 0), (1, 0)])
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 7, 7, 7, 0, 0, 1, 7, 0, 2, 3, 7]
+    );
+
+    let input = Tensor::from_contiguous(&[1.0, 2.0], &[2]);
+    let result = input
+        .PadWith(&[(1, 1)], PadMode::Constant(-1.5))
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[-1.5, 1.0, 2.0, -1.5]
+    );
+
+    // NaN pad values still compare equal to themselves
+    let padded = input.PadWith(&[(1, 0)], PadMode::Constant(f32::NAN));
+    assert_eq!(padded.view(), &padded.view().clone());
+    let output = padded.evaluate_webgpu(&wgpu_device).load();
+    let output = bytemuck::cast_slice::<u8, f32>(&output[..]);
+    assert!(output[0].is_nan() && output[1..] == [1.0, 2.0]);
+}
+
+#[tokio::test]
+async fn concat() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let a = Tensor::arange(&[2, 2]);
+    let b = Tensor::from_contiguous(&[4, 5], &[2, 1]);
+    let result = Tensor::Concat(&[a.clone(), b], 1).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 1, 4, 2, 3, 5]
+    );
+
+    let c = Tensor::from_contiguous(&[6, 7], &[1, 2]);
+    let result = Tensor::Concat(&[a.clone(), c, a.clone()], 0).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 1, 2, 3, 6, 7, 0, 1, 2, 3]
+    );
+
+    let result = Tensor::Stack(&[a.clone(), a.transpose(&[1, 0])], 0).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 1, 2, 3, 0, 2, 1, 3]
+    );
+
+    let splits = Tensor::arange(&[5]).Split(0, &[2, 3]);
+    let result =
+        Tensor::Concat(&[splits[1].clone(), splits[0].clone()], 0).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[2, 3, 4, 0, 1]
+    );
+}
+
+#[tokio::test]
+async fn view_as() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let input = Tensor::arange(&[2, 3]);
+    let result = input.view_as(&[0, -1, 1]);
+    assert_eq!(result.shape(), &[2, 3, 1]);
+
+    // Transposed strides cannot be flattened without going through a contiguous view
+    let result = input
+        .transpose(&[1, 0])
+        .view_as(&[-1])
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 3, 1, 4, 2, 5]
+    );
+
+    let result = input
+        .transpose(&[1, 0])
+        .view_as(&[3, 1, 2])
+        .Sum(&[2], false)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, i32>(&output[..]), &[3, 5, 7]);
+}
+
+#[tokio::test]
+async fn strided_views() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let result = Tensor::arange(&[2, 1])
+        .broadcast_to(&TensorView::from_contiguous_shape(&[2, 3]))
+        .transpose(&[1, 0])
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 1, 0, 1, 0, 1]
+    );
+
+    let result = Tensor::arange(&[3, 3])
+        .as_strided(&[2, 2], &[1, 3])
+        .transpose(&[1, 0])
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, i32>(&output[..]), &[0, 1, 3, 4]);
+
+    let result = Tensor::arange(&[3, 3])
+        .as_strided(&[3], &[4])
+        .unsqueeze(0)
+        .squeeze(0)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, i32>(&output[..]), &[0, 4, 8]);
+}
+
+#[tokio::test]
+async fn unfold() {
+    use dtensor::primitives::tensor::ConvPadding;
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let result = Tensor::arange(&[5])
+        .Unfold(&[2], &[1], &[2])
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 2, 1, 3, 2, 4]
+    );
+
+    let result = Tensor::arange(&[4])
+        .as_strided(&[3, 2], &[1, 1])
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 1, 1, 2, 2, 3]
+    );
+
+    let input = Tensor::arange(&[1, 2, 3, 3]);
+    let result = input
+        .MaxPool(&[2, 2], &[1, 1], &[1, 1], false, ConvPadding::Valid)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[4, 5, 7, 8, 13, 14, 16, 17]
+    );
+
+    let input = Tensor::from_contiguous(
+        &(0..18).map(|x| x as f32).collect::<Vec<_>>()[..],
+        &[1, 2, 3, 3],
+    );
+    let result = input
+        .AvgPool(&[2, 2], &[1, 1], &[1, 1], false, false, ConvPadding::Valid)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[2.0, 3.0, 5.0, 6.0, 11.0, 12.0, 14.0, 15.0]
+    );
+
+    let result = input
+        .MaxPool(&[2, 2], &[1, 1], &[1, 1], false, ConvPadding::Same)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        &bytemuck::cast_slice::<u8, f32>(&output[..])[..9],
+        &[4.0, 5.0, 5.0, 7.0, 8.0, 8.0, 7.0, 8.0, 8.0]
+    );
+}
+
+#[tokio::test]
+async fn pooling() {
+    use dtensor::primitives::tensor::ConvPadding;
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let input = Tensor::from_contiguous(
+        &(0..9).map(|x| x as f32).collect::<Vec<_>>()[..],
+        &[1, 1, 3, 3],
+    );
+
+    // Ceil mode keeps the partial windows
+    let (result, indices) =
+        input.MaxPoolWithIndices(&[2, 2], &[2, 2], &[1, 1], true, ConvPadding::Valid);
+    let result = result.evaluate_webgpu(&wgpu_device);
+    let indices = indices.evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[1, 1, 2, 2]);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[4.0, 5.0, 7.0, 8.0]
+    );
+    let output = indices.load();
+    assert_eq!(bytemuck::cast_slice::<u8, i32>(&output[..]), &[4, 5, 7, 8]);
+
+    let result = input
+        .AvgPool(&[2, 2], &[2, 2], &[1, 1], true, false, ConvPadding::Valid)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[2.0, 3.5, 6.5, 8.0]
+    );
+
+    // Padding is counted
+    let padding = [(0, 0), (0, 0), (1, 1), (1, 1)];
+    let result = input
+        .AvgPool(
+            &[3, 3],
+            &[1, 1],
+            &[1, 1],
+            false,
+            true,
+            ConvPadding::Custom(&padding[..]),
+        )
+        .Multiply(&Tensor::scalar(9.0))
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    bytemuck::cast_slice::<u8, f32>(&output[..])
+        .iter()
+        .zip([8.0, 15.0, 12.0, 21.0, 36.0, 27.0, 20.0, 33.0, 24.0])
+        .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+
+    // Dilated windows never pick up padding
+    let input = Tensor::arange(&[1, 1, 4, 4]).Neg();
+    let result = input
+        .MaxPool(
+            &[2, 2],
+            &[1, 1],
+            &[2, 2],
+            false,
+            ConvPadding::Custom(&padding[..]),
+        )
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[-5, -4, -5, -6, -1, 0, -1, -2, -5, -4, -5, -6, -9, -8, -9, -10]
+    );
+
+    let input = Tensor::from_contiguous(&[3.0, 0.0, 0.0, 4.0], &[1, 1, 2, 2]);
+    let result = input
+        .LpPool(2, &[2, 2], &[1, 1], &[1, 1], false, ConvPadding::Valid)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert!((bytemuck::cast_slice::<u8, f32>(&output[..])[0] - 5.0).abs() < 1e-4);
+
+    let input = Tensor::from_contiguous(
+        &(0..8).map(|x| x as f32).collect::<Vec<_>>()[..],
+        &[1, 2, 2, 2],
+    );
+    let result = input.GlobalAvgPool().evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[1, 2, 1, 1]);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, f32>(&output[..]), &[1.5, 5.5]);
+
+    let result = input.GlobalMaxPool().evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, f32>(&output[..]), &[3.0, 7.0]);
+}
+
+#[tokio::test]
+async fn interpolation() {
+    use dtensor::primitives::tensor::{
+        CoordinateTransformation, GridSampleMode, GridSamplePadding, NearestMode, ResizeMode,
+    };
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let input = Tensor::from_contiguous(&[0, 10, 20, 30, 40, 50], &[2, 3]);
+    let indices = Tensor::from_contiguous(&[5, 0, 3, 3], &[2, 2]);
+    let result = input.Take(&indices).evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[2, 2]);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[50, 0, 30, 30]
+    );
+
+    let input = Tensor::from_contiguous(&[1.0, 2.0, 3.0, 4.0], &[1, 4]);
+    let result = input
+        .Resize(
+            &[1, 8],
+            &[1.0, 2.0],
+            ResizeMode::Nearest(NearestMode::RoundPreferFloor),
+            CoordinateTransformation::Asymmetric,
+            false,
+        )
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0]
+    );
+
+    // Regions outside of the input are extrapolated
+    let roi = [(0.0, 1.0), (0.5, 1.5)];
+    let result = input
+        .Resize(
+            &[1, 3],
+            &[1.0, 0.75],
+            ResizeMode::Linear,
+            CoordinateTransformation::TfCropAndResize(&roi[..], 10.0),
+            false,
+        )
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[2.5, 4.0, 10.0]
+    );
+
+    let input = Tensor::from_contiguous(&[1.0, 2.0, 3.0, 4.0], &[2, 2]);
+    let result = input
+        .Resize(
+            &[4, 4],
+            &[2.0, 2.0],
+            ResizeMode::Linear,
+            CoordinateTransformation::HalfPixel,
+            false,
+        )
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[1.0, 1.25, 1.75, 2.0, 1.5, 1.75, 2.25, 2.5, 2.5, 2.75, 3.25, 3.5, 3.0, 3.25, 3.75, 4.0]
+    );
+
+    let input = Tensor::from_contiguous(&[0.0, 1.0, 4.0], &[1, 3]);
+    let result = input
+        .Resize(
+            &[1, 5],
+            &[1.0, 5.0 / 3.0],
+            ResizeMode::Linear,
+            CoordinateTransformation::AlignCorners,
+            false,
+        )
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[0.0, 0.5, 1.0, 2.5, 4.0]
+    );
+
+    let input = Tensor::from_contiguous(&[1.0, 2.0, 4.0, 8.0], &[1, 4]);
+    let result = input
+        .Resize(
+            &[1, 8],
+            &[1.0, 2.0],
+            ResizeMode::Cubic(-0.75),
+            CoordinateTransformation::HalfPixel,
+            false,
+        )
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    bytemuck::cast_slice::<u8, f32>(&output[..])
+        .iter()
+        .zip([
+            0.89453, 1.15625, 1.5625, 2.41797, 3.16016, 5.11719, 7.16406, 8.42188,
+        ])
+        .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+
+    let result = input
+        .Resize(
+            &[1, 8],
+            &[1.0, 2.0],
+            ResizeMode::Cubic(-0.75),
+            CoordinateTransformation::HalfPixel,
+            true,
+        )
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    bytemuck::cast_slice::<u8, f32>(&output[..])
+        .iter()
+        .zip([
+            0.86364, 1.14134, 1.5434, 2.41797, 3.16016, 5.21509, 7.24382, 8.54545,
+        ])
+        .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+
+    let input = Tensor::from_contiguous(&[1.0, 2.0, 3.0, 4.0], &[1, 1, 2, 2]);
+    let grid = Tensor::from_contiguous(&[-1.0, -1.0, 0.0, 0.0, 1.0, 1.0], &[1, 1, 3, 2]);
+    let result = input
+        .GridSample(
+            &grid,
+            GridSampleMode::Bilinear,
+            GridSamplePadding::Zeros,
+            false,
+        )
+        .evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[1, 1, 1, 3]);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[0.25, 2.5, 1.0]
+    );
+
+    let result = input
+        .GridSample(
+            &grid,
+            GridSampleMode::Bilinear,
+            GridSamplePadding::Border,
+            false,
+        )
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[1.0, 2.5, 4.0]
+    );
+
+    // Halfway coordinates round to even
+    let grid = Tensor::from_contiguous(&[0.0, 0.0, 0.5, -0.5, 1.0, 1.0], &[1, 1, 3, 2]);
+    let result = input
+        .GridSample(
+            &grid,
+            GridSampleMode::Nearest,
+            GridSamplePadding::Zeros,
+            false,
+        )
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[1.0, 2.0, 0.0]
+    );
+
+    let input = Tensor::from_contiguous(&[0.0, 10.0, 20.0], &[1, 1, 1, 3]);
+    let grid = Tensor::from_contiguous(&[1.5, 0.0, -1.25, 0.0], &[1, 1, 2, 2]);
+    let result = input
+        .GridSample(
+            &grid,
+            GridSampleMode::Bilinear,
+            GridSamplePadding::Reflection,
+            true,
+        )
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    bytemuck::cast_slice::<u8, f32>(&output[..])
+        .iter()
+        .zip([15.0, 2.5])
+        .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+}
+
+#[tokio::test]
+async fn normalization() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let input = Tensor::from_contiguous(
+        &(0..8).map(|x| x as f32).collect::<Vec<_>>()[..],
+        &[2, 2, 2],
+    );
+    let mean = Tensor::from_contiguous(&[1.0, 2.0], &[2]);
+    let variance = Tensor::from_contiguous(&[4.0, 1.0], &[2]);
+    let epsilon = Tensor::scalar(0.0);
+    let result = input
+        .BatchNormalization(&mean, &variance, &epsilon)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[-0.5, 0.0, 0.0, 1.0, 1.5, 2.0, 4.0, 5.0]
+    );
+
+    // Batch statistics are folded into the running statistics
+    let momentum = Tensor::scalar(0.9);
+    let (result, running_mean, running_variance) =
+        input.BatchNormalizationTraining(&mean, &variance, &momentum, &epsilon);
+    let result = result.evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    bytemuck::cast_slice::<u8, f32>(&output[..])
+        .iter()
+        .zip([
+            -1.21268, -0.72761, -1.21268, -0.72761, 0.72761, 1.21268, 0.72761, 1.21268,
+        ])
+        .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+    let result = running_mean.evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    bytemuck::cast_slice::<u8, f32>(&output[..])
+        .iter()
+        .zip([1.15, 2.25])
+        .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+    let result = running_variance.evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    bytemuck::cast_slice::<u8, f32>(&output[..])
+        .iter()
+        .zip([4.025, 1.325])
+        .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+
+    let input = Tensor::from_contiguous(&[1.0, 2.0, 3.0, 4.0, 2.0, 4.0, 6.0, 8.0], &[2, 4]);
+    let (result, mean, inverse_standard_deviation) = input.LayerNormalization(-1, &epsilon);
+    let result = result.evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    bytemuck::cast_slice::<u8, f32>(&output[..])
+        .iter()
+        .zip([
+            -1.34164, -0.44721, 0.44721, 1.34164, -1.34164, -0.44721, 0.44721, 1.34164,
+        ])
+        .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+    let result = mean.evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[2, 1]);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, f32>(&output[..]), &[2.5, 5.0]);
+    let result = inverse_standard_deviation.evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    bytemuck::cast_slice::<u8, f32>(&output[..])
+        .iter()
+        .zip([0.89443, 0.44721])
+        .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+
+    let input = Tensor::from_contiguous(&[1.0, 2.0, 3.0, 5.0], &[1, 4, 1]);
+    let result = input
+        .GroupNormalization(2, &epsilon)
+        .evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[1, 4, 1]);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[-1.0, 1.0, -1.0, 1.0]
+    );
+
+    let input = Tensor::from_contiguous(&[3.0, 4.0], &[1, 2]);
+    let result = input
+        .RMSNormalization(-1, &epsilon)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    bytemuck::cast_slice::<u8, f32>(&output[..])
+        .iter()
+        .zip([0.84853, 1.13137])
+        .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+
+    // Windows are truncated at the first and last channels
+    let input = Tensor::from_contiguous(&[1.0, 2.0, 3.0], &[1, 3, 1, 1]);
+    let result = input.LRN(3, 3.0, 1.0, 1.0).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    bytemuck::cast_slice::<u8, f32>(&output[..])
+        .iter()
+        .zip([0.16667, 0.13333, 0.21429])
+        .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+}
+
+#[tokio::test]
+async fn recurrent() {
+    use dtensor::primitives::tensor::{RecurrentActivation, RecurrentConfig, RecurrentDirection};
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    // [seq_length, batch_size, input_size]
+    let input = Tensor::from_contiguous(&[1.0, 0.5, 2.0, -1.0, 3.0, 2.0], &[3, 2, 1]);
+
+    // Steps beyond the sequence length output zeros and keep the state
+    let input_weights = Tensor::from_contiguous(&[0.5, -0.4], &[2, 1, 1]);
+    let recurrent_weights = Tensor::from_contiguous(&[-0.3, 0.6], &[2, 1, 1]);
+    let bias = Tensor::from_contiguous(&[0.1, 0.2, 0.0, -0.1], &[2, 2]);
+    let sequence_lens = Tensor::from_contiguous(&[3, 2], &[2]);
+    let config = RecurrentConfig {
+        input_weights: &input_weights,
+        recurrent_weights: &recurrent_weights,
+        bias: Some(&bias),
+        sequence_lens: Some(&sequence_lens),
+        direction: RecurrentDirection::Bidirectional,
+        activations: &[RecurrentActivation::Tanh],
+        clip: None,
+    };
+    let (result, hidden) = input.RNN(config, None);
+    let result = result.evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[3, 2, 2, 1]);
+    let output = result.load();
+    bytemuck::cast_slice::<u8, f32>(&output[..])
+        .iter()
+        .zip([
+            0.66404, 0.50052, -0.77526, -0.12456, 0.80078, -0.33651, -0.88898, 0.29131, 0.91538,
+            0.0, -0.86172, 0.0,
+        ])
+        .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+    let result = hidden.evaluate_webgpu(&wgpu_device);
+    assert_eq!(result.shape(), &[2, 2, 1]);
+    let output = result.load();
+    bytemuck::cast_slice::<u8, f32>(&output[..])
+        .iter()
+        .zip([0.91538, -0.33651, -0.77526, -0.12456])
+        .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+
+    let input_weights = Tensor::from_contiguous(&[0.3, -0.2, 0.5, 0.7], &[1, 4, 1]);
+    let recurrent_weights = Tensor::from_contiguous(&[0.1, 0.4, -0.3, 0.2], &[1, 4, 1]);
+    let bias = Tensor::from_contiguous(&[0.1, 0.0, 0.0, 0.2, 0.0, 0.1, 0.0, 0.0], &[1, 8]);
+    let peepholes = Tensor::from_contiguous(&[0.5, -0.5, 0.25], &[1, 3]);
+    let initial_h = Tensor::from_contiguous(&[0.1, -0.1], &[1, 2, 1]);
+    let initial_c = Tensor::from_contiguous(&[0.2, 0.3], &[1, 2, 1]);
+    let config = RecurrentConfig {
+        input_weights: &input_weights,
+        recurrent_weights: &recurrent_weights,
+        bias: Some(&bias),
+        sequence_lens: None,
+        direction: RecurrentDirection::Forward,
+        activations: &[
+            RecurrentActivation::Sigmoid,
+            RecurrentActivation::Tanh,
+            RecurrentActivation::Tanh,
+        ],
+        clip: None,
+    };
+    let (result, hidden, cell) = input.LSTM(
+        config,
+        Some(&initial_h),
+        Some(&initial_c),
+        Some(&peepholes),
+        false,
+    );
+    let result = result.evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    bytemuck::cast_slice::<u8, f32>(&output[..])
+        .iter()
+        .zip([0.21582, 0.18791, 0.25476, -0.02337, 0.2056, 0.18573])
+        .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+    let result = hidden.evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    bytemuck::cast_slice::<u8, f32>(&output[..])
+        .iter()
+        .zip([0.2056, 0.18573])
+        .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+    let result = cell.evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    bytemuck::cast_slice::<u8, f32>(&output[..])
+        .iter()
+        .zip([1.7555, 0.5823])
+        .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+
+    let input_weights = Tensor::from_contiguous(&[0.4, -0.6, 0.8], &[1, 3, 1]);
+    let recurrent_weights = Tensor::from_contiguous(&[0.3, 0.5, -0.7], &[1, 3, 1]);
+    let bias = Tensor::from_contiguous(&[0.1, 0.2, -0.1, 0.05, -0.05, 0.3], &[1, 6]);
+    let config = RecurrentConfig {
+        input_weights: &input_weights,
+        recurrent_weights: &recurrent_weights,
+        bias: Some(&bias),
+        sequence_lens: None,
+        direction: RecurrentDirection::Forward,
+        activations: &[RecurrentActivation::Sigmoid, RecurrentActivation::Tanh],
+        clip: None,
+    };
+    for (linear_before_reset, expected) in [
+        (
+            false,
+            [0.27864, 0.22201, 0.45236, -0.23214, 0.55114, 0.11454],
+        ),
+        (
+            true,
+            [0.24635, 0.17057, 0.42212, -0.27997, 0.52587, 0.07651],
+        ),
+    ] {
+        let (result, _) = input.GRU(config, None, linear_before_reset);
+        let result = result.evaluate_webgpu(&wgpu_device);
+        let output = result.load();
+        bytemuck::cast_slice::<u8, f32>(&output[..])
+            .iter()
+            .zip(expected)
+            .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+    }
+}
+
+#[tokio::test]
+async fn attention() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    // Key and value broadcast against the batch dimension of the query
+    let query = Tensor::from_contiguous(&[1.0, 0.5, -0.5, 2.0], &[1, 2, 2]);
+    let key = Tensor::from_contiguous(&[0.5, 1.0, -1.0, 0.25, 2.0, -0.5], &[3, 2]);
+    let value = Tensor::from_contiguous(&[1.0, 2.0, 0.0, -1.0, 3.0, 0.5], &[3, 2]);
+    let keep = Tensor::from_contiguous(&[1, 0, 1, 1, 1, 0], &[2, 3]);
+    let bias = Tensor::from_contiguous(&[0.5, 0.0, -1.0, 0.0, 1.0, 0.0], &[2, 3]);
+
+    for ((mask, causal, scale), expected) in [
+        ((None, false, None), [2.05677, 0.87153, 0.73035, 0.87215]),
+        (
+            (Some(&keep), false, None),
+            [2.25912, 1.05566, 0.62956, 0.88868],
+        ),
+        ((None, true, None), [1.0, 2.0, 0.62956, 0.88868]),
+        (
+            (Some(&bias), false, Some(0.5)),
+            [1.2636, 1.23213, 0.48321, 0.06898],
+        ),
+    ] {
+        let result = Tensor::ScaledDotProductAttention(&query, &key, &value, mask, causal, scale);
+        let result = result.evaluate_webgpu(&wgpu_device);
+        assert_eq!(result.shape(), &[1, 2, 2]);
+        let output = result.load();
+        bytemuck::cast_slice::<u8, f32>(&output[..])
+            .iter()
+            .zip(expected)
+            .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+    }
+}
+
+#[tokio::test]
+async fn spectral() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let signal = Tensor::from_contiguous(&[1.0, 2.0, 3.0, 4.0, 0.0, -1.0, 2.0, 0.5], &[1, 8, 1]);
+    let complex = Tensor::from_contiguous(
+        &[
+            1.0, 0.5, -1.0, 2.0, 0.5, 0.0, 2.0, -1.0, 0.0, 1.0, -0.5, -0.5,
+        ],
+        &[6, 1, 2],
+    );
+    let spectrum = Tensor::from_contiguous(&[1.0, 0.0, 0.0, 1.0, 2.0, 0.0, -1.0, -1.0], &[4, 2]);
+    let window = Tensor::HannWindow(4, true);
+
+    // Radix-2, mixed radix and prime lengths
+    for (result, shape, expected) in [
+        (
+            signal.DFT(1, None, false, true),
+            vec![1, 5, 2],
+            vec![
+                11.5, 0.0, 0.6464, -5.5962, -4.0, 3.5, 1.3536, -3.5962, 0.5, 0.0,
+            ],
+        ),
+        (
+            complex.DFT(0, None, false, false),
+            vec![6, 1, 2],
+            vec![
+                2.0, 2.0, -0.701, 1.75, 6.5311, -0.884, 1.0, 1.0, 0.4689, -2.616, -3.299, 1.75,
+            ],
+        ),
+        (
+            spectrum.DFT(0, Some(5), true, false),
+            vec![5, 2],
+            vec![
+                0.4, 0.0, -0.2696, 0.5763, 0.3345, -0.7942, 0.1891, 0.347, 0.346, -0.1291,
+            ],
+        ),
+        (
+            signal.STFT(2, Some(&window), None, true),
+            vec![1, 3, 3, 2],
+            vec![
+                6.0, 0.0, -3.0, 1.0, 0.0, 0.0, 1.5, 0.0, 0.0, -2.5, -1.5, 0.0, 1.75, 0.0, -2.0,
+                0.75, 2.25, 0.0,
+            ],
+        ),
+        (window, vec![4], vec![0.0, 0.5, 1.0, 0.5]),
+        (
+            Tensor::HammingWindow(5, false),
+            vec![5],
+            vec![0.08696, 0.54348, 1.0, 0.54348, 0.08696],
+        ),
+        (
+            Tensor::BlackmanWindow(4, true),
+            vec![4],
+            vec![0.0, 0.34, 1.0, 0.34],
+        ),
+    ] {
+        let result = result.evaluate_webgpu(&wgpu_device);
+        assert_eq!(result.shape(), &shape[..]);
+        let output = result.load();
+        bytemuck::cast_slice::<u8, f32>(&output[..])
+            .iter()
+            .zip(expected)
+            .for_each(|(&x, y)| assert!((x - y).abs() < 1e-3, "{} != {}", x, y));
+    }
+}
+
+#[tokio::test]
+async fn linalg() {
+    use dtensor::primitives::tensor::TensorInput;
+
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    // The second matrix needs a row exchange and the third is singular
+    let matrices = Tensor::from_contiguous(
+        &[
+            2.0, 1.0, 1.0, 4.0, 3.0, 3.0, 8.0, 7.0, 9.0, 0.0, 1.0, 2.0, 1.0, 0.0, 3.0, 4.0, -3.0,
+            8.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0,
+        ],
+        &[3, 3, 3],
+    );
+    let invertible = matrices.Split(0, &[2, 1])[0].clone();
+    let rhs = Tensor::from_contiguous(&[1.0, 2.0, 0.0, -1.0, 3.0, 1.0], &[3, 2]);
+    let symmetric =
+        Tensor::from_contiguous(&[4.0, 2.0, 2.0, 2.0, 5.0, 3.0, 2.0, 3.0, 6.0], &[3, 3]);
+    // Upper and lower triangles differ so only one of them may be read
+    let triangular =
+        Tensor::from_contiguous(&[2.0, 1.0, -1.0, 1.0, 3.0, 2.0, -1.0, 2.0, 4.0], &[3, 3]);
+
+    for (result, shape, expected) in [
+        (matrices.Det(), vec![3], vec![4.0, -2.0, 0.0]),
+        (
+            invertible.Inverse(),
+            vec![2, 3, 3],
+            vec![
+                1.5, -0.5, 0.0, -3.0, 2.5, -0.5, 1.0, -1.5, 0.5, -4.5, 7.0, -1.5, -2.0, 4.0, -1.0,
+                1.5, -2.0, 0.5,
+            ],
+        ),
+        (
+            invertible.Solve(&rhs),
+            vec![2, 3, 2],
+            vec![
+                1.5, 3.5, -4.5, -9.0, 2.5, 4.0, -9.0, -17.5, -5.0, -9.0, 3.0, 5.5,
+            ],
+        ),
+        (
+            symmetric.Cholesky(false),
+            vec![3, 3],
+            vec![2.0, 0.0, 0.0, 1.0, 2.0, 0.0, 1.0, 1.0, 2.0],
+        ),
+        (
+            symmetric.Cholesky(true),
+            vec![3, 3],
+            vec![2.0, 1.0, 1.0, 0.0, 2.0, 1.0, 0.0, 0.0, 2.0],
+        ),
+        (
+            triangular.TriangularSolve(&rhs, true, false),
+            vec![3, 2],
+            vec![1.125, 1.375, -0.5, -0.5, 0.75, 0.25],
+        ),
+        (
+            triangular.TriangularSolve(&rhs, false, false),
+            vec![3, 2],
+            vec![0.5, 1.0, -0.16667, -0.66667, 0.95833, 0.83333],
+        ),
+        (
+            triangular.TriangularSolve(&rhs, false, true),
+            vec![3, 2],
+            vec![1.0, 2.0, -1.0, -3.0, 6.0, 9.0],
+        ),
+    ] {
+        let result = result.evaluate_webgpu(&wgpu_device);
+        assert_eq!(result.shape(), &shape[..]);
+        let output = result.load();
+        bytemuck::cast_slice::<u8, f32>(&output[..])
+            .iter()
+            .zip(expected)
+            .for_each(|(&x, y)| assert!((x - y).abs() < 1e-4, "{} != {}", x, y));
+    }
+
+    // Det, Inverse and Solve differentiate through LU and TriangularSolve, so their
+    // gradients of the summed output are checked against central differences
+    let epsilon = 1e-2;
+    let perturbed = |input: &Tensor| {
+        let size = input.shape().iter().product::<i32>();
+        let deltas = (0..2 * size)
+            .flat_map(|batch| {
+                let delta = if batch % 2 == 0 { epsilon } else { -epsilon };
+                (0..size).map(move |index| if index == batch / 2 { delta } else { 0.0 })
+            })
+            .collect::<Vec<_>>();
+        input.Add(&Tensor::from_contiguous(
+            &deltas,
+            &[&[2 * size], input.shape()].concat(),
+        ))
+    };
+    let assert_differences = |gradient: &Tensor, sums: &Tensor| {
+        let gradient = gradient.evaluate_webgpu(&wgpu_device).load();
+        let sums = sums.evaluate_webgpu(&wgpu_device).load();
+        bytemuck::cast_slice::<u8, f32>(&gradient[..])
+            .iter()
+            .zip(bytemuck::cast_slice::<u8, f32>(&sums[..]).chunks(2))
+            .for_each(|(&x, pair)| {
+                let y = (pair[0] - pair[1]) / (2.0 * epsilon);
+                assert!((x - y).abs() < 1e-2, "{} != {}", x, y)
+            });
+    };
+
+    let matrix = invertible.Split(0, &[1, 1])[0].squeeze(0);
+    let (factors, _) = matrix.LU(true);
+    let TensorInput::NoOp(augmented) = factors.data() else {
+        panic!("LU factors are expected to be a view of the augmented output");
+    };
+    let gradients = augmented.backward();
+    let sums = perturbed(&matrix).LU(true).0.Sum(&[1, 2], false);
+    assert_differences(gradients[0].gradient(), &sums);
+
+    for (upper, unit_diagonal) in [(true, false), (false, false), (false, true)] {
+        let gradients = triangular
+            .TriangularSolve(&rhs, upper, unit_diagonal)
+            .backward();
+        let sums = perturbed(&triangular)
+            .TriangularSolve(&rhs, upper, unit_diagonal)
+            .Sum(&[1, 2], false);
+        assert_differences(gradients[0].gradient(), &sums);
+
+        let sums = triangular
+            .TriangularSolve(&perturbed(&rhs), upper, unit_diagonal)
+            .Sum(&[1, 2], false);
+        assert_differences(gradients[1].gradient(), &sums);
+    }
+}
+
+#[tokio::test]
+async fn quantization() {
+    use dtensor::primitives::tensor::{ConvConfig, ConvPadding, Quantization, TensorType};
+
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+    let quantized =
+        |data: &[i32], shape: &[i32], datatype| Tensor::from_contiguous(data, shape).Cast(datatype);
+
+    // Halfway cases round to even before saturating
+    let x = Tensor::from_contiguous(&[0.5, 1.5, 2.5, -0.5, 300.0, -3.0], &[6]);
+    let per_tensor = x.QuantizeLinear(
+        Quantization {
+            scale: &Tensor::scalar(1.0),
+            zero_point: Some(&quantized(&[128], &[], TensorType::U8)),
+        },
+        0,
+        TensorType::U8,
+    );
+
+    // Scales and zero points along the columns
+    let x = Tensor::from_contiguous(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+    let scale = Tensor::from_contiguous(&[0.5, 1.0, 2.0], &[3]);
+    let zero_point = quantized(&[0, -1, 1], &[3], TensorType::I8);
+    let per_axis = Quantization {
+        scale: &scale,
+        zero_point: Some(&zero_point),
+    };
+    let per_axis_result = x.QuantizeLinear(per_axis, 1, TensorType::I8);
+    let dequantized = per_axis_result
+        .DequantizeLinear(per_axis, 1)
+        .evaluate_webgpu(&wgpu_device);
+    let output = dequantized.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[1.0, 2.0, 4.0, 4.0, 5.0, 6.0]
+    );
+
+    let a = quantized(&[130, 128, 126, 129, 131, 128], &[2, 3], TensorType::U8);
+    let a_zero_point = quantized(&[128], &[], TensorType::U8);
+    let b = quantized(&[1, -2, 3, 4, -1, 0], &[3, 2], TensorType::I8);
+    let b_zero_point = quantized(&[1, 0], &[2], TensorType::I8);
+    let b_scale = Tensor::from_contiguous(&[0.25, 0.5], &[2]);
+    let y_zero_point = quantized(&[10], &[], TensorType::U8);
+
+    // Padding with the zero point contributes nothing to the accumulator
+    let input = quantized(&[1, 2, 3, 4, 5, 6, 7, 8, 9], &[1, 1, 3, 3], TensorType::U8);
+    let input_zero_point = quantized(&[1], &[], TensorType::U8);
+    let kernel = quantized(&[1, 2, 0, -1], &[1, 1, 2, 2], TensorType::I8);
+    let padding = [(0, 0), (0, 0), (1, 0), (0, 0)];
+    let config = ConvConfig {
+        kernel_strides: &[1, 1],
+        dilations: &[1, 1],
+        group: 1,
+        padding: ConvPadding::Custom(&padding),
+    };
+
+    for (result, datatype, shape, expected) in [
+        (
+            per_tensor,
+            TensorType::U8,
+            vec![6],
+            vec![128, 130, 130, 128, 255, 125],
+        ),
+        (
+            per_axis_result,
+            TensorType::I8,
+            vec![2, 3],
+            vec![2, 1, 3, 8, 4, 4],
+        ),
+        (
+            a.MatMulInteger(&b, Some(&a_zero_point), Some(&b_zero_point)),
+            TensorType::I32,
+            vec![2, 2],
+            vec![4, -4, 6, 10],
+        ),
+        (
+            a.QLinearMatMul(
+                Quantization {
+                    scale: &Tensor::scalar(0.5),
+                    zero_point: Some(&a_zero_point),
+                },
+                &b,
+                Quantization {
+                    scale: &b_scale,
+                    zero_point: Some(&b_zero_point),
+                },
+                Quantization {
+                    scale: &Tensor::scalar(0.25),
+                    zero_point: Some(&y_zero_point),
+                },
+            ),
+            TensorType::U8,
+            vec![2, 2],
+            vec![12, 6, 13, 20],
+        ),
+        (
+            input.ConvInteger(&kernel, Some(&input_zero_point), None, config),
+            TensorType::I32,
+            vec![1, 1, 3, 2],
+            vec![-1, -2, -2, 0, 4, 6],
+        ),
+        (
+            input.QLinearConv(
+                Quantization {
+                    scale: &Tensor::scalar(0.5),
+                    zero_point: Some(&input_zero_point),
+                },
+                &kernel,
+                Quantization {
+                    scale: &Tensor::from_contiguous(&[0.25], &[1]),
+                    zero_point: None,
+                },
+                Quantization {
+                    scale: &Tensor::scalar(0.5),
+                    zero_point: Some(&quantized(&[0], &[], TensorType::I8)),
+                },
+                Some(&Tensor::from_contiguous(&[4], &[1])),
+                config,
+            ),
+            TensorType::I8,
+            vec![1, 1, 3, 2],
+            vec![1, 0, 0, 1, 2, 2],
+        ),
+    ] {
+        assert_eq!(result.datatype(), datatype);
+        let result = result.evaluate_webgpu(&wgpu_device);
+        assert_eq!(result.shape(), &shape[..]);
+        let output = result.load();
+        assert_eq!(bytemuck::cast_slice::<u8, i32>(&output[..]), &expected[..]);
+    }
+
+    // 8-bit results wrap around like their native types
+    let u8_lhs = quantized(&[5, 0, 200], &[3], TensorType::U8);
+    let u8_rhs = quantized(&[100, 1, 100], &[3], TensorType::U8);
+    let i8_lhs = quantized(&[100, -128, 1], &[3], TensorType::I8);
+    let i8_rhs = quantized(&[100, -1, 7], &[3], TensorType::I8);
+    for (result, expected) in [
+        (u8_lhs.BitwiseNot(), [250, 255, 55]),
+        (u8_lhs.Add(&u8_rhs), [105, 1, 44]),
+        (u8_lhs.Multiply(&u8_rhs), [244, 0, 32]),
+        (i8_lhs.Add(&i8_rhs), [-56, 127, 8]),
+        (i8_lhs.Multiply(&i8_rhs).Abs(), [16, -128, 7]),
+        (
+            i8_lhs.BitShiftLeft(&quantized(&[1, 1, 7], &[3], TensorType::I8)),
+            [-56, 0, -128],
+        ),
+    ] {
+        let output = result.evaluate_webgpu(&wgpu_device).load();
+        assert_eq!(bytemuck::cast_slice::<u8, i32>(&output[..]), &expected[..]);
+    }
+}
+
+#[tokio::test]
+async fn where_select() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let condition = Tensor::from_contiguous(&[1, 0, 1, 0], &[4]);
+    let result = condition
+        .Where(&Tensor::arange(&[2, 4]), &Tensor::scalar(10))
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 10, 2, 10, 4, 10, 6, 10]
+    );
+
+    // Fractional conditions select integer branches by being nonzero
+    let result = Tensor::from_contiguous(&[0.5, 0.0, -0.25], &[3])
+        .Where(
+            &Tensor::from_contiguous(&[1, 1, 1], &[3]),
+            &Tensor::from_contiguous(&[2, 2, 2], &[3]),
+        )
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, i32>(&output[..]), &[1, 2, 1]);
+
+    // The unselected branch is never evaluated into the output
+    let input = Tensor::from_contiguous(&[-2.0, 0.0, 3.0], &[3]);
+    let zero = Tensor::scalar(0.0);
+    let result = input
+        .Equal(&zero)
+        .Where(&zero, &Tensor::scalar(1.0).Divide(&input))
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[-0.5, 0.0, 1.0 / 3.0]
+    );
+
+    let result = input
+        .Clip(&Tensor::scalar(-1.0), &Tensor::scalar(2.0))
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[-1.0, 0.0, 2.0]
+    );
+
+    let result = input.Relu().evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[0.0, 0.0, 3.0]
+    );
+}
+
+#[tokio::test]
+async fn reductions() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let input = Tensor::from_contiguous(&[3.0, -1.0, 2.0, 0.0, 5.0, 5.0], &[2, 3]);
+
+    let result = input.Prod(&[1], false).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, f32>(&output[..]), &[-6.0, 0.0]);
+
+    let result = input.Min(&[0], false).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[0.0, -1.0, 2.0]
+    );
+
+    let result = input.Any(&[0], false).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[1.0, 1.0, 1.0]
+    );
+
+    let result = input.All(&[1], false).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, f32>(&output[..]), &[1.0, 0.0]);
+
+    let result = input.ArgMax(1, false, false).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, f32>(&output[..]), &[0.0, 1.0]);
+
+    let result = input.ArgMax(1, false, true).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, f32>(&output[..]), &[0.0, 2.0]);
+
+    let result = input.ArgMin(0, true, false).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[1.0, 0.0, 0.0]
+    );
+
+    // Rows without zeros, with a single zero and with several zeros
+    let input = Tensor::from_contiguous(&[3.0, -1.0, 2.0, 0.0, 5.0, 4.0, 0.0, 2.0, 0.0], &[3, 3]);
+    let gradients = input.Prod(&[1], true).backward();
+    let result = gradients[0].gradient().evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[-2.0, 6.0, -3.0, 20.0, 0.0, 0.0, 0.0, 0.0, 0.0]
+    );
+}
+
+#[tokio::test]
+async fn scan() {
+    let wgpu_device = common::wgpu_setup().await.unwrap();
+
+    let input = Tensor::arange(&[2, 3]);
+    let result = input.CumSum(1, false, false).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 1, 3, 3, 7, 12]
+    );
+
+    let result = input.CumSum(0, true, false).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[0, 0, 0, 0, 1, 2]
+    );
+
+    let result = input.CumSum(1, true, true).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[3, 2, 0, 9, 5, 0]
+    );
+
+    let input = Tensor::from_contiguous(&[2.0, 1.0, 3.0, 0.5, 4.0], &[5]);
+    let result = input.CumProd(0, false, true).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[12.0, 6.0, 6.0, 2.0, 4.0]
+    );
+
+    let result = input.CumMax(0, false).evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[2.0, 2.0, 3.0, 3.0, 4.0]
+    );
+
+    // Lines longer than a workgroup
+    let result = Tensor::arange(&[3, 200])
+        .CumSum(1, false, false)
+        .evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(bytemuck::cast_slice::<u8, i32>(&output[..])[599], 99900);
+
+    let input = Tensor::from_contiguous(&[2.0, 0.0, 3.0, 1.0, 2.0, 3.0], &[2, 3]);
+    let gradients = input.CumSum(1, false, false).backward();
+    let result = gradients[0].gradient().evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, i32>(&output[..]),
+        &[3, 2, 1, 3, 2, 1]
+    );
+
+    // Products through a zero
+    let gradients = input.CumProd(1, false, false).backward();
+    let result = gradients[0].gradient().evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[1.0, 8.0, 0.0, 9.0, 4.0, 2.0]
+    );
+
+    let gradients = input.CumProd(1, true, true).backward();
+    let result = gradients[0].gradient().evaluate_webgpu(&wgpu_device);
+    let output = result.load();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, f32>(&output[..]),
+        &[0.0, 3.0, 1.0, 0.0, 3.0, 3.0]
+    );
+}